@@ -0,0 +1,30 @@
+package com.sergioribera.biblion
+
+import android.app.NativeActivity
+import android.os.Bundle
+
+/**
+ * NativeActivity that tells the app when Android pauses it or asks it to save its state, the
+ * last moments the app is sure to run before its process can be killed.
+ */
+class BiblionActivity : NativeActivity() {
+    companion object {
+        init {
+            // NativeActivity opens the library on its own, which does not make its JNI
+            // functions visible to this class
+            System.loadLibrary("biblion")
+        }
+    }
+
+    private external fun saveState()
+
+    override fun onPause() {
+        super.onPause()
+        saveState()
+    }
+
+    override fun onSaveInstanceState(outState: Bundle) {
+        super.onSaveInstanceState(outState)
+        saveState()
+    }
+}
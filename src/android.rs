@@ -0,0 +1,169 @@
+//! Access to the Android activity the app runs in
+
+use std::sync::OnceLock;
+
+use freya::prelude::Gaps;
+use jni::objects::{JObject, JString, JValue};
+use jni::{JNIEnv, JavaVM};
+use winit::platform::android::activity::AndroidApp;
+
+static APP: OnceLock<AndroidApp> = OnceLock::new();
+
+/// Called by `BiblionActivity` when Android pauses the app or asks it to save its state, so
+/// what is only kept to be written later reaches the disk before the app can be killed
+#[unsafe(no_mangle)]
+extern "system" fn Java_com_sergioribera_biblion_BiblionActivity_saveState(
+    _env: JNIEnv,
+    _activity: JObject,
+) {
+    crate::session::Session::flush();
+}
+
+/// Keeps the handle of the activity, set once by `android_main`
+pub fn init(app: AndroidApp) {
+    let _ = APP.set(app);
+}
+
+pub fn app() -> Option<&'static AndroidApp> {
+    APP.get()
+}
+
+/// Space of the window covered by the status and navigation bars and by the keyboard, in
+/// logical pixels. The activity reports the part of the window left for its content.
+pub fn insets() -> Gaps {
+    let none = Gaps::new(0., 0., 0., 0.);
+    let Some(app) = app() else {
+        return none;
+    };
+    let Some(window) = app.native_window() else {
+        return none;
+    };
+    let content = app.content_rect();
+    if content.right <= content.left || content.bottom <= content.top {
+        return none;
+    }
+    // Android densities are relative to 160 dpi
+    let scale = app.config().density().map_or(1., |dpi| dpi as f32 / 160.);
+    Gaps::new(
+        content.top.max(0) as f32 / scale,
+        (window.width() - content.right).max(0) as f32 / scale,
+        (window.height() - content.bottom).max(0) as f32 / scale,
+        content.left.max(0) as f32 / scale,
+    )
+}
+
+/// Runs `f` with the Java environment of the current thread and the activity. Java exceptions
+/// are cleared so they do not abort the next call.
+fn with_activity<T>(
+    f: impl FnOnce(&mut JNIEnv, &JObject) -> jni::errors::Result<T>,
+) -> jni::errors::Result<T> {
+    let app = app().ok_or(jni::errors::Error::NullPtr("activity"))?;
+    // SAFETY: both pointers are owned by the activity, which lives as long as the app
+    let vm = unsafe { JavaVM::from_raw(app.vm_as_ptr().cast()) }?;
+    let activity = unsafe { JObject::from_raw(app.activity_as_ptr().cast()) };
+    let mut env = vm.attach_current_thread()?;
+    let result = f(&mut env, &activity);
+    if let Err(jni::errors::Error::JavaException) = result {
+        let _ = env.exception_describe();
+        let _ = env.exception_clear();
+    }
+    result
+}
+
+/// Copies HTML to the clipboard, with the plain text apps that do not read HTML paste instead
+pub fn copy_html(html: &str, plain: &str) -> jni::errors::Result<()> {
+    with_activity(|env, activity| {
+        let service = env.new_string("clipboard")?;
+        let clipboard = env
+            .call_method(
+                activity,
+                "getSystemService",
+                "(Ljava/lang/String;)Ljava/lang/Object;",
+                &[JValue::from(&service)],
+            )?
+            .l()?;
+        let label = env.new_string("Verses")?;
+        let plain = env.new_string(plain)?;
+        let html = env.new_string(html)?;
+        let clip = env
+            .call_static_method(
+                "android/content/ClipData",
+                "newHtmlText",
+                "(Ljava/lang/CharSequence;Ljava/lang/CharSequence;Ljava/lang/String;)Landroid/content/ClipData;",
+                &[JValue::from(&label), JValue::from(&plain), JValue::from(&html)],
+            )?
+            .l()?;
+        env.call_method(
+            &clipboard,
+            "setPrimaryClip",
+            "(Landroid/content/ClipData;)V",
+            &[JValue::from(&clip)],
+        )?;
+        Ok(())
+    })
+}
+
+/// Opens the share sheet of the system to send the text to another app
+pub fn share_text(text: &str) -> jni::errors::Result<()> {
+    with_activity(|env, activity| {
+        let action = env.new_string("android.intent.action.SEND")?;
+        let intent = env.new_object(
+            "android/content/Intent",
+            "(Ljava/lang/String;)V",
+            &[JValue::from(&action)],
+        )?;
+        let mime = env.new_string("text/plain")?;
+        env.call_method(
+            &intent,
+            "setType",
+            "(Ljava/lang/String;)Landroid/content/Intent;",
+            &[JValue::from(&mime)],
+        )?;
+        let extra = env.new_string("android.intent.extra.TEXT")?;
+        let text = env.new_string(text)?;
+        env.call_method(
+            &intent,
+            "putExtra",
+            "(Ljava/lang/String;Ljava/lang/String;)Landroid/content/Intent;",
+            &[JValue::from(&extra), JValue::from(&text)],
+        )?;
+        let chooser = env
+            .call_static_method(
+                "android/content/Intent",
+                "createChooser",
+                "(Landroid/content/Intent;Ljava/lang/CharSequence;)Landroid/content/Intent;",
+                &[JValue::from(&intent), JValue::from(&JObject::null())],
+            )?
+            .l()?;
+        env.call_method(
+            activity,
+            "startActivity",
+            "(Landroid/content/Intent;)V",
+            &[JValue::from(&chooser)],
+        )?;
+        Ok(())
+    })
+}
+
+/// `biblion://` link the activity was started for, if it was opened from one
+pub fn launch_uri() -> Option<String> {
+    with_activity(|env, activity| {
+        let intent = env
+            .call_method(activity, "getIntent", "()Landroid/content/Intent;", &[])?
+            .l()?;
+        if intent.is_null() {
+            return Ok(None);
+        }
+        let data = env
+            .call_method(&intent, "getDataString", "()Ljava/lang/String;", &[])?
+            .l()?;
+        if data.is_null() {
+            return Ok(None);
+        }
+        let data = env.get_string(&JString::from(data))?;
+        Ok(Some(data.into()))
+    })
+    .inspect_err(|e| tracing::error!("Failed to read the link the app was opened for: {e}"))
+    .ok()
+    .flatten()
+}
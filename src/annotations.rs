@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::Catalog;
+use crate::reference::{PassageRef, VerseRef};
+use crate::utils::{data_dir, load_json, save_json};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum HighlightColor {
+    Yellow,
+    Green,
+    Blue,
+    Pink,
+}
+
+impl HighlightColor {
+    pub const ALL: [HighlightColor; 4] = [
+        HighlightColor::Yellow,
+        HighlightColor::Green,
+        HighlightColor::Blue,
+        HighlightColor::Pink,
+    ];
+
+    pub fn hex(&self) -> &'static str {
+        match self {
+            HighlightColor::Yellow => "#E6C07B",
+            HighlightColor::Green => "#98C379",
+            HighlightColor::Blue => "#6FA8DC",
+            HighlightColor::Pink => "#E586B4",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Highlight {
+    pub passage: PassageRef,
+    pub color: HighlightColor,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Note {
+    pub passage: PassageRef,
+    pub text: String,
+    /// Seconds since the Unix epoch
+    pub created: u64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Bookmark {
+    pub passage: PassageRef,
+    pub created: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Highlights, notes and bookmarks made by the user, stored in the data dir
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+pub struct Annotations {
+    #[serde(default)]
+    pub highlights: Vec<Highlight>,
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+}
+
+impl Annotations {
+    fn path() -> PathBuf {
+        data_dir(&["annotations.json"])
+    }
+
+    pub fn load() -> Self {
+        load_json(&Self::path())
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = save_json(&Self::path(), self) {
+            tracing::error!("Failed to save annotations: {e}");
+        }
+    }
+
+    /// Highlights the passage, replacing the highlights it overlaps
+    pub fn highlight(&mut self, passage: PassageRef, color: HighlightColor) {
+        self.clear_highlight(&passage);
+        self.highlights.push(Highlight { passage, color });
+    }
+
+    pub fn clear_highlight(&mut self, passage: &PassageRef) {
+        self.highlights.retain(|h| !h.passage.overlaps(passage));
+    }
+
+    pub fn add_note(&mut self, passage: PassageRef, text: impl Into<String>) {
+        self.notes.push(Note {
+            passage,
+            text: text.into(),
+            created: now(),
+        });
+    }
+
+    /// Bookmarks the passage, or removes its bookmark. Returns whether it is bookmarked now
+    pub fn toggle_bookmark(&mut self, passage: PassageRef) -> bool {
+        let len = self.bookmarks.len();
+        self.bookmarks.retain(|b| b.passage != passage);
+        if self.bookmarks.len() != len {
+            return false;
+        }
+        self.bookmarks.push(Bookmark {
+            passage,
+            created: now(),
+        });
+        true
+    }
+
+    pub fn highlight_at(&self, verse: &VerseRef) -> Option<HighlightColor> {
+        self.highlights
+            .iter()
+            .rev()
+            .find(|h| h.passage.contains(verse))
+            .map(|h| h.color)
+    }
+
+    /// Notes of the verse, including the ones written in other Bibles, matched through their
+    /// versification
+    pub fn notes_at(&self, catalog: &Catalog, verse: &VerseRef) -> Vec<&Note> {
+        self.notes
+            .iter()
+            .filter(|n| {
+                n.passage
+                    .contains(&verse.in_bible(catalog, &n.passage.bible_id))
+            })
+            .collect()
+    }
+
+    pub fn is_bookmarked(&self, verse: &VerseRef) -> bool {
+        self.bookmarks.iter().any(|b| b.passage.contains(verse))
+    }
+}
@@ -1,59 +1,299 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
+use async_io::Timer;
 use freya::animation::*;
 use freya::prelude::*;
 use freya::radio::*;
+use futures::StreamExt;
+use kanal::{Receiver, Sender, unbounded};
 use setup_core::TantivySink;
 
 use crate::AppChannel;
 use crate::AppState;
+use crate::annotations::Annotations;
+use crate::catalog::{BibleCatalog, Canon, Catalog, last_chapters};
 use crate::components::AutoCompleteInput;
-use crate::components::{Toolbar, ToolbarItem, VersePanel};
-use crate::dialog::manage_bibles;
+use crate::components::{
+    MobileScreen, MobileShell, NotesPanel, Toolbar, ToolbarItem, VersePanel, empty_state,
+    is_mobile_layout,
+};
+use crate::dialog::{
+    export_passages, manage_bibles, manage_modules, memory_verses, reading_plans, storage_location,
+    topic_browser,
+};
+use crate::history::{NavigationHistory, go_back, go_forward, navigate};
+use crate::memory::MemoryVerses;
+use crate::plans::{PlanProgress, ReadingPlan};
+use crate::reference::VerseRef;
+use crate::session::{Session, Tab};
+use crate::settings::{Settings, SplitDirection};
+use crate::storage::{external_data_dir, index_dir};
 use crate::utils::data_dir;
+use crate::versification::Versification;
+
+/// Passage of the `biblion://` link the app was opened for. Windows and Linux pass the link as
+/// an argument and Android in the intent of the activity. macOS sends links in an Apple event
+/// that winit does not deliver, so the app does not register the scheme there.
+fn launch_link() -> Option<VerseRef> {
+    #[cfg(target_os = "android")]
+    return crate::android::launch_uri().and_then(|uri| VerseRef::from_uri(&uri));
+    #[cfg(not(target_os = "android"))]
+    std::env::args()
+        .skip(1)
+        .find_map(|arg| VerseRef::from_uri(&arg))
+}
+
+/// Bibles installed in the cache, numbered with the schemes chosen in the settings
+fn installed_catalog(versifications: &BTreeMap<String, Versification>) -> Catalog {
+    let setup = setup_core::SetupBuilder::new()
+        .cache_path(data_dir(&["cache"]))
+        .build()
+        .1;
+    Catalog::from_installed(
+        setup.list_installed_books().unwrap_or_default(),
+        versifications,
+    )
+}
 
 pub fn init() -> impl IntoElement {
-    use_init_radio_station::<AppState, AppChannel>(AppState::default);
-    let radio = use_radio::<AppState, AppChannel>(AppChannel::BooksSuggesions);
+    use_init_radio_station::<AppState, AppChannel>(|| {
+        let settings = Settings::load();
+        AppState {
+            annotations: Annotations::load(),
+            memory: MemoryVerses::load(),
+            index_dir: index_dir(settings.index_storage),
+            settings,
+            ..Default::default()
+        }
+    });
+    let mut radio = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+    let mut settings = use_radio::<AppState, AppChannel>(AppChannel::Settings);
 
     let mut theme = use_init_root_theme(|| PreferredTheme::Dark.to_theme());
-    let mut show_bible_manager = use_state(|| false);
-    let search_state = use_state(String::new);
+    let show_bible_manager = use_state(|| false);
+    let show_module_manager = use_state(|| false);
+    let show_export = use_state(|| false);
+    let show_memory = use_state(|| false);
+    let show_topics = use_state(|| false);
+    let show_storage = use_state(|| false);
+    // Today's reading of the active plan is shown on startup until it is read
+    let show_plans = use_state(|| {
+        PlanProgress::load()
+            .pending_today(&ReadingPlan::all())
+            .is_some()
+    });
+    // Tabs of the last run, plus one for the link the app was opened with
+    let mut session = use_state(|| {
+        let mut session = Session::load();
+        if let Some(verse) = launch_link() {
+            session.open(Tab::at(verse));
+        }
+        session
+    });
+    let mut search_state = use_state(|| session.peek().active_tab().search.clone());
     let mut filtered_verses = use_state(Vec::new);
-    let mut selected_verse = use_state(|| None::<(String, String, usize, usize)>);
+    let mut selected_verse = use_state(|| session.peek().active_tab().verse.clone());
+    let mut history = use_state(|| session.peek().active_tab().history.clone());
+    let reader_scroll = use_scroll_controller(ScrollConfig::default);
+    // Changes every time another tab is shown so the reader starts over with its scroll
+    let mut tab_generation = use_state(|| 0usize);
 
-    let database = Arc::new(TantivySink::from(data_dir(&["index"])));
+    // Opened again when the indexes are moved to another folder
+    let index_dir = use_reactive(&settings.read().index_dir);
+    let mut sink = use_state(|| {
+        let index_dir = index_dir();
+        (index_dir.clone(), Arc::new(TantivySink::from(index_dir)))
+    });
+    use_side_effect(move || {
+        let index_dir = index_dir();
+        if sink.peek().0 != index_dir {
+            sink.set((index_dir.clone(), Arc::new(TantivySink::from(index_dir))));
+        }
+    });
+    let database = sink.read().1.clone();
 
-    let platform = Platform::get();
-    let root_size = platform.root_size.read().width;
-    let root_size = if root_size < 768.0 {
-        100.
-    } else if root_size < 1024.0 {
-        50.
-    } else {
-        35.
-    };
+    // The catalog is loaded with the index and again every time the Manage Bibles dialog
+    // finishes an install. It is published right away, only the books the canonical table does
+    // not know are counted in the index in the background and merged by the loop below.
+    let installs = use_state(|| 0usize);
+    let (counted_tx, counted_rx): (Sender<BibleCatalog>, Receiver<BibleCatalog>) =
+        use_hook(unbounded);
+    use_side_effect(move || {
+        let _ = installs.read();
+        let database = sink.read().1.clone();
+        let versifications = settings.read().settings.versifications.clone();
+        let catalog = installed_catalog(&versifications);
+        let uncounted = catalog
+            .bibles
+            .iter()
+            .filter(|bible| bible.has_uncounted_books())
+            .cloned()
+            .collect::<Vec<_>>();
+        radio.write_channel(AppChannel::Catalog).catalog = catalog;
+        if uncounted.is_empty() {
+            return;
+        }
+        let counted_tx = counted_tx.clone();
+        thread::spawn(move || {
+            for mut bible in uncounted {
+                match last_chapters(&database.verse_index(), &bible.id) {
+                    Ok(last) => bible.count_chapters(&last),
+                    Err(e) => {
+                        tracing::error!("Failed to count the chapters of {}: {e}", bible.id);
+                        continue;
+                    }
+                }
+                let _ = counted_tx.send(bible);
+            }
+        });
+    });
+    use_hook(|| {
+        spawn(async move {
+            let mut interval = Timer::interval(Duration::from_millis(120));
+            loop {
+                interval.next().await;
+
+                while let Ok(Some(counted)) = counted_rx.try_recv() {
+                    let mut state = radio.write_channel(AppChannel::Catalog);
+                    if let Some(bible) =
+                        state.catalog.bibles.iter_mut().find(|b| b.id == counted.id)
+                    {
+                        bible.books = counted.books;
+                    }
+                }
+            }
+        });
+    });
+
+    // Read on every render so the split follows the window while it is resized
+    let window_width = Platform::get().root_size.read().width;
+    let mobile = is_mobile_layout(window_width);
+    let tab_font_size = if mobile { 15. } else { 13. };
+    let mut mobile_screen = use_state(MobileScreen::default);
 
-    let mut panel_width_anim = use_animation_with_dependencies(&root_size, |c, root_size| {
+    // Goes from 0 to 1 as the reader opens
+    let mut panel_anim = use_animation(|c| {
         c.on_change(OnChange::Finish);
-        AnimNum::new(0., *root_size)
+        AnimNum::new(0., 1.)
             .function(Function::Sine)
             .ease(Ease::InOut)
             .time(300)
     });
 
     let mut is_panel_open = use_state(|| false);
+    let mut split_area = use_state(Area::default);
+    let mut resizing_split = use_state(|| false);
 
     use_side_effect(move || theme.set(PreferredTheme::Dark.to_theme()));
 
+    // On the mobile layout, opening a passage from any screen switches to reading it
+    use_side_effect(move || {
+        if selected_verse.read().is_some() && *mobile_screen.peek() != MobileScreen::Read {
+            mobile_screen.set(MobileScreen::Read);
+        }
+    });
+
+    // Keep the active tab up to date with what is being read, saved right away since Android
+    // can kill the app at any moment once it is in the background
+    use_side_effect(move || {
+        let verse = selected_verse.read().clone();
+        let search = search_state.read().clone();
+        let visited = history.read().clone();
+        let tab = session.peek().active_tab().clone();
+        if tab.verse == verse && tab.search == search && tab.history == visited {
+            return;
+        }
+        let (_, scroll): (State<i32>, State<i32>) = reader_scroll.into();
+        let mut session = session.write();
+        let tab = session.active_tab_mut();
+        tab.scroll = if tab.verse != verse {
+            None
+        } else {
+            Some(*scroll.peek())
+        };
+        tab.verse = verse;
+        tab.search = search;
+        tab.history = visited;
+        session.save();
+    });
+
+    // The reading position changes too often to write it every time, so it is kept to be
+    // written once the scroll has stopped for a moment, or right away when Android pauses the
+    // app
+    use_side_effect(move || {
+        let (_, scroll): (State<i32>, State<i32>) = reader_scroll.into();
+        let scroll = *scroll.read();
+        let mut pending = session.peek().clone();
+        pending.active_tab_mut().scroll = Some(scroll);
+        pending.save_later();
+    });
+    use_hook(|| {
+        spawn(async move {
+            let mut interval = Timer::interval(Duration::from_millis(500));
+            loop {
+                interval.next().await;
+                Session::flush_settled();
+            }
+        });
+    });
+
+    // Stores what the active tab is showing before another tab takes its place
+    let mut store_tab = move || {
+        let (_, scroll): (State<i32>, State<i32>) = reader_scroll.into();
+        let mut session = session.write();
+        let tab = session.active_tab_mut();
+        tab.scroll = Some(*scroll.peek());
+        tab.history = history.peek().clone();
+    };
+    let mut show_active_tab = move || {
+        let tab = session.peek().active_tab().clone();
+        selected_verse.set(tab.verse);
+        search_state.set(tab.search);
+        history.set(tab.history);
+        *tab_generation.write() += 1;
+        session.peek().save();
+    };
+    let select_tab = move |index: usize| {
+        if index == session.peek().active {
+            return;
+        }
+        store_tab();
+        session.write().active = index;
+        show_active_tab();
+    };
+    let new_tab = move || {
+        store_tab();
+        session.write().open(Tab::default());
+        show_active_tab();
+    };
+    let close_tab = move |index: usize| {
+        if session.write().close(index) {
+            show_active_tab();
+        } else {
+            session.peek().save();
+        }
+    };
+    let catalog = radio.read().catalog.clone();
+    let tab_titles = session
+        .read()
+        .tabs
+        .iter()
+        .map(|tab| tab.title(&catalog))
+        .collect::<Vec<_>>();
+    let active_tab = session.read().active;
+    let initial_scroll = session.peek().active_tab().scroll;
+
     use_side_effect({
         let search_state = search_state.clone();
-        let database = database.clone();
         move || {
             let s = search_state.read();
+            let database = sink.read().1.clone();
             let index = database.verse_index();
             let Ok(verses_found) =
-                setup_core::service_db::SearchedVerse::from_search(s.as_str(), index, Some(50))
+                setup_core::service_db::SearchedVerse::from_search(s.as_str(), index, Some(200))
             else {
                 return;
             };
@@ -61,130 +301,460 @@ pub fn init() -> impl IntoElement {
         }
     });
 
-    let filtered_verses_data = filtered_verses.read().clone();
+    // More results than shown are searched so the canon filter still fills the list
+    let search_canon = settings.read().settings.search_canon;
+    let filtered_verses_data = filtered_verses
+        .read()
+        .iter()
+        .filter(|v| search_canon.is_none_or(|canon| canon.contains(&v.book_id)))
+        .take(50)
+        .cloned()
+        .collect::<Vec<_>>();
+    let canon_button = move |text: &'static str, canon: Option<Canon>| {
+        Button::new()
+            .compact()
+            .background(if search_canon == canon {
+                Color::from_hex("#3A3A3A").unwrap()
+            } else {
+                Color::from_hex("#2C2C2C").unwrap()
+            })
+            .hover_background(Color::from_hex("#353535").unwrap())
+            .on_press(move |_| {
+                let mut state = settings.write();
+                state.settings.search_canon = canon;
+                state.settings.save();
+            })
+            .child(label().color(Color::WHITE).font_size(13.).text(text))
+    };
     let should_show_panel = selected_verse.read().is_some();
 
     if should_show_panel != *is_panel_open.read() {
         if should_show_panel {
-            panel_width_anim.start();
+            panel_anim.start();
         } else {
-            panel_width_anim.reverse();
+            panel_anim.reverse();
         }
         is_panel_open.set(should_show_panel);
     }
 
-    let panel_width_value = panel_width_anim.read().value();
+    let panes = settings.read().settings.panes;
+    // Narrow windows have no room for both panes side by side
+    let direction = if window_width < 768.0 {
+        SplitDirection::Vertical
+    } else {
+        panes.direction
+    };
+    let vertical = direction == SplitDirection::Vertical;
+    let reader_size = panel_anim.read().value() * panes.reader_size;
+    let is_reader_shown = reader_size > 0.001;
+    let split_cursor = if vertical {
+        CursorIcon::RowResize
+    } else {
+        CursorIcon::ColResize
+    };
+    let split_button = move |text: &'static str, direction: SplitDirection| {
+        MenuButton::new().child(text).on_press(move |_| {
+            let mut state = settings.write();
+            state.settings.panes.direction = direction;
+            state.settings.save();
+            ContextMenu::close();
+        })
+    };
 
-    let search_panel_percentage = 100.0 - panel_width_value;
+    let mut tools = vec![
+        ("Install Bible", show_bible_manager),
+        ("Install Modules", show_module_manager),
+        ("Reading Plans", show_plans),
+        ("Topics", show_topics),
+        ("Memory Verses", show_memory),
+        ("Export Passages", show_export),
+    ];
+    // Only devices with an external storage have a choice of where to keep the indexes
+    if external_data_dir().is_some() {
+        tools.push(("Storage", show_storage));
+    }
 
-    rect()
+    // The back key of Android, and of some keyboards, first closes what is covering the text,
+    // then the reader, and then goes back through the history
+    let mut go_back_or_close = {
+        let tools = tools.clone();
+        move || {
+            if let Some((_, mut show)) = tools.iter().copied().find(|(_, show)| show()) {
+                show.set(false);
+            } else if mobile && mobile_screen() != MobileScreen::Read {
+                mobile_screen.set(MobileScreen::Read);
+            } else if selected_verse.read().is_some() {
+                // Going back again opens the passage read before this one
+                selected_verse.set(None);
+            } else {
+                go_back(selected_verse, history);
+            }
+        }
+    };
+
+    let tab_bar = rect()
+        .horizontal()
+        .content(Content::Wrap)
+        .width(Size::Fill)
+        .padding((5., 10., 0., 10.))
+        .spacing(5.)
+        .children(tab_titles.into_iter().enumerate().map(|(i, title)| {
+            let mut select_tab = select_tab.clone();
+            let mut close_tab = close_tab.clone();
+            rect()
+                .key(i)
+                .horizontal()
+                .cross_align(Alignment::Center)
+                .rounded()
+                .background(if i == active_tab {
+                    Color::from_hex("#3A3A3A").unwrap()
+                } else {
+                    Color::from_hex("#2C2C2C").unwrap()
+                })
+                .child(
+                    Button::new()
+                        .compact()
+                        .background(Color::TRANSPARENT)
+                        .hover_background(Color::from_hex("#353535").unwrap())
+                        .on_press(move |_| select_tab(i))
+                        .child(
+                            label()
+                                .color(if i == active_tab {
+                                    Color::WHITE
+                                } else {
+                                    Color::from_hex("#888888").unwrap()
+                                })
+                                .font_size(tab_font_size)
+                                .max_lines(1)
+                                .text(title),
+                        ),
+                )
+                .child(
+                    Button::new()
+                        .compact()
+                        .background(Color::TRANSPARENT)
+                        .hover_background(Color::from_hex("#353535").unwrap())
+                        .on_press(move |_| close_tab(i))
+                        .child(
+                            label()
+                                .color(Color::from_hex("#888888").unwrap())
+                                .font_size(tab_font_size)
+                                .text("×"),
+                        ),
+                )
+                .into_element()
+        }))
+        .child(
+            Button::new()
+                .compact()
+                .background(Color::from_hex("#2C2C2C").unwrap())
+                .hover_background(Color::from_hex("#353535").unwrap())
+                .on_press({
+                    let mut new_tab = new_tab.clone();
+                    move |_| new_tab()
+                })
+                .child(
+                    label()
+                        .color(Color::WHITE)
+                        .font_size(tab_font_size)
+                        .text("+"),
+                ),
+        )
+        .into_element();
+
+    let search_pane = rect()
+        .expanded()
+        .content(Content::Flex)
+        .spacing(10.)
+        .vertical()
+        .child(
+            rect()
+                .content(Content::Flex)
+                .center()
+                .width(Size::Inner)
+                .padding(5.)
+                .spacing(10.)
+                .horizontal()
+                .child(
+                    AutoCompleteInput::new(search_state, radio.read().catalog.book_names())
+                        .auto_focus(!mobile)
+                        .width(Size::Fill)
+                        .placeholder("Search: Juan 1:3"),
+                ),
+        )
+        .child(
+            rect()
+                .horizontal()
+                .content(Content::Wrap)
+                .spacing(6.)
+                .padding((0., 5., 0., 5.))
+                .child(canon_button("All books", None))
+                .children(
+                    Canon::ALL
+                        .into_iter()
+                        .map(|canon| canon_button(canon.label(), Some(canon)).into_element()),
+                ),
+        )
+        .child(
+            ScrollView::new()
+                .expanded()
+                .direction(Direction::Vertical)
+                .scroll_with_arrows(true)
+                .spacing(10.)
+                .children(filtered_verses_data.iter().map(|verse| {
+                    let target = VerseRef::new(
+                        verse.bible.id.clone(),
+                        verse.book_id.clone(),
+                        verse.chapter as _,
+                        verse.verse.0 as _,
+                    );
+                    Button::new()
+                        .background(Color::from_hex("#2C2C2C").unwrap())
+                        .hover_background(Color::from_hex("#353535").unwrap())
+                        .on_press({
+                            let target = target.clone();
+                            move |_| navigate(selected_verse, history, target.clone())
+                        })
+                        .child(
+                            rect()
+                                .key(target.to_string())
+                                .rounded()
+                                .vertical()
+                                .spacing(5.)
+                                .padding(if mobile { 10. } else { 5. })
+                                .width(Size::fill())
+                                .content(Content::Flex)
+                                .children([
+                                    label()
+                                        .color(Color::WHITE)
+                                        .font_weight(FontWeight::BOLD)
+                                        .text(format!(
+                                            "{} {}:{}",
+                                            verse.book, target.chapter, target.verse
+                                        ))
+                                        .into_element(),
+                                    label()
+                                        .color(Color::WHITE)
+                                        .text(verse.text.clone())
+                                        .into_element(),
+                                ]),
+                        )
+                        .into_element()
+                })),
+        )
+        .into_element();
+
+    let reader = VersePanel::new(database.clone())
+        .key(tab_generation())
+        .selected_verse(selected_verse)
+        .history(history)
+        .scroll_controller(reader_scroll)
+        .initial_scroll(initial_scroll)
+        .into_element();
+
+    let dialogs = [
+        manage_bibles(show_bible_manager, database.clone(), installs).into_element(),
+        manage_modules(show_module_manager).into_element(),
+        reading_plans(show_plans, selected_verse, history).into_element(),
+        memory_verses(show_memory, selected_verse, history).into_element(),
+        topic_browser(show_topics, selected_verse, history).into_element(),
+        export_passages(show_export, database.clone(), selected_verse).into_element(),
+        storage_location(show_storage).into_element(),
+    ];
+
+    let root = rect()
         .content(Content::Flex)
         .expanded()
         .vertical()
         .theme_background()
-        .child(Toolbar::new().child(ToolbarItem::new(
-            "Tools".to_string(),
-            Menu::new().child(MenuButton::new().child("Install Bible").on_press(move |_| {
-                show_bible_manager.set(true);
-                ContextMenu::close();
-            })),
-        )))
-        .child(manage_bibles(show_bible_manager, database.clone()))
+        // Alt+Left/Right as in browsers
+        .on_global_key_down(move |e: Event<KeyboardEventData>| match e.key {
+            Key::Named(NamedKey::ArrowLeft) if e.modifiers.alt() => {
+                go_back(selected_verse, history);
+            }
+            Key::Named(NamedKey::ArrowRight) if e.modifiers.alt() => {
+                go_forward(selected_verse, history);
+            }
+            Key::Named(NamedKey::BrowserBack | NamedKey::GoBack) => {
+                go_back_or_close();
+            }
+            Key::Named(NamedKey::BrowserForward) => {
+                go_forward(selected_verse, history);
+            }
+            _ => {}
+        })
+        .on_global_mouse_up(move |e: Event<MouseEventData>| match e.button {
+            Some(MouseButton::Back) => {
+                go_back(selected_verse, history);
+            }
+            Some(MouseButton::Forward) => {
+                go_forward(selected_verse, history);
+            }
+            _ => {}
+        });
+
+    let root = if mobile {
+        root.child(
+            MobileShell::new(mobile_screen)
+                .screen(
+                    MobileScreen::Read,
+                    rect()
+                        .expanded()
+                        .content(Content::Flex)
+                        .vertical()
+                        .spacing(5.)
+                        .child(tab_bar)
+                        .child(if should_show_panel {
+                            rect()
+                                .width(Size::Fill)
+                                .height(Size::flex(1.))
+                                .child(reader)
+                                .into_element()
+                        } else {
+                            empty_state("Search a passage to start reading")
+                        }),
+                )
+                .screen(
+                    MobileScreen::Search,
+                    rect().expanded().padding(10.).child(search_pane),
+                )
+                .screen(
+                    MobileScreen::Notes,
+                    rect()
+                        .expanded()
+                        .padding(10.)
+                        .child(NotesPanel::new(selected_verse, history)),
+                )
+                .screen(
+                    MobileScreen::Library,
+                    rect().expanded().padding(10.).child(
+                        ScrollView::new()
+                            .expanded()
+                            .direction(Direction::Vertical)
+                            .spacing(8.)
+                            .children(tools.into_iter().map(|(text, mut show)| {
+                                Button::new()
+                                    .width(Size::Fill)
+                                    .height(Size::px(56.))
+                                    .background(Color::from_hex("#2C2C2C").unwrap())
+                                    .hover_background(Color::from_hex("#353535").unwrap())
+                                    .on_press(move |_| show.set(true))
+                                    .child(label().color(Color::WHITE).font_size(16.).text(text))
+                                    .into_element()
+                            })),
+                    ),
+                ),
+        )
+    } else {
+        root.child(
+            Toolbar::new()
+                .child(ToolbarItem::new(
+                    "Tools".to_string(),
+                    tools
+                        .into_iter()
+                        .fold(Menu::new(), |menu, (text, mut show)| {
+                            menu.child(MenuButton::new().child(text).on_press(move |_| {
+                                show.set(true);
+                                ContextMenu::close();
+                            }))
+                        }),
+                ))
+                .child(ToolbarItem::new(
+                    "View".to_string(),
+                    Menu::new()
+                        .child(split_button(
+                            "Reader Beside Results",
+                            SplitDirection::Horizontal,
+                        ))
+                        .child(split_button(
+                            "Reader Below Results",
+                            SplitDirection::Vertical,
+                        )),
+                )),
+        )
+        .child(tab_bar)
         .child(
             rect()
                 .content(Content::Flex)
                 .padding(10.)
                 .spacing(10.)
                 .expanded()
-                .horizontal()
-                .maybe(panel_width_value < 80., |r| {
+                .direction(if vertical {
+                    Direction::Vertical
+                } else {
+                    Direction::Horizontal
+                })
+                .on_sized(move |e: Event<SizedEventData>| split_area.set(e.visible_area))
+                .on_global_mouse_move(move |e: Event<MouseEventData>| {
+                    if !resizing_split() {
+                        return;
+                    }
+                    let area = split_area.read();
+                    let size = if vertical {
+                        (area.max_y() as f64 - e.global_location.y) / area.height() as f64
+                    } else {
+                        (area.max_x() as f64 - e.global_location.x) / area.width() as f64
+                    };
+                    if size.is_finite() {
+                        settings
+                            .write()
+                            .settings
+                            .panes
+                            .set_reader_size(size as f32 * 100.);
+                    }
+                })
+                .on_global_mouse_up(move |_| {
+                    if resizing_split() {
+                        resizing_split.set(false);
+                        Cursor::set(CursorIcon::default());
+                        settings.read().settings.save();
+                    }
+                })
+                .child(
+                    rect()
+                        .width(if vertical { Size::Fill } else { Size::flex(1.) })
+                        .height(if vertical { Size::flex(1.) } else { Size::Fill })
+                        .child(search_pane),
+                )
+                .maybe(is_reader_shown, |r| {
                     r.child(
                         rect()
-                            .width(Size::percent(search_panel_percentage))
-                            .content(Content::Flex)
-                            .spacing(10.)
-                            .vertical()
-                            .child(
-                                rect()
-                                    .content(Content::Flex)
-                                    .center()
-                                    .width(Size::Inner)
-                                    .padding(5.)
-                                    .spacing(10.)
-                                    .horizontal()
-                                    .child(
-                                        AutoCompleteInput::new(search_state, radio.read().books.clone())
-                                            .auto_focus(true)
-                                            .width(Size::Fill)
-                                            .placeholder("Search: Juan 1:3"),
-                                    ),
-                            )
-                            .child(
-                                ScrollView::new()
-                                    .expanded()
-                                    .direction(Direction::Vertical)
-                                    .scroll_with_arrows(true)
-                                    .spacing(10.)
-                                    .children(filtered_verses_data.iter().map(|verse| {
-                                        let bible_id = verse.bible.id.clone();
-                                        let book_id = verse.book_id.clone();
-                                        let ch_idx = verse.chapter;
-                                        let v_idx = verse.verse.0;
-                                        Button::new()
-                                            .background(Color::from_hex("#2C2C2C").unwrap())
-                                            .hover_background(Color::from_hex("#353535").unwrap())
-                                            .on_press({
-                                                move |_| {
-                                                    selected_verse.set(Some((
-                                                        bible_id.clone(),
-                                                        book_id.clone(),
-                                                        ch_idx as _,
-                                                        v_idx as _,
-                                                    )));
-                                                }
-                                            })
-                                            .child(
-                                                rect()
-                                                    .key(v_idx)
-                                                    .rounded()
-                                                    .vertical()
-                                                    .spacing(5.)
-                                                    .padding(5.)
-                                                    .width(Size::fill())
-                                                    .content(Content::Flex)
-                                                    .children([
-                                                        label()
-                                                            .color(Color::WHITE)
-                                                            .font_weight(FontWeight::BOLD)
-                                                            .text(format!(
-                                                                "{} {}:{}",
-                                                                verse.book, verse.chapter, v_idx
-                                                            ))
-                                                            .into_element(),
-                                                        label()
-                                                            .color(Color::WHITE)
-                                                            .text(verse.text.clone())
-                                                            .into_element(),
-                                                    ]),
-                                            )
-                                            .into_element()
-                                    })),
-                            ),
+                            .width(if vertical { Size::Fill } else { Size::px(6.) })
+                            .height(if vertical { Size::px(6.) } else { Size::Fill })
+                            .rounded()
+                            .background(if resizing_split() {
+                                Color::from_hex("#4A4A4A").unwrap()
+                            } else {
+                                Color::from_hex("#2C2C2C").unwrap()
+                            })
+                            .on_pointer_down(move |e: Event<PointerEventData>| {
+                                e.stop_propagation();
+                                resizing_split.set(true);
+                            })
+                            .on_pointer_enter(move |_| Cursor::set(split_cursor))
+                            .on_pointer_leave(move |_| {
+                                if !resizing_split() {
+                                    Cursor::set(CursorIcon::default());
+                                }
+                            }),
+                    )
+                    .child(
+                        rect()
+                            .width(if vertical {
+                                Size::Fill
+                            } else {
+                                Size::percent(reader_size)
+                            })
+                            .height(if vertical {
+                                Size::percent(reader_size)
+                            } else {
+                                Size::Fill
+                            })
+                            .child(reader),
                     )
-                })
-                .maybe(panel_width_value > 0.001, {
-                    let database = database.clone();
-                    |r| {
-                        r.child(
-                            VersePanel::new(database)
-                                .width(Size::percent(panel_width_value))
-                                .selected_verse(selected_verse)
-                                .on_word_click(|word| {
-                                    println!("Word clicked: {}", word);
-                                })
-                                .into_element(),
-                        )
-                    }
                 }),
         )
+    };
+
+    root.children(dialogs)
 }
@@ -0,0 +1,641 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use tantivy::collector::DocSetCollector;
+use tantivy::query::TermQuery;
+use tantivy::schema::{IndexRecordOption, Value};
+use tantivy::{Index, TantivyDocument, Term};
+
+use crate::versification::Versification;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, PartialOrd, Ord)]
+pub enum Testament {
+    Old,
+    New,
+}
+
+/// Canonical information of a book, independent of the installed Bible.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct CanonicalBook {
+    pub id: &'static str,
+    pub testament: Testament,
+    pub chapters: u16,
+    /// English and Spanish names, used to recognize the books of an installed Bible
+    pub names: &'static [&'static str],
+    pub abbreviations: &'static [&'static str],
+}
+
+macro_rules! book {
+    ($id:literal, $testament:ident, $chapters:literal, [$($name:literal),*], [$($abbr:literal),*]) => {
+        CanonicalBook {
+            id: $id,
+            testament: Testament::$testament,
+            chapters: $chapters,
+            names: &[$($name),*],
+            abbreviations: &[$($abbr),*],
+        }
+    };
+}
+
+#[rustfmt::skip]
+pub const CANONICAL_BOOKS: &[CanonicalBook] = &[
+    book!("GEN", Old, 50, ["Genesis", "Génesis"], ["Gen", "Gn", "Ge"]),
+    book!("EXO", Old, 40, ["Exodus", "Éxodo"], ["Exod", "Ex", "Éx"]),
+    book!("LEV", Old, 27, ["Leviticus", "Levítico"], ["Lev", "Lv"]),
+    book!("NUM", Old, 36, ["Numbers", "Números"], ["Num", "Nm", "Nu"]),
+    book!("DEU", Old, 34, ["Deuteronomy", "Deuteronomio"], ["Deut", "Dt"]),
+    book!("JOS", Old, 24, ["Joshua", "Josué"], ["Josh", "Jos"]),
+    book!("JDG", Old, 21, ["Judges", "Jueces"], ["Judg", "Jdg", "Jue"]),
+    book!("RUT", Old, 4, ["Ruth", "Rut"], ["Ru", "Rt"]),
+    book!("1SA", Old, 31, ["1 Samuel"], ["1 Sam", "1 Sa", "1 S"]),
+    book!("2SA", Old, 24, ["2 Samuel"], ["2 Sam", "2 Sa", "2 S"]),
+    book!("1KI", Old, 22, ["1 Kings", "1 Reyes"], ["1 Kgs", "1 Ki", "1 R"]),
+    book!("2KI", Old, 25, ["2 Kings", "2 Reyes"], ["2 Kgs", "2 Ki", "2 R"]),
+    book!("1CH", Old, 29, ["1 Chronicles", "1 Crónicas"], ["1 Chr", "1 Ch", "1 Cr"]),
+    book!("2CH", Old, 36, ["2 Chronicles", "2 Crónicas"], ["2 Chr", "2 Ch", "2 Cr"]),
+    book!("EZR", Old, 10, ["Ezra", "Esdras"], ["Ezr", "Esd"]),
+    book!("NEH", Old, 13, ["Nehemiah", "Nehemías"], ["Neh"]),
+    book!("EST", Old, 10, ["Esther", "Ester"], ["Esth", "Est"]),
+    book!("JOB", Old, 42, ["Job"], ["Jb"]),
+    book!("PSA", Old, 150, ["Psalms", "Salmos"], ["Ps", "Psa", "Sal"]),
+    book!("PRO", Old, 31, ["Proverbs", "Proverbios"], ["Prov", "Pr"]),
+    book!("ECC", Old, 12, ["Ecclesiastes", "Eclesiastés"], ["Eccl", "Ec"]),
+    book!("SNG", Old, 8, ["Song of Solomon", "Cantares"], ["Song", "SS", "Cnt", "Cant"]),
+    book!("ISA", Old, 66, ["Isaiah", "Isaías"], ["Isa", "Is"]),
+    book!("JER", Old, 52, ["Jeremiah", "Jeremías"], ["Jer"]),
+    book!("LAM", Old, 5, ["Lamentations", "Lamentaciones"], ["Lam", "Lm"]),
+    book!("EZK", Old, 48, ["Ezekiel", "Ezequiel"], ["Ezek", "Ez"]),
+    book!("DAN", Old, 12, ["Daniel"], ["Dan", "Dn"]),
+    book!("HOS", Old, 14, ["Hosea", "Oseas"], ["Hos", "Os"]),
+    book!("JOL", Old, 3, ["Joel"], ["Jl"]),
+    book!("AMO", Old, 9, ["Amos", "Amós"], ["Am"]),
+    book!("OBA", Old, 1, ["Obadiah", "Abdías"], ["Obad", "Ob", "Abd"]),
+    book!("JON", Old, 4, ["Jonah", "Jonás"], ["Jon"]),
+    book!("MIC", Old, 7, ["Micah", "Miqueas"], ["Mic", "Mi"]),
+    book!("NAM", Old, 3, ["Nahum", "Nahúm"], ["Nah", "Na"]),
+    book!("HAB", Old, 3, ["Habakkuk", "Habacuc"], ["Hab"]),
+    book!("ZEP", Old, 3, ["Zephaniah", "Sofonías"], ["Zeph", "Zep", "Sof"]),
+    book!("HAG", Old, 2, ["Haggai", "Hageo"], ["Hag"]),
+    book!("ZEC", Old, 14, ["Zechariah", "Zacarías"], ["Zech", "Zec", "Zac"]),
+    book!("MAL", Old, 4, ["Malachi", "Malaquías"], ["Mal"]),
+    book!("MAT", New, 28, ["Matthew", "Mateo"], ["Matt", "Mt"]),
+    book!("MRK", New, 16, ["Mark", "Marcos"], ["Mk", "Mr", "Mc"]),
+    book!("LUK", New, 24, ["Luke", "Lucas"], ["Lk", "Lc"]),
+    book!("JHN", New, 21, ["John", "Juan"], ["Jn", "Jhn"]),
+    book!("ACT", New, 28, ["Acts", "Hechos"], ["Act", "Hch"]),
+    book!("ROM", New, 16, ["Romans", "Romanos"], ["Rom", "Ro"]),
+    book!("1CO", New, 16, ["1 Corinthians", "1 Corintios"], ["1 Cor", "1 Co"]),
+    book!("2CO", New, 13, ["2 Corinthians", "2 Corintios"], ["2 Cor", "2 Co"]),
+    book!("GAL", New, 6, ["Galatians", "Gálatas"], ["Gal", "Gá", "Ga"]),
+    book!("EPH", New, 6, ["Ephesians", "Efesios"], ["Eph", "Ef"]),
+    book!("PHP", New, 4, ["Philippians", "Filipenses"], ["Phil", "Php", "Fil"]),
+    book!("COL", New, 4, ["Colossians", "Colosenses"], ["Col"]),
+    book!("1TH", New, 5, ["1 Thessalonians", "1 Tesalonicenses"], ["1 Thess", "1 Th", "1 Ts"]),
+    book!("2TH", New, 3, ["2 Thessalonians", "2 Tesalonicenses"], ["2 Thess", "2 Th", "2 Ts"]),
+    book!("1TI", New, 6, ["1 Timothy", "1 Timoteo"], ["1 Tim", "1 Ti"]),
+    book!("2TI", New, 4, ["2 Timothy", "2 Timoteo"], ["2 Tim", "2 Ti"]),
+    book!("TIT", New, 3, ["Titus", "Tito"], ["Tit"]),
+    book!("PHM", New, 1, ["Philemon", "Filemón"], ["Phlm", "Phm", "Flm"]),
+    book!("HEB", New, 13, ["Hebrews", "Hebreos"], ["Heb", "He"]),
+    book!("JAS", New, 5, ["James", "Santiago"], ["Jas", "Stg"]),
+    book!("1PE", New, 5, ["1 Peter", "1 Pedro"], ["1 Pet", "1 Pe", "1 P"]),
+    book!("2PE", New, 3, ["2 Peter", "2 Pedro"], ["2 Pet", "2 Pe", "2 P"]),
+    book!("1JN", New, 5, ["1 John", "1 Juan"], ["1 Jn"]),
+    book!("2JN", New, 1, ["2 John", "2 Juan"], ["2 Jn"]),
+    book!("3JN", New, 1, ["3 John", "3 Juan"], ["3 Jn"]),
+    book!("JUD", New, 1, ["Jude", "Judas"], ["Jud"]),
+    book!("REV", New, 22, ["Revelation", "Apocalipsis"], ["Rev", "Ap", "Apoc"]),
+];
+
+/// Books of the Old Testament only included by the Catholic or the Orthodox canons
+#[rustfmt::skip]
+pub const DEUTEROCANONICAL_BOOKS: &[CanonicalBook] = &[
+    book!("TOB", Old, 14, ["Tobit", "Tobías"], ["Tob", "Tb"]),
+    book!("JDT", Old, 16, ["Judith", "Judit"], ["Jdt", "Jdth"]),
+    book!("ESG", Old, 16, ["Esther (Greek)", "Ester (Griego)"], ["EsthGr", "AddEsth"]),
+    book!("WIS", Old, 19, ["Wisdom of Solomon", "Wisdom", "Sabiduría"], ["Wis", "Sab", "Sb"]),
+    book!("SIR", Old, 51, ["Sirach", "Ecclesiasticus", "Eclesiástico"], ["Sir", "Ecclus", "Eclo"]),
+    book!("BAR", Old, 6, ["Baruch", "Baruc"], ["Bar", "Ba"]),
+    book!("LJE", Old, 1, ["Letter of Jeremiah", "Carta de Jeremías"], ["EpJer", "LJe"]),
+    book!("S3Y", Old, 1, ["Song of the Three Young Men", "Prayer of Azariah"], ["Sg of 3", "PrAzar"]),
+    book!("SUS", Old, 1, ["Susanna", "Susana"], ["Sus"]),
+    book!("BEL", Old, 1, ["Bel and the Dragon", "Bel y el Dragón"], ["Bel"]),
+    book!("1MA", Old, 16, ["1 Maccabees", "1 Macabeos"], ["1 Macc", "1 Mac", "1 M"]),
+    book!("2MA", Old, 15, ["2 Maccabees", "2 Macabeos"], ["2 Macc", "2 Mac", "2 M"]),
+    book!("3MA", Old, 7, ["3 Maccabees", "3 Macabeos"], ["3 Macc", "3 Mac"]),
+    book!("4MA", Old, 18, ["4 Maccabees", "4 Macabeos"], ["4 Macc", "4 Mac"]),
+    book!("1ES", Old, 9, ["1 Esdras"], ["1 Esd"]),
+    book!("2ES", Old, 16, ["2 Esdras"], ["2 Esd"]),
+    book!("MAN", Old, 1, ["Prayer of Manasseh", "Oración de Manasés"], ["PrMan"]),
+    book!("PS2", Old, 1, ["Psalm 151", "Salmo 151"], ["Ps151"]),
+];
+
+/// Old Testament of the Catholic canon
+#[rustfmt::skip]
+const CATHOLIC_OLD_TESTAMENT: &[&str] = &[
+    "GEN", "EXO", "LEV", "NUM", "DEU", "JOS", "JDG", "RUT", "1SA", "2SA", "1KI", "2KI", "1CH",
+    "2CH", "EZR", "NEH", "TOB", "JDT", "EST", "ESG", "1MA", "2MA", "JOB", "PSA", "PRO", "ECC",
+    "SNG", "WIS", "SIR", "ISA", "JER", "LAM", "BAR", "LJE", "EZK", "DAN", "S3Y", "SUS", "BEL",
+    "HOS", "JOL", "AMO", "OBA", "JON", "MIC", "NAM", "HAB", "ZEP", "HAG", "ZEC", "MAL",
+];
+
+/// Old Testament of the Orthodox canon, following the order of the Septuagint
+#[rustfmt::skip]
+const ORTHODOX_OLD_TESTAMENT: &[&str] = &[
+    "GEN", "EXO", "LEV", "NUM", "DEU", "JOS", "JDG", "RUT", "1SA", "2SA", "1KI", "2KI", "1CH",
+    "2CH", "MAN", "1ES", "EZR", "NEH", "TOB", "JDT", "EST", "ESG", "1MA", "2MA", "3MA", "PSA",
+    "PS2", "JOB", "PRO", "ECC", "SNG", "WIS", "SIR", "HOS", "AMO", "MIC", "JOL", "OBA", "JON",
+    "NAM", "HAB", "ZEP", "HAG", "ZEC", "MAL", "ISA", "JER", "BAR", "LAM", "LJE", "EZK", "DAN",
+    "S3Y", "SUS", "BEL", "4MA",
+];
+
+/// Books only found in the Orthodox canon, used to recognize Orthodox Bibles
+const ORTHODOX_ONLY: &[&str] = &["1ES", "3MA", "4MA", "MAN", "PS2"];
+
+/// Set of books a Bible tradition recognizes, with its own order
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Canon {
+    #[default]
+    Protestant,
+    Catholic,
+    Orthodox,
+}
+
+impl Canon {
+    pub const ALL: [Canon; 3] = [Canon::Protestant, Canon::Catholic, Canon::Orthodox];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Canon::Protestant => "Protestant",
+            Canon::Catholic => "Catholic",
+            Canon::Orthodox => "Orthodox",
+        }
+    }
+
+    /// Canon of a Bible from the books it contains
+    pub fn detect<'a>(book_ids: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut canon = Canon::Protestant;
+        for id in book_ids {
+            if ORTHODOX_ONLY.iter().any(|o| o.eq_ignore_ascii_case(id)) {
+                return Canon::Orthodox;
+            }
+            if CanonicalBook::by_id(id).is_some_and(CanonicalBook::is_deuterocanonical) {
+                canon = Canon::Catholic;
+            }
+        }
+        canon
+    }
+
+    fn old_testament(&self) -> Vec<&'static str> {
+        match self {
+            Canon::Protestant => CANONICAL_BOOKS
+                .iter()
+                .filter(|b| b.testament == Testament::Old)
+                .map(|b| b.id)
+                .collect(),
+            Canon::Catholic => CATHOLIC_OLD_TESTAMENT.to_vec(),
+            Canon::Orthodox => ORTHODOX_OLD_TESTAMENT.to_vec(),
+        }
+    }
+
+    /// Ids of the books of this canon in its order
+    fn books(&self) -> Vec<&'static str> {
+        let mut books = self.old_testament();
+        books.extend(
+            CANONICAL_BOOKS
+                .iter()
+                .filter(|b| b.testament == Testament::New)
+                .map(|b| b.id),
+        );
+        books
+    }
+
+    /// Matches the books the canonical table could not recognize by their place in the Bible,
+    /// when the books around them leave exactly that many books of this canon between them
+    fn match_by_position(&self, books: &mut [Option<&'static CanonicalBook>]) {
+        let order = self.books();
+        let position = |book: Option<&CanonicalBook>| {
+            book.and_then(|book| order.iter().position(|id| *id == book.id))
+        };
+        let mut start = 0;
+        while start < books.len() {
+            if books[start].is_some() {
+                start += 1;
+                continue;
+            }
+            let end = (start..books.len())
+                .find(|&i| books[i].is_some())
+                .unwrap_or(books.len());
+            let after = match start {
+                0 => Some(0),
+                _ => position(books[start - 1]).map(|p| p + 1),
+            };
+            let before = match end == books.len() {
+                true => Some(order.len()),
+                false => position(books[end]),
+            };
+            if let (Some(after), Some(before)) = (after, before)
+                && before.checked_sub(after) == Some(end - start)
+            {
+                for (i, id) in (start..end).zip(&order[after..before]) {
+                    let taken = books.iter().flatten().any(|book| book.id == *id);
+                    books[i] = CanonicalBook::by_id(id).filter(|_| !taken);
+                }
+            }
+            start = end;
+        }
+    }
+
+    pub fn contains(&self, book_id: &str) -> bool {
+        self.position(book_id).is_some()
+    }
+
+    fn position(&self, book_id: &str) -> Option<usize> {
+        let old_testament = self.old_testament();
+        old_testament
+            .iter()
+            .position(|id| id.eq_ignore_ascii_case(book_id))
+            .or_else(|| {
+                CANONICAL_BOOKS
+                    .iter()
+                    .filter(|b| b.testament == Testament::New)
+                    .position(|b| b.id.eq_ignore_ascii_case(book_id))
+                    .map(|i| old_testament.len() + i)
+            })
+    }
+
+    /// Position of the book in this canon, books outside of it go last
+    pub fn order(&self, book_id: &str) -> usize {
+        self.position(book_id).unwrap_or_else(|| {
+            CANONICAL_BOOKS.len()
+                + DEUTEROCANONICAL_BOOKS.len()
+                + CanonicalBook::by_id(book_id).map_or(usize::MAX / 2, CanonicalBook::order)
+        })
+    }
+}
+
+/// Normalizes a book name so it can be compared ignoring case, accents and spacing.
+pub fn normalize_name(input: &str) -> String {
+    input
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '.')
+        .map(|c| match c {
+            'á' | 'à' | 'ä' | 'â' => 'a',
+            'é' | 'è' | 'ë' | 'ê' => 'e',
+            'í' | 'ì' | 'ï' | 'î' => 'i',
+            'ó' | 'ò' | 'ö' | 'ô' => 'o',
+            'ú' | 'ù' | 'ü' | 'û' => 'u',
+            'ñ' => 'n',
+            _ => c,
+        })
+        .collect()
+}
+
+impl CanonicalBook {
+    /// Every known book, the deuterocanonical ones after the Protestant canon
+    pub fn all() -> impl Iterator<Item = &'static CanonicalBook> {
+        CANONICAL_BOOKS.iter().chain(DEUTEROCANONICAL_BOOKS)
+    }
+
+    pub fn by_id(id: &str) -> Option<&'static CanonicalBook> {
+        Self::all().find(|b| b.id.eq_ignore_ascii_case(id))
+    }
+
+    /// Finds the canonical book from a localized name or abbreviation
+    pub fn by_name(name: &str) -> Option<&'static CanonicalBook> {
+        let name = normalize_name(name);
+        Self::all().find(|b| {
+            normalize_name(b.id) == name
+                || b.names.iter().any(|n| normalize_name(n) == name)
+                || b.abbreviations.iter().any(|a| normalize_name(a) == name)
+        })
+    }
+
+    pub fn is_deuterocanonical(&self) -> bool {
+        DEUTEROCANONICAL_BOOKS.iter().any(|b| b.id == self.id)
+    }
+
+    /// Position of the book in the canonical order, with the deuterocanonical books between
+    /// the testaments
+    pub fn order(&self) -> usize {
+        let old_testament = CANONICAL_BOOKS
+            .iter()
+            .filter(|b| b.testament == Testament::Old)
+            .count();
+        if let Some(i) = DEUTEROCANONICAL_BOOKS.iter().position(|b| b.id == self.id) {
+            return old_testament + i;
+        }
+        match CANONICAL_BOOKS.iter().position(|b| b.id == self.id) {
+            Some(i) if self.testament == Testament::New => i + DEUTEROCANONICAL_BOOKS.len(),
+            Some(i) => i,
+            None => usize::MAX,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Book {
+    pub id: String,
+    /// Name of the book in the language of the Bible
+    pub name: String,
+    pub abbreviations: Vec<String>,
+    pub testament: Testament,
+    pub chapters: u16,
+}
+
+impl Book {
+    /// Builds the book metadata from the name stored by an installed Bible
+    pub fn from_localized(name: impl Into<String>) -> Option<Self> {
+        let name = name.into();
+        let canonical = CanonicalBook::by_name(&name)?;
+        Some(Self::localized(canonical, name))
+    }
+
+    fn localized(canonical: &CanonicalBook, name: String) -> Self {
+        Self {
+            id: canonical.id.to_string(),
+            abbreviations: canonical
+                .abbreviations
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            testament: canonical.testament,
+            chapters: canonical.chapters,
+            name,
+        }
+    }
+
+    /// Book the canonical table does not know. It keeps its name so it can still be searched,
+    /// and its chapters are only known once counted in the installed Bible.
+    fn unknown(name: String, testament: Testament) -> Self {
+        Self {
+            id: normalize_name(&name).to_uppercase(),
+            abbreviations: Vec::new(),
+            testament,
+            chapters: 0,
+            name,
+        }
+    }
+
+    pub fn canonical(&self) -> Option<&'static CanonicalBook> {
+        CanonicalBook::by_id(&self.id)
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct BibleCatalog {
+    pub id: String,
+    /// Books of the Bible in the order of its canon
+    pub books: Vec<Book>,
+    pub canon: Canon,
+    pub versification: Versification,
+}
+
+impl BibleCatalog {
+    pub fn new(id: impl Into<String>, book_names: impl IntoIterator<Item = String>) -> Self {
+        let id = id.into();
+        let names = book_names.into_iter().collect::<Vec<_>>();
+        let mut canonical = names
+            .iter()
+            .map(|name| CanonicalBook::by_name(name))
+            .collect::<Vec<_>>();
+        let canon = Canon::detect(canonical.iter().flatten().map(|b| b.id));
+        canon.match_by_position(&mut canonical);
+        // Unknown books take the testament of the book before them
+        let mut testament = Testament::Old;
+        let mut books = names
+            .into_iter()
+            .zip(canonical)
+            .map(|(name, canonical)| match canonical {
+                Some(canonical) => {
+                    testament = canonical.testament;
+                    Book::localized(canonical, name)
+                }
+                None => {
+                    tracing::warn!("Unknown book {name} in bible {id}, keeping it by name");
+                    Book::unknown(name, testament)
+                }
+            })
+            .collect::<Vec<_>>();
+        books.sort_by_key(|b| canon.order(&b.id));
+        books.dedup_by(|a, b| a.id == b.id);
+        let mut bible = Self {
+            canon,
+            versification: Versification::detect(&id),
+            id,
+            books,
+        };
+        bible.set_versification(bible.versification);
+        bible
+    }
+
+    /// Numbers the Bible with another scheme, when the one guessed from its id is wrong
+    pub fn set_versification(&mut self, versification: Versification) {
+        self.versification = versification;
+        // Schemes that divide a book differently also change its chapter count
+        for book in &mut self.books {
+            if let Some(canonical) = book.canonical() {
+                book.chapters =
+                    versification.chapters(canonical.id, u32::from(canonical.chapters)) as u16;
+            }
+        }
+    }
+
+    pub fn book(&self, book_id: &str) -> Option<&Book> {
+        self.books
+            .iter()
+            .find(|b| b.id.eq_ignore_ascii_case(book_id))
+    }
+
+    /// Whether some book has no canonical chapter count and needs `count_chapters`
+    pub fn has_uncounted_books(&self) -> bool {
+        self.books.iter().any(|b| b.chapters == 0)
+    }
+
+    /// Counts the chapters of the books the canonical table does not know, from the last
+    /// chapter of every book in the installed text, see `last_chapters`
+    pub fn count_chapters(&mut self, last_chapters: &HashMap<String, u32>) {
+        for book in self.books.iter_mut().filter(|b| b.chapters == 0) {
+            book.chapters = last_chapters.get(&book.name).copied().unwrap_or_default() as u16;
+        }
+    }
+
+    pub fn testament(&self, testament: Testament) -> impl Iterator<Item = &Book> {
+        self.books.iter().filter(move |b| b.testament == testament)
+    }
+}
+
+/// Last chapter of every book of a Bible in the verse index of setup_core, keyed by the name the
+/// Bible gives to the book
+pub fn last_chapters(index: &Index, bible_id: &str) -> tantivy::Result<HashMap<String, u32>> {
+    let schema = index.schema();
+    let bible = schema.get_field("bible_id")?;
+    let book = schema.get_field("book")?;
+    let chapter = schema.get_field("chapter")?;
+    let searcher = index.reader()?.searcher();
+    let query = TermQuery::new(
+        Term::from_field_text(bible, bible_id),
+        IndexRecordOption::Basic,
+    );
+    let mut last = HashMap::<String, u32>::new();
+    for address in searcher.search(&query, &DocSetCollector)? {
+        let doc = searcher.doc::<TantivyDocument>(address)?;
+        let name = doc.get_first(book).and_then(|v| v.as_str());
+        let number = doc.get_first(chapter).and_then(|v| v.as_u64());
+        if let (Some(name), Some(number)) = (name, number) {
+            let last = last.entry(name.to_string()).or_default();
+            *last = (*last).max(number as u32);
+        }
+    }
+    Ok(last)
+}
+
+/// Installed Bibles with their books
+#[derive(Default, PartialEq, Clone, Debug)]
+pub struct Catalog {
+    pub bibles: Vec<BibleCatalog>,
+}
+
+impl Catalog {
+    /// Catalog of the installed Bibles, numbered with the schemes chosen in `versifications`
+    /// or the ones guessed from their ids
+    pub fn from_installed(
+        installed: impl IntoIterator<Item = (String, Vec<String>)>,
+        versifications: &BTreeMap<String, Versification>,
+    ) -> Self {
+        Self {
+            bibles: installed
+                .into_iter()
+                .map(|(bible_id, books)| {
+                    let mut bible = BibleCatalog::new(bible_id, books);
+                    if let Some(versification) = versifications.get(&bible.id) {
+                        bible.set_versification(*versification);
+                    }
+                    bible
+                })
+                .collect(),
+        }
+    }
+
+    pub fn bible(&self, bible_id: &str) -> Option<&BibleCatalog> {
+        self.bibles.iter().find(|b| b.id == bible_id)
+    }
+
+    pub fn book(&self, bible_id: &str, book_id: &str) -> Option<&Book> {
+        self.bible(bible_id)?.book(book_id)
+    }
+
+    /// Verse numbering of an installed Bible, the KJV one when it is not installed
+    pub fn versification(&self, bible_id: &str) -> Versification {
+        self.bible(bible_id)
+            .map(|b| b.versification)
+            .unwrap_or_default()
+    }
+
+    /// Chapter and verse in `to_bible` of a verse of `from_bible`
+    pub fn map_verse(
+        &self,
+        from_bible: &str,
+        to_bible: &str,
+        book_id: &str,
+        chapter: u32,
+        verse: u32,
+    ) -> (u32, u32) {
+        self.versification(from_bible)
+            .map(self.versification(to_bible), book_id, chapter, verse)
+    }
+
+    /// Where a chapter of `from_bible` ends in `to_bible`, as the chapter and its verse, the
+    /// verse being `None` when that chapter ends there too
+    pub fn map_chapter_end(
+        &self,
+        from_bible: &str,
+        to_bible: &str,
+        book_id: &str,
+        chapter: u32,
+    ) -> (u32, Option<u32>) {
+        self.versification(from_bible).map_chapter_end(
+            self.versification(to_bible),
+            book_id,
+            chapter,
+        )
+    }
+
+    /// Book of the Bible called `name`, falling back to the canonical names when the Bible
+    /// does not know it
+    pub fn book_by_name(&self, bible_id: &str, name: &str) -> Option<Book> {
+        let normalized = normalize_name(name);
+        self.bible(bible_id)
+            .and_then(|bible| {
+                bible
+                    .books
+                    .iter()
+                    .find(|b| normalize_name(&b.name) == normalized)
+            })
+            .cloned()
+            .or_else(|| Book::from_localized(name))
+    }
+
+    /// Localized book names of every installed Bible, without duplicates
+    pub fn book_names(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.bibles
+            .iter()
+            .flat_map(|bible| bible.books.iter())
+            .filter(|book| seen.insert(book.name.clone()))
+            .map(|book| book.name.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::doc;
+    use tantivy::schema::{INDEXED, STORED, STRING, Schema};
+
+    use super::*;
+
+    #[test]
+    fn counts_the_chapters_of_unknown_books() {
+        let mut bible = BibleCatalog::new(
+            "kjva",
+            ["Genesis".to_string(), "Book of Jasher".to_string()],
+        );
+        assert!(bible.has_uncounted_books());
+        let last_chapters = HashMap::from([
+            ("Genesis".to_string(), 49),
+            ("Book of Jasher".to_string(), 91),
+        ]);
+        bible.count_chapters(&last_chapters);
+        assert!(!bible.has_uncounted_books());
+        assert_eq!(bible.book("GEN").map(|b| b.chapters), Some(50));
+        assert_eq!(bible.book("BOOKOFJASHER").map(|b| b.chapters), Some(91));
+    }
+
+    #[test]
+    fn last_chapters_come_from_the_verse_index() {
+        let mut schema = Schema::builder();
+        let bible = schema.add_text_field("bible_id", STRING | STORED);
+        let book = schema.add_text_field("book", STRING | STORED);
+        let chapter = schema.add_u64_field("chapter", INDEXED | STORED);
+        let index = Index::create_in_ram(schema.build());
+        let mut writer = index.writer(15_000_000).unwrap();
+        for (bible_id, name, number) in [
+            ("kjva", "Tobit", 14),
+            ("kjva", "Tobit", 2),
+            ("kjva", "Susanna", 1),
+            ("web", "Tobit", 20),
+        ] {
+            writer
+                .add_document(doc!(bible => bible_id, book => name, chapter => number as u64))
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        assert_eq!(
+            last_chapters(&index, "kjva").unwrap(),
+            HashMap::from([("Tobit".to_string(), 14), ("Susanna".to_string(), 1)])
+        );
+    }
+}
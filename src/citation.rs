@@ -0,0 +1,309 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferencePosition {
+    #[default]
+    After,
+    Before,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CitationLayout {
+    /// All the verses in a single paragraph
+    #[default]
+    Paragraph,
+    /// Every verse in its own line
+    Lines,
+}
+
+/// How copied verses are written
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct CitationFormat {
+    pub reference: ReferencePosition,
+    /// Appends the abbreviation of the Bible to the reference
+    pub translation: bool,
+    pub verse_numbers: bool,
+    pub layout: CitationLayout,
+}
+
+impl Default for CitationFormat {
+    fn default() -> Self {
+        Self {
+            reference: ReferencePosition::After,
+            translation: true,
+            verse_numbers: false,
+            layout: CitationLayout::Paragraph,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CitedVerse {
+    pub number: u32,
+    pub text: String,
+}
+
+/// Verses to copy out of the app, with the reference they come from
+#[derive(Clone, PartialEq, Debug)]
+pub struct Citation {
+    /// Reference of the passage, like `John 3:16-18`
+    pub reference: String,
+    /// Abbreviation of the Bible the verses come from
+    pub translation: String,
+    pub verses: Vec<CitedVerse>,
+}
+
+/// Escapes the characters Markdown would read as emphasis, links, headings, code, HTML or
+/// quotes, and a list marker the text starts with
+fn escape_markdown(text: &str) -> String {
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let list_marker = if text.starts_with(['-', '+']) {
+        Some(0)
+    } else if digits > 0 && text[digits..].starts_with(['.', ')']) {
+        Some(digits)
+    } else {
+        None
+    };
+    let mut escaped = String::with_capacity(text.len());
+    for (i, c) in text.char_indices() {
+        if matches!(c, '\\' | '*' | '_' | '[' | ']' | '#' | '<' | '>' | '`')
+            || list_marker == Some(i)
+        {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Citation {
+    fn reference(&self, format: &CitationFormat) -> String {
+        if format.translation && !self.translation.is_empty() {
+            format!("{} ({})", self.reference, self.translation)
+        } else {
+            self.reference.clone()
+        }
+    }
+
+    /// Text of every verse, with its number when the format asks for it
+    fn verse_lines(
+        &self,
+        format: &CitationFormat,
+        number: impl Fn(u32) -> String,
+        text: impl Fn(&str) -> String,
+    ) -> Vec<String> {
+        self.verses
+            .iter()
+            .map(|v| {
+                let verse = text(v.text.trim());
+                if format.verse_numbers {
+                    format!("{} {verse}", number(v.number))
+                } else {
+                    verse
+                }
+            })
+            .collect()
+    }
+
+    fn join(format: &CitationFormat, lines: Vec<String>, line_break: &str) -> String {
+        match format.layout {
+            CitationLayout::Paragraph => lines.join(" "),
+            CitationLayout::Lines => lines.join(line_break),
+        }
+    }
+
+    pub fn plain(&self, format: &CitationFormat) -> String {
+        let body = Self::join(
+            format,
+            self.verse_lines(format, |n| n.to_string(), str::to_string),
+            "\n",
+        );
+        let reference = self.reference(format);
+        match format.reference {
+            ReferencePosition::Before => format!("{reference}\n{body}"),
+            ReferencePosition::After => format!("{body}\n— {reference}"),
+        }
+    }
+
+    pub fn markdown(&self, format: &CitationFormat) -> String {
+        let lines = self.verse_lines(format, |n| format!("<sup>{n}</sup>"), escape_markdown);
+        let body = match format.layout {
+            CitationLayout::Paragraph => format!("> {}", lines.join(" ")),
+            CitationLayout::Lines => lines
+                .iter()
+                .map(|line| format!("> {line}"))
+                .collect::<Vec<_>>()
+                .join("\n>\n"),
+        };
+        let reference = format!("**{}**", escape_markdown(&self.reference(format)));
+        match format.reference {
+            ReferencePosition::Before => format!("{reference}\n\n{body}"),
+            ReferencePosition::After => format!("{body}\n>\n> — {reference}"),
+        }
+    }
+
+    pub fn html(&self, format: &CitationFormat) -> String {
+        let lines = self
+            .verses
+            .iter()
+            .map(|v| {
+                let text = escape_html(v.text.trim());
+                if format.verse_numbers {
+                    format!("<sup>{}</sup> {text}", v.number)
+                } else {
+                    text
+                }
+            })
+            .collect::<Vec<_>>();
+        let body = match format.layout {
+            CitationLayout::Paragraph => format!("<p>{}</p>", lines.join(" ")),
+            CitationLayout::Lines => lines
+                .iter()
+                .map(|line| format!("<p>{line}</p>"))
+                .collect::<Vec<_>>()
+                .join(""),
+        };
+        let reference = format!("<cite>{}</cite>", escape_html(&self.reference(format)));
+        match format.reference {
+            ReferencePosition::Before => {
+                format!("<blockquote><footer>{reference}</footer>{body}</blockquote>")
+            }
+            ReferencePosition::After => {
+                format!("<blockquote>{body}<footer>— {reference}</footer></blockquote>")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn john_3() -> Citation {
+        Citation {
+            reference: "John 3:16-17".to_string(),
+            translation: "KJV".to_string(),
+            verses: vec![
+                CitedVerse {
+                    number: 16,
+                    text: "For God so loved the world ".to_string(),
+                },
+                CitedVerse {
+                    number: 17,
+                    text: "For God sent not his Son".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn plain_defaults_to_a_paragraph_with_the_reference_after() {
+        assert_eq!(
+            john_3().plain(&CitationFormat::default()),
+            "For God so loved the world For God sent not his Son\n— John 3:16-17 (KJV)"
+        );
+    }
+
+    #[test]
+    fn plain_lines_with_numbers_and_the_reference_before() {
+        let format = CitationFormat {
+            reference: ReferencePosition::Before,
+            translation: false,
+            verse_numbers: true,
+            layout: CitationLayout::Lines,
+        };
+        assert_eq!(
+            john_3().plain(&format),
+            "John 3:16-17\n16 For God so loved the world\n17 For God sent not his Son"
+        );
+    }
+
+    #[test]
+    fn markdown_quotes_every_line() {
+        let format = CitationFormat {
+            verse_numbers: true,
+            layout: CitationLayout::Lines,
+            ..Default::default()
+        };
+        assert_eq!(
+            john_3().markdown(&format),
+            "> <sup>16</sup> For God so loved the world\n>\n> <sup>17</sup> For God sent not his Son\n>\n> — **John 3:16-17 (KJV)**"
+        );
+    }
+
+    #[test]
+    fn markdown_escapes_its_syntax() {
+        let citation = Citation {
+            reference: "Psalm 23:1".to_string(),
+            translation: String::new(),
+            verses: vec![CitedVerse {
+                number: 1,
+                text: "*The* [LORD] is my_shepherd #1 \\".to_string(),
+            }],
+        };
+        assert_eq!(
+            citation.markdown(&CitationFormat::default()),
+            "> \\*The\\* \\[LORD\\] is my\\_shepherd \\#1 \\\\\n>\n> — **Psalm 23:1**"
+        );
+    }
+
+    #[test]
+    fn markdown_escapes_lines_read_as_blocks() {
+        let citation = Citation {
+            reference: "Psalm 23:1-4".to_string(),
+            translation: String::new(),
+            verses: [
+                "- The LORD is my shepherd",
+                "+ I shall not want",
+                "12. He maketh me <lie> down",
+                "> `He` restoreth my soul",
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(i, text)| CitedVerse {
+                number: i as u32 + 1,
+                text: text.to_string(),
+            })
+            .collect(),
+        };
+        let format = CitationFormat {
+            layout: CitationLayout::Lines,
+            ..CitationFormat::default()
+        };
+        assert_eq!(
+            citation.markdown(&format),
+            "> \\- The LORD is my shepherd\n>\n\
+             > \\+ I shall not want\n>\n\
+             > 12\\. He maketh me \\<lie\\> down\n>\n\
+             > \\> \\`He\\` restoreth my soul\n>\n\
+             > — **Psalm 23:1-4**"
+        );
+    }
+
+    #[test]
+    fn html_escapes_the_text() {
+        let citation = Citation {
+            reference: "Proverbs 3:5".to_string(),
+            translation: "KJV".to_string(),
+            verses: vec![CitedVerse {
+                number: 5,
+                text: "Trust <in> the \"LORD\" & lean not".to_string(),
+            }],
+        };
+        assert_eq!(
+            citation.html(&CitationFormat::default()),
+            "<blockquote><p>Trust &lt;in&gt; the &quot;LORD&quot; &amp; lean not</p>\
+             <footer>— <cite>Proverbs 3:5 (KJV)</cite></footer></blockquote>"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use freya::prelude::*;
+use freya::radio::*;
+
+use crate::components::empty_state;
+use crate::history::{NavigationHistory, navigate};
+use crate::modules::CommentaryHit;
+use crate::reference::VerseRef;
+use crate::{AppChannel, AppState};
+
+/// Commentary entries covering the selected verse, or matching a search inside commentaries
+#[derive(Clone, PartialEq)]
+pub struct CommentaryPanel {
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+    key: DiffKey,
+}
+
+impl CommentaryPanel {
+    pub fn new(
+        selected_verse: impl Into<State<Option<VerseRef>>>,
+        history: impl Into<State<NavigationHistory<VerseRef>>>,
+    ) -> Self {
+        Self {
+            selected_verse: selected_verse.into(),
+            history: history.into(),
+            key: DiffKey::None,
+        }
+    }
+}
+
+impl KeyExt for CommentaryPanel {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+impl Component for CommentaryPanel {
+    fn render(&self) -> impl IntoElement {
+        let modules = use_radio::<AppState, AppChannel>(AppChannel::Modules);
+        let catalog = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+        let mut search = use_state(String::new);
+        let selected_verse = self.selected_verse;
+        let history = self.history;
+
+        if modules.read().modules.commentaries.is_empty() {
+            return empty_state("No commentaries installed. Use Tools > Install Modules.");
+        }
+
+        let Some(verse) = selected_verse.read().clone() else {
+            return empty_state("Select a verse to read its commentaries");
+        };
+
+        let catalog = catalog.read().catalog.clone();
+        let bible_id = verse.bible_id.clone();
+        let query = search.read().trim().to_string();
+        let hits = if query.is_empty() {
+            // Commentaries are numbered as the KJV
+            let (chapter, number) = verse.to_standard(&catalog);
+            modules
+                .read()
+                .modules
+                .comments(&verse.book_id, chapter, number)
+        } else {
+            modules.read().modules.search_comments(&query, 50)
+        };
+
+        rect()
+            .expanded()
+            .content(Content::Flex)
+            .vertical()
+            .spacing(10.)
+            .child(
+                Input::new(search)
+                    .width(Size::Fill)
+                    .placeholder("Search in commentaries...")
+                    .on_submit(move |v| search.set(v)),
+            )
+            .child(if hits.is_empty() {
+                empty_state(if query.is_empty() {
+                    "No commentary covers this verse"
+                } else {
+                    "No results"
+                })
+            } else {
+                ScrollView::new()
+                    .expanded()
+                    .direction(Direction::Vertical)
+                    .spacing(10.)
+                    .children(hits.into_iter().enumerate().map(|(i, hit)| {
+                        let CommentaryHit {
+                            module_name, entry, ..
+                        } = hit;
+                        let book_name = catalog
+                            .book(&bible_id, &entry.book)
+                            .map(|b| b.name.clone())
+                            .unwrap_or_else(|| entry.book.clone());
+                        let target = VerseRef::from_standard(
+                            &catalog,
+                            bible_id.clone(),
+                            entry.book.clone(),
+                            entry.chapter,
+                            entry.verse_start,
+                        );
+                        rect()
+                            .key(i)
+                            .background(Color::from_hex("#2C2C2C").unwrap())
+                            .rounded()
+                            .padding(10.0)
+                            .content(Content::Flex)
+                            .vertical()
+                            .spacing(5.0)
+                            .width(Size::Fill)
+                            .child(
+                                rect()
+                                    .horizontal()
+                                    .width(Size::Fill)
+                                    .main_align(Alignment::SpaceBetween)
+                                    .child(
+                                        Button::new()
+                                            .compact()
+                                            .on_press(move |_| {
+                                                search.set(String::new());
+                                                navigate(selected_verse, history, target.clone());
+                                            })
+                                            .child(
+                                                label()
+                                                    .color(Color::from_hex("#6FA8DC").unwrap())
+                                                    .font_size(12.0)
+                                                    .font_weight(FontWeight::BOLD)
+                                                    .text(format!(
+                                                        "{book_name} {}",
+                                                        entry.range_label()
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        label()
+                                            .color(Color::from_hex("#888888").unwrap())
+                                            .font_size(12.0)
+                                            .text(module_name),
+                                    ),
+                            )
+                            .maybe_child(entry.title.map(|title| {
+                                label()
+                                    .color(Color::WHITE)
+                                    .font_weight(FontWeight::BOLD)
+                                    .text(title)
+                                    .into_element()
+                            }))
+                            .child(
+                                label()
+                                    .color(Color::from_hex("#CCCCCC").unwrap())
+                                    .font_size(14.0)
+                                    .text(entry.text),
+                            )
+                            .into_element()
+                    }))
+                    .into_element()
+            })
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
@@ -0,0 +1,456 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use async_io::Timer;
+use freya::{prelude::*, radio::*};
+use futures::StreamExt;
+use kanal::{Receiver, Sender, unbounded};
+use setup_core::{DbSink, TantivySink};
+
+use crate::catalog::{CanonicalBook, Catalog};
+use crate::components::empty_state;
+use crate::history::{NavigationHistory, navigate};
+use crate::reference::VerseRef;
+use crate::{AppChannel, AppState};
+
+/// Reference from a verse of the open chapter to another passage
+#[derive(Clone, PartialEq, Debug)]
+pub struct CrossReference {
+    /// Verse of the open chapter the reference belongs to
+    pub from: VerseRef,
+    /// Referenced verse, numbered as the open Bible
+    pub target: VerseRef,
+    pub text: String,
+    /// Votes of the reference in the installed cross reference datasets
+    pub votes: Option<i32>,
+}
+
+/// Reference shown in the list, with the votes it got from every verse in scope
+#[derive(Clone, PartialEq, Debug)]
+struct RankedReference {
+    reference: CrossReference,
+    book_order: usize,
+    votes: Option<i32>,
+}
+
+/// Deduplicates the references of the verses in `scope`, grouped by book in canonical order
+/// and ranked inside every book by their votes, the ones without votes last
+fn rank_references(
+    references: &[CrossReference],
+    scope: Option<&RangeInclusive<u32>>,
+) -> Vec<RankedReference> {
+    let mut ranked = Vec::<RankedReference>::new();
+    let mut positions = HashMap::<&VerseRef, usize>::new();
+    for reference in references
+        .iter()
+        .filter(|r| scope.is_none_or(|s| s.contains(&r.from.verse)))
+    {
+        if let Some(i) = positions.get(&reference.target) {
+            let votes = &mut ranked[*i].votes;
+            *votes = match (*votes, reference.votes) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            };
+            continue;
+        }
+        positions.insert(&reference.target, ranked.len());
+        ranked.push(RankedReference {
+            reference: reference.clone(),
+            book_order: CanonicalBook::by_id(&reference.target.book_id)
+                .map_or(usize::MAX, CanonicalBook::order),
+            votes: reference.votes,
+        });
+    }
+    ranked.sort_by(|a, b| {
+        let (a_target, b_target) = (&a.reference.target, &b.reference.target);
+        (a.book_order, &a_target.book_id)
+            .cmp(&(b.book_order, &b_target.book_id))
+            .then(b.votes.cmp(&a.votes))
+            .then(a_target.cmp(b_target))
+    });
+    ranked
+}
+
+/// Amount of distinct references of the verses in `scope`
+pub fn count_references(
+    references: &[CrossReference],
+    scope: Option<&RangeInclusive<u32>>,
+) -> usize {
+    references
+        .iter()
+        .filter(|r| scope.is_none_or(|s| s.contains(&r.from.verse)))
+        .map(|r| &r.target)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// Verses around a reference, shown while the pointer is over it
+#[derive(Clone, PartialEq, Debug)]
+struct ReferencePreview {
+    title: String,
+    verse: u32,
+    verses: Vec<(u32, String)>,
+}
+
+impl ReferencePreview {
+    fn load(database: &TantivySink, catalog: &Catalog, target: &VerseRef) -> Option<Self> {
+        let chapter = database
+            .get_crossreferences(
+                &target.bible_id,
+                &target.book_id,
+                target.chapter as _,
+                target.verse as _,
+            )
+            .ok()??;
+        let context = target.verse.saturating_sub(1)..=target.verse + 1;
+        Some(Self {
+            title: format!("{} {}", target.book_name(catalog), target.chapter),
+            verse: target.verse,
+            verses: chapter
+                .verses
+                .iter()
+                .filter(|v| context.contains(&(v.verse_number as u32)))
+                .map(|v| (v.verse_number as u32, v.text.clone()))
+                .collect(),
+        })
+    }
+}
+
+/// Cross references of the selected verses, or of the whole chapter
+pub struct CrossReferences {
+    database: Arc<TantivySink>,
+    references: Vec<CrossReference>,
+    verses: RangeInclusive<u32>,
+    whole_chapter: State<bool>,
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+    key: DiffKey,
+}
+
+impl PartialEq for CrossReferences {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.database, &other.database)
+            && self.references == other.references
+            && self.verses == other.verses
+            && self.whole_chapter == other.whole_chapter
+            && self.selected_verse == other.selected_verse
+            && self.history == other.history
+    }
+}
+
+impl CrossReferences {
+    pub fn new(
+        database: Arc<TantivySink>,
+        references: Vec<CrossReference>,
+        selected_verse: impl Into<State<Option<VerseRef>>>,
+        history: impl Into<State<NavigationHistory<VerseRef>>>,
+    ) -> Self {
+        Self {
+            database,
+            references,
+            verses: 1..=1,
+            whole_chapter: State::create(false),
+            selected_verse: selected_verse.into(),
+            history: history.into(),
+            key: DiffKey::None,
+        }
+    }
+
+    /// Verses of the open chapter whose references are listed
+    pub fn verses(mut self, verses: RangeInclusive<u32>) -> Self {
+        self.verses = verses;
+        self
+    }
+
+    pub fn whole_chapter(mut self, whole_chapter: impl Into<State<bool>>) -> Self {
+        self.whole_chapter = whole_chapter.into();
+        self
+    }
+}
+
+impl KeyExt for CrossReferences {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+impl Component for CrossReferences {
+    fn render(&self) -> impl IntoElement {
+        let catalog = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+        let mut hovered = use_state(|| None::<usize>);
+        // The surrounding verses are read in the background, like the reference graph, so
+        // moving over the list never waits for the index
+        let mut preview = use_state(|| None::<(usize, ReferencePreview)>);
+        let mut loading = use_state(|| None::<usize>);
+        let mut whole_chapter = self.whole_chapter;
+        let selected_verse = self.selected_verse;
+        let history = self.history;
+        let database = self.database.clone();
+
+        let (tx, rx): (
+            Sender<(usize, Option<ReferencePreview>)>,
+            Receiver<(usize, Option<ReferencePreview>)>,
+        ) = use_hook(unbounded);
+
+        use_hook(|| {
+            let rx = rx.clone();
+
+            spawn(async move {
+                let mut interval = Timer::interval(Duration::from_millis(120));
+                loop {
+                    interval.next().await;
+
+                    while let Ok(Some((i, loaded))) = rx.try_recv() {
+                        // Previews of references left while they were read are dropped
+                        if *loading.peek() == Some(i) {
+                            loading.set(None);
+                            preview.set(loaded.map(|loaded| (i, loaded)));
+                        }
+                    }
+                }
+            });
+        });
+
+        // Shared with the threads reading the previews
+        let catalog = Arc::new(catalog.read().catalog.clone());
+
+        let scope = (!*whole_chapter.read()).then(|| self.verses.clone());
+        let ranked = rank_references(&self.references, scope.as_ref());
+
+        let loading_card = loading.read().is_some().then(|| {
+            rect()
+                .width(Size::Fill)
+                .background(Color::from_hex("#252525").unwrap())
+                .rounded()
+                .padding(10.0)
+                .child(
+                    label()
+                        .color(Color::from_hex("#888888").unwrap())
+                        .font_size(12.0)
+                        .text("Loading..."),
+                )
+                .into_element()
+        });
+
+        // Surrounding verses of the reference under the pointer, read once when it is hovered
+        let preview_card = preview.read().as_ref().map(|(_, preview)| {
+            let spans = preview.verses.iter().flat_map(|(number, text)| {
+                [
+                    Span::new(format!("{number} "))
+                        .color(Color::LIGHT_GRAY.with_a(75))
+                        .font_weight(FontWeight::BOLD),
+                    Span::new(format!("{text} ")).color(if *number == preview.verse {
+                        Color::WHITE
+                    } else {
+                        Color::from_hex("#999999").unwrap()
+                    }),
+                ]
+            });
+            rect()
+                .width(Size::Fill)
+                .background(Color::from_hex("#252525").unwrap())
+                .rounded()
+                .padding(10.0)
+                .vertical()
+                .spacing(5.0)
+                .child(
+                    label()
+                        .color(Color::from_hex("#888888").unwrap())
+                        .font_size(12.0)
+                        .font_weight(FontWeight::BOLD)
+                        .text(preview.title.clone()),
+                )
+                .child(
+                    paragraph()
+                        .width(Size::Fill)
+                        .font_size(14.0)
+                        .spans_iter(spans),
+                )
+                .into_element()
+        });
+
+        let open = move |target: VerseRef| {
+            hovered.set(None);
+            loading.set(None);
+            preview.set(None);
+            navigate(selected_verse, history, target);
+        };
+
+        let scope_label = match &scope {
+            Some(verses) if verses.start() == verses.end() => format!("Verse {}", verses.start()),
+            Some(verses) => format!("Verses {}-{}", verses.start(), verses.end()),
+            None => "Whole chapter".to_string(),
+        };
+
+        let mut groups = Vec::<(String, Vec<(usize, RankedReference)>)>::new();
+        for (i, ranked) in ranked.into_iter().enumerate() {
+            match groups.last_mut() {
+                Some((_, refs))
+                    if refs[0].1.reference.target.book_id == ranked.reference.target.book_id =>
+                {
+                    refs.push((i, ranked))
+                }
+                _ => groups.push((
+                    ranked.reference.target.book_name(&catalog),
+                    vec![(i, ranked)],
+                )),
+            }
+        }
+
+        rect()
+            .expanded()
+            .content(Content::Flex)
+            .vertical()
+            .spacing(10.)
+            .child(
+                rect()
+                    .horizontal()
+                    .width(Size::Fill)
+                    .main_align(Alignment::SpaceBetween)
+                    .cross_align(Alignment::Center)
+                    .child(
+                        label()
+                            .color(Color::from_hex("#888888").unwrap())
+                            .font_size(12.0)
+                            .font_weight(FontWeight::BOLD)
+                            .text(scope_label),
+                    )
+                    .child(
+                        Button::new()
+                            .compact()
+                            .background(if *whole_chapter.read() {
+                                Color::from_hex("#3A3A3A").unwrap()
+                            } else {
+                                Color::from_hex("#2C2C2C").unwrap()
+                            })
+                            .hover_background(Color::from_hex("#353535").unwrap())
+                            .on_press(move |_| whole_chapter.set(!whole_chapter()))
+                            .child(
+                                label()
+                                    .color(Color::WHITE)
+                                    .font_size(12.0)
+                                    .text("Whole chapter"),
+                            ),
+                    ),
+            )
+            .child(if groups.is_empty() {
+                empty_state("No cross references")
+            } else {
+                ScrollView::new()
+                    .expanded()
+                    .direction(Direction::Vertical)
+                    .spacing(10.)
+                    .children(groups.into_iter().map(|(book_name, refs)| {
+                        rect()
+                            .key(&book_name)
+                            .width(Size::Fill)
+                            .vertical()
+                            .spacing(10.)
+                            .child(
+                                label()
+                                    .color(Color::WHITE)
+                                    .font_size(13.0)
+                                    .font_weight(FontWeight::BOLD)
+                                    .text(format!("{book_name} · {}", refs.len())),
+                            )
+                            .children(refs.into_iter().map(|(i, ranked)| {
+                                let reference = ranked.reference;
+                                let target = reference.target.clone();
+                                let is_hovered = *hovered.read() == Some(i);
+                                let title = target.localized(&catalog);
+                                let mut open = open.clone();
+                                let database = database.clone();
+                                let catalog = catalog.clone();
+                                let tx = tx.clone();
+                                let hovered_target = target.clone();
+                                rect()
+                                    .key(i)
+                                    .background(if is_hovered {
+                                        Color::from_hex("#353535").unwrap()
+                                    } else {
+                                        Color::from_hex("#2C2C2C").unwrap()
+                                    })
+                                    .rounded()
+                                    .padding(10.0)
+                                    .content(Content::Flex)
+                                    .vertical()
+                                    .spacing(5.0)
+                                    .width(Size::Fill)
+                                    .on_pointer_enter(move |_| {
+                                        hovered.set(Some(i));
+                                        preview.set(None);
+                                        loading.set(Some(i));
+                                        let database = database.clone();
+                                        let catalog = catalog.clone();
+                                        let tx = tx.clone();
+                                        let target = hovered_target.clone();
+                                        thread::spawn(move || {
+                                            let loaded = ReferencePreview::load(
+                                                &database, &catalog, &target,
+                                            );
+                                            let _ = tx.send((i, loaded));
+                                        });
+                                        Cursor::set(CursorIcon::Pointer);
+                                    })
+                                    .on_pointer_leave(move |_| {
+                                        if *hovered.read() == Some(i) {
+                                            hovered.set(None);
+                                        }
+                                        if preview.read().as_ref().is_some_and(|(p, _)| *p == i) {
+                                            preview.set(None);
+                                        }
+                                        if *loading.read() == Some(i) {
+                                            loading.set(None);
+                                        }
+                                        Cursor::set(CursorIcon::default());
+                                    })
+                                    .on_press(move |_| {
+                                        Cursor::set(CursorIcon::default());
+                                        open(target.clone());
+                                    })
+                                    .child(
+                                        rect()
+                                            .horizontal()
+                                            .width(Size::Fill)
+                                            .main_align(Alignment::SpaceBetween)
+                                            .child(
+                                                label()
+                                                    .color(Color::from_hex("#6FA8DC").unwrap())
+                                                    .font_size(12.0)
+                                                    .font_weight(FontWeight::BOLD)
+                                                    .text(title),
+                                            )
+                                            .maybe_child(ranked.votes.map(|votes| {
+                                                label()
+                                                    .color(Color::from_hex("#888888").unwrap())
+                                                    .font_size(12.0)
+                                                    .text(format!("▲ {votes}"))
+                                                    .into_element()
+                                            })),
+                                    )
+                                    .child(
+                                        label()
+                                            .color(Color::from_hex("#CCCCCC").unwrap())
+                                            .font_size(14.0)
+                                            .max_lines(2)
+                                            .text_overflow(TextOverflow::Ellipsis)
+                                            .text(reference.text),
+                                    )
+                                    .into_element()
+                            }))
+                            .into_element()
+                    }))
+                    .into_element()
+            })
+            .maybe_child(loading_card)
+            .maybe_child(preview_card)
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
@@ -0,0 +1,123 @@
+use freya::prelude::*;
+use freya::radio::*;
+
+use crate::components::empty_state;
+use crate::{AppChannel, AppState};
+
+/// Definitions of the selected word in the installed dictionaries
+#[derive(Clone, PartialEq)]
+pub struct Glossary {
+    word: State<Option<String>>,
+    key: DiffKey,
+}
+
+impl Glossary {
+    pub fn new(word: impl Into<State<Option<String>>>) -> Self {
+        Self {
+            word: word.into(),
+            key: DiffKey::None,
+        }
+    }
+}
+
+impl KeyExt for Glossary {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+impl Component for Glossary {
+    fn render(&self) -> impl IntoElement {
+        let radio = use_radio::<AppState, AppChannel>(AppChannel::Modules);
+        let mut word = self.word;
+
+        if radio.read().modules.dictionaries.is_empty() {
+            return empty_state("No dictionaries installed. Use Tools > Install Modules.");
+        }
+
+        let Some(current) = word.read().clone() else {
+            return empty_state("Click a word to look it up");
+        };
+
+        let definitions = radio.read().modules.define(&current);
+        if definitions.is_empty() {
+            return empty_state(&format!("No entries found for \"{current}\""));
+        }
+
+        ScrollView::new()
+            .expanded()
+            .direction(Direction::Vertical)
+            .spacing(10.)
+            .children(definitions.into_iter().enumerate().map(|(i, definition)| {
+                rect()
+                    .key(i)
+                    .background(Color::from_hex("#2C2C2C").unwrap())
+                    .rounded()
+                    .padding(10.0)
+                    .content(Content::Flex)
+                    .vertical()
+                    .spacing(5.0)
+                    .width(Size::Fill)
+                    .child(
+                        rect()
+                            .horizontal()
+                            .width(Size::Fill)
+                            .main_align(Alignment::SpaceBetween)
+                            .child(
+                                label()
+                                    .color(Color::WHITE)
+                                    .font_size(16.0)
+                                    .font_weight(FontWeight::BOLD)
+                                    .text(definition.entry.headword.clone()),
+                            )
+                            .child(
+                                label()
+                                    .color(Color::from_hex("#888888").unwrap())
+                                    .font_size(12.0)
+                                    .text(definition.module.clone()),
+                            ),
+                    )
+                    .child(
+                        label()
+                            .color(Color::from_hex("#CCCCCC").unwrap())
+                            .font_size(14.0)
+                            .text(definition.entry.definition.clone()),
+                    )
+                    .maybe(!definition.entry.see_also.is_empty(), |r| {
+                        r.child(
+                            rect()
+                                .horizontal()
+                                .spacing(5.)
+                                .child(
+                                    label()
+                                        .color(Color::from_hex("#888888").unwrap())
+                                        .font_size(12.0)
+                                        .text("See also:"),
+                                )
+                                .children(definition.entry.see_also.iter().map(|related| {
+                                    let related = related.clone();
+                                    Button::new()
+                                        .compact()
+                                        .on_press({
+                                            let related = related.clone();
+                                            move |_| word.set(Some(related.clone()))
+                                        })
+                                        .child(
+                                            label()
+                                                .color(Color::from_hex("#6FA8DC").unwrap())
+                                                .font_size(12.0)
+                                                .text(related),
+                                        )
+                                        .into_element()
+                                })),
+                        )
+                    })
+                    .into_element()
+            }))
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
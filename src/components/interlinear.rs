@@ -0,0 +1,242 @@
+use freya::prelude::*;
+use freya::radio::*;
+
+use crate::components::{WordSelection, empty_state};
+use crate::modules::TaggedWord;
+use crate::reference::VerseRef;
+use crate::{AppChannel, AppState};
+
+/// Lines shown under every word of the interlinear text
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct InterlinearLines {
+    pub original: bool,
+    pub transliteration: bool,
+    pub strong: bool,
+    pub morphology: bool,
+}
+
+impl Default for InterlinearLines {
+    fn default() -> Self {
+        Self {
+            original: true,
+            transliteration: true,
+            strong: true,
+            morphology: false,
+        }
+    }
+}
+
+/// Chapter of a tagged Bible laid out word by word, with the original language below
+#[derive(Clone, PartialEq)]
+pub struct Interlinear {
+    bible_id: String,
+    book_id: String,
+    chapter: u32,
+    selected_verse: Option<u32>,
+    lines: State<InterlinearLines>,
+    on_word_click: Option<EventHandler<WordSelection>>,
+    key: DiffKey,
+}
+
+impl Interlinear {
+    pub fn new(
+        bible_id: impl Into<String>,
+        book_id: impl Into<String>,
+        chapter: u32,
+        lines: impl Into<State<InterlinearLines>>,
+    ) -> Self {
+        Self {
+            bible_id: bible_id.into(),
+            book_id: book_id.into(),
+            chapter,
+            selected_verse: None,
+            lines: lines.into(),
+            on_word_click: None,
+            key: DiffKey::None,
+        }
+    }
+
+    pub fn selected_verse(mut self, verse: impl Into<Option<u32>>) -> Self {
+        self.selected_verse = verse.into();
+        self
+    }
+
+    pub fn on_word_click(mut self, handler: impl Into<EventHandler<WordSelection>>) -> Self {
+        self.on_word_click = Some(handler.into());
+        self
+    }
+}
+
+impl KeyExt for Interlinear {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+fn toggle(
+    name: &'static str,
+    mut lines: State<InterlinearLines>,
+    flip: fn(&mut InterlinearLines) -> &mut bool,
+) -> Element {
+    let active = *flip(&mut lines.read().clone());
+    Button::new()
+        .compact()
+        .background(if active {
+            Color::from_hex("#3A3A3A").unwrap()
+        } else {
+            Color::from_hex("#2C2C2C").unwrap()
+        })
+        .hover_background(Color::from_hex("#353535").unwrap())
+        .on_press(move |_| {
+            lines.with_mut(|mut l| {
+                let line = flip(&mut l);
+                *line = !*line;
+            })
+        })
+        .child(
+            label()
+                .color(if active {
+                    Color::WHITE
+                } else {
+                    Color::from_hex("#888888").unwrap()
+                })
+                .font_size(12.0)
+                .text(name),
+        )
+        .into_element()
+}
+
+fn word_cell(
+    word: &TaggedWord,
+    lines: InterlinearLines,
+    original: Option<(String, String)>,
+) -> Rect {
+    let (lemma, transliteration) = original.unwrap_or_default();
+    rect()
+        .vertical()
+        .cross_align(Alignment::Center)
+        .padding((2., 4., 2., 4.))
+        .corner_radius(4.)
+        .child(
+            label()
+                .color(Color::from_hex("#FFFFFF").unwrap())
+                .font_size(15.0)
+                .text(word.text.clone()),
+        )
+        .maybe_child(lines.original.then(|| {
+            label()
+                .color(Color::from_hex("#E6C07B").unwrap())
+                .font_size(15.0)
+                .text(word.original.clone().unwrap_or(lemma))
+                .into_element()
+        }))
+        .maybe_child(lines.transliteration.then(|| {
+            label()
+                .color(Color::from_hex("#AAAAAA").unwrap())
+                .font_size(12.0)
+                .text(word.transliteration.clone().unwrap_or(transliteration))
+                .into_element()
+        }))
+        .maybe_child(lines.strong.then(|| {
+            label()
+                .color(Color::from_hex("#6FA8DC").unwrap())
+                .font_size(11.0)
+                .text(word.strong.clone().unwrap_or_default())
+                .into_element()
+        }))
+        .maybe_child(lines.morphology.then(|| {
+            label()
+                .color(Color::from_hex("#888888").unwrap())
+                .font_size(11.0)
+                .text(word.morph.clone().unwrap_or_default())
+                .into_element()
+        }))
+}
+
+impl Component for Interlinear {
+    fn render(&self) -> impl IntoElement {
+        let modules = use_radio::<AppState, AppChannel>(AppChannel::Modules);
+        let lines = self.lines;
+        let current_lines = *lines.read();
+
+        let library = modules.read().modules.clone();
+        let Some(tagged) = library.tagged_bible(&self.bible_id) else {
+            return empty_state("No tagged text installed for this Bible");
+        };
+        let verses = tagged.chapter(&self.book_id, self.chapter);
+        if verses.is_empty() {
+            return empty_state("This chapter is not tagged");
+        }
+
+        rect()
+            .width(Size::Fill)
+            .vertical()
+            .spacing(10.)
+            .child(
+                rect()
+                    .horizontal()
+                    .spacing(5.)
+                    .child(toggle("Original", lines, |l| &mut l.original))
+                    .child(toggle("Transliteration", lines, |l| &mut l.transliteration))
+                    .child(toggle("Strong's", lines, |l| &mut l.strong))
+                    .child(toggle("Parsing", lines, |l| &mut l.morphology)),
+            )
+            .children(verses.into_iter().map(|verse| {
+                let is_selected = self.selected_verse == Some(verse.verse);
+                rect()
+                    .key(verse.verse)
+                    .width(Size::Fill)
+                    .horizontal()
+                    .content(Content::Wrap)
+                    .spacing(4.)
+                    .padding(4.)
+                    .corner_radius(6.)
+                    .maybe(is_selected, |r| {
+                        r.background(Color::from_hex("#3A3A3A").unwrap())
+                    })
+                    .child(
+                        label()
+                            .color(Color::LIGHT_GRAY.with_a(75))
+                            .font_weight(FontWeight::BOLD)
+                            .text(verse.verse.to_string()),
+                    )
+                    .children(verse.words.iter().enumerate().map(|(i, word)| {
+                        let original = word
+                            .strong
+                            .as_deref()
+                            .and_then(|n| library.lexicon_entry(n))
+                            .map(|l| (l.lemma.clone(), l.transliteration.clone()));
+                        let cell = word_cell(word, current_lines, original);
+                        match (&self.on_word_click, word.strong.is_some()) {
+                            (Some(handler), true) => {
+                                let handler = handler.clone();
+                                let selection = WordSelection {
+                                    verse: VerseRef::new(
+                                        self.bible_id.clone(),
+                                        self.book_id.clone(),
+                                        self.chapter,
+                                        verse.verse,
+                                    ),
+                                    word: word
+                                        .text
+                                        .split_whitespace()
+                                        .next()
+                                        .unwrap_or_default()
+                                        .to_string(),
+                                    occurrence: verse.occurrence_of(i),
+                                };
+                                cell.on_press(move |_| handler.call(selection.clone()))
+                                    .into_element()
+                            }
+                            _ => cell.into_element(),
+                        }
+                    }))
+                    .into_element()
+            }))
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
@@ -0,0 +1,185 @@
+use freya::animation::*;
+use freya::prelude::*;
+
+/// Height of the bottom navigation bar, tall enough to be pressed with a thumb
+const NAV_HEIGHT: f32 = 64.;
+
+/// Whether the window should use the mobile layout. Android uses it up to tablet widths, other
+/// platforms only when the window is made very narrow.
+pub fn is_mobile_layout(window_width: f32) -> bool {
+    if cfg!(target_os = "android") {
+        window_width < 840.
+    } else {
+        window_width < 480.
+    }
+}
+
+/// Space covered by the system bars and the on-screen keyboard, that content must stay out of
+pub fn safe_area() -> Gaps {
+    #[cfg(target_os = "android")]
+    return crate::android::insets();
+    #[cfg(not(target_os = "android"))]
+    Gaps::new(0., 0., 0., 0.)
+}
+
+/// Screens of the mobile layout, picked from the bottom navigation bar
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash)]
+pub enum MobileScreen {
+    #[default]
+    Read,
+    Search,
+    Notes,
+    Library,
+}
+
+impl MobileScreen {
+    pub const ALL: [MobileScreen; 4] = [
+        MobileScreen::Read,
+        MobileScreen::Search,
+        MobileScreen::Notes,
+        MobileScreen::Library,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MobileScreen::Read => "Read",
+            MobileScreen::Search => "Search",
+            MobileScreen::Notes => "Notes",
+            MobileScreen::Library => "Library",
+        }
+    }
+
+    fn icon(&self) -> &'static str {
+        match self {
+            MobileScreen::Read => "¶",
+            MobileScreen::Search => "⌕",
+            MobileScreen::Notes => "✎",
+            MobileScreen::Library => "☰",
+        }
+    }
+
+    /// Place in the navigation bar, which decides the side a screen slides in from
+    fn position(&self) -> usize {
+        Self::ALL.iter().position(|s| s == self).unwrap_or_default()
+    }
+}
+
+/// Full-screen layout for phones: one screen at a time, switched from a bottom navigation bar
+/// and kept clear of the system bars and the keyboard.
+#[derive(Clone, PartialEq)]
+pub struct MobileShell {
+    screen: State<MobileScreen>,
+    screens: Vec<(MobileScreen, Element)>,
+    key: DiffKey,
+}
+
+impl MobileShell {
+    pub fn new(screen: impl Into<State<MobileScreen>>) -> Self {
+        Self {
+            screen: screen.into(),
+            screens: Vec::new(),
+            key: DiffKey::None,
+        }
+    }
+
+    /// Content shown while the screen is selected
+    pub fn screen(mut self, screen: MobileScreen, content: impl IntoElement) -> Self {
+        self.screens.push((screen, content.into_element()));
+        self
+    }
+}
+
+impl KeyExt for MobileShell {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+impl Component for MobileShell {
+    fn render(&self) -> impl IntoElement {
+        let mut screen = self.screen;
+        let current = screen();
+        let mut previous = use_state(|| current);
+        // 1 when the screen comes from the right, -1 from the left
+        let mut slide_from = use_state(|| 1f32);
+        let mut slide = use_animation(|_| {
+            AnimNum::new(1., 0.)
+                .function(Function::Expo)
+                .ease(Ease::Out)
+                .time(250)
+        });
+
+        if current != previous() {
+            slide_from.set(if current.position() > previous().position() {
+                1.
+            } else {
+                -1.
+            });
+            previous.set(current);
+            slide.start();
+        }
+
+        let width = Platform::get().root_size.read().width;
+        let offset = slide.read().value() * slide_from() * width;
+        let content = self
+            .screens
+            .iter()
+            .find(|(s, _)| *s == current)
+            .map(|(_, content)| content.clone());
+
+        rect()
+            .expanded()
+            .content(Content::Flex)
+            .vertical()
+            .padding(safe_area())
+            .child(
+                rect()
+                    .width(Size::Fill)
+                    .height(Size::flex(1.))
+                    .overflow(Overflow::Clip)
+                    .child(
+                        rect()
+                            .key(current.label())
+                            .expanded()
+                            .offset_x(offset)
+                            .maybe_child(content),
+                    ),
+            )
+            .child(
+                rect()
+                    .horizontal()
+                    .content(Content::Flex)
+                    .width(Size::Fill)
+                    .height(Size::px(NAV_HEIGHT))
+                    .background(Color::from_hex("#252525").unwrap())
+                    .children(MobileScreen::ALL.into_iter().map(|item| {
+                        let color = if item == current {
+                            Color::WHITE
+                        } else {
+                            Color::from_hex("#888888").unwrap()
+                        };
+                        Button::new()
+                            .width(Size::flex(1.))
+                            .height(Size::Fill)
+                            .background(Color::TRANSPARENT)
+                            .hover_background(Color::from_hex("#303030").unwrap())
+                            .on_press(move |_| screen.set(item))
+                            .child(
+                                rect()
+                                    .key(item.label())
+                                    .vertical()
+                                    .cross_align(Alignment::Center)
+                                    .spacing(2.)
+                                    .child(label().color(color).font_size(20.).text(item.icon()))
+                                    .child(label().color(color).font_size(12.).text(item.label())),
+                            )
+                            .into_element()
+                    })),
+            )
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
@@ -1,7 +1,48 @@
+use freya::prelude::*;
+
 mod autocomplete;
+mod commentary;
+mod cross_references;
+mod glossary;
+mod interlinear;
+mod mobile;
+mod notes;
+mod presentation_controls;
+mod reference_graph;
+mod selection_bar;
 mod toolbar;
+mod topics;
 mod verse_panel;
+mod word_study;
 
 pub use autocomplete::*;
+pub use commentary::*;
+pub use cross_references::*;
+pub use glossary::*;
+pub use interlinear::*;
+pub use mobile::*;
+pub use notes::*;
+pub use presentation_controls::*;
+pub use reference_graph::*;
+pub use selection_bar::*;
 pub use toolbar::*;
+pub use topics::*;
 pub use verse_panel::*;
+pub use word_study::*;
+
+/// Centered hint shown when a panel has nothing to display
+pub(crate) fn empty_state(text: &str) -> Element {
+    rect()
+        .content(Content::Flex)
+        .center()
+        .main_align(Alignment::Center)
+        .cross_align(Alignment::Center)
+        .height(Size::Fill)
+        .child(
+            label()
+                .color(Color::from_hex("#888888").unwrap())
+                .font_size(16.0)
+                .text(text.to_string()),
+        )
+        .into_element()
+}
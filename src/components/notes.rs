@@ -0,0 +1,130 @@
+use freya::prelude::*;
+use freya::radio::*;
+
+use crate::components::empty_state;
+use crate::history::{NavigationHistory, navigate};
+use crate::reference::{PassageRef, VerseRef};
+use crate::{AppChannel, AppState};
+
+/// Notes and bookmarks of the user, the newest first. Pressing one opens its passage.
+#[derive(Clone, PartialEq)]
+pub struct NotesPanel {
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+    on_navigate: Option<EventHandler<()>>,
+    key: DiffKey,
+}
+
+impl NotesPanel {
+    pub fn new(
+        selected_verse: impl Into<State<Option<VerseRef>>>,
+        history: impl Into<State<NavigationHistory<VerseRef>>>,
+    ) -> Self {
+        Self {
+            selected_verse: selected_verse.into(),
+            history: history.into(),
+            on_navigate: None,
+            key: DiffKey::None,
+        }
+    }
+
+    /// Called after opening the passage of a note or bookmark
+    pub fn on_navigate(mut self, handler: impl Into<EventHandler<()>>) -> Self {
+        self.on_navigate = Some(handler.into());
+        self
+    }
+}
+
+impl KeyExt for NotesPanel {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+impl Component for NotesPanel {
+    fn render(&self) -> impl IntoElement {
+        let annotations = use_radio::<AppState, AppChannel>(AppChannel::Annotations);
+        let catalog = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+        let selected_verse = self.selected_verse;
+        let history = self.history;
+        let on_navigate = self.on_navigate.clone();
+
+        // Notes show their text, bookmarks only the passage
+        let mut entries = {
+            let annotations = &annotations.read().annotations;
+            annotations
+                .notes
+                .iter()
+                .map(|note| (note.created, note.passage.clone(), Some(note.text.clone())))
+                .chain(
+                    annotations
+                        .bookmarks
+                        .iter()
+                        .map(|bookmark| (bookmark.created, bookmark.passage.clone(), None)),
+                )
+                .collect::<Vec<(u64, PassageRef, Option<String>)>>()
+        };
+        if entries.is_empty() {
+            return empty_state("Bookmarks and notes you add while reading show up here");
+        }
+        entries.sort_by(|a, b| b.0.cmp(&a.0));
+        let catalog = catalog.read().catalog.clone();
+
+        let open = move |target: VerseRef| {
+            navigate(selected_verse, history, target);
+            if let Some(on_navigate) = &on_navigate {
+                on_navigate.call(());
+            }
+        };
+
+        ScrollView::new()
+            .expanded()
+            .direction(Direction::Vertical)
+            .spacing(8.)
+            .children(
+                entries
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (_, passage, text))| {
+                        let open = open.clone();
+                        let target = passage.first();
+                        Button::new()
+                            .background(Color::from_hex("#2C2C2C").unwrap())
+                            .hover_background(Color::from_hex("#353535").unwrap())
+                            .on_press(move |_| open(target.clone()))
+                            .child(
+                                rect()
+                                    .key(i)
+                                    .vertical()
+                                    .spacing(5.)
+                                    .padding(8.)
+                                    .width(Size::Fill)
+                                    .child(
+                                        label()
+                                            .color(Color::WHITE)
+                                            .font_weight(FontWeight::BOLD)
+                                            .text(format!(
+                                                "{} {}",
+                                                if text.is_some() { "✎" } else { "★" },
+                                                passage.localized(&catalog)
+                                            )),
+                                    )
+                                    .maybe_child(text.map(|text| {
+                                        label()
+                                            .color(Color::from_hex("#BBBBBB").unwrap())
+                                            .max_lines(3)
+                                            .text_overflow(TextOverflow::Ellipsis)
+                                            .text(text)
+                                            .into_element()
+                                    })),
+                            )
+                            .into_element()
+                    }),
+            )
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
@@ -0,0 +1,238 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_io::Timer;
+use freya::prelude::*;
+use freya::radio::*;
+use futures::StreamExt;
+
+use crate::presentation::{
+    BACKGROUNDS, FONT_FAMILIES, Presentation, PresentationStyle, Slide, TEXT_COLORS,
+};
+use crate::{AppChannel, AppState};
+
+/// Controls of the projector window shown in the reader while presenting. The projector follows
+/// the verse selected in the reader
+#[derive(Clone, PartialEq)]
+pub struct PresentationControls {
+    chapter_key: String,
+    slides: Vec<Slide>,
+    verse: u32,
+    key: DiffKey,
+}
+
+impl PresentationControls {
+    pub fn new(chapter_key: impl Into<String>, slides: Vec<Slide>, verse: u32) -> Self {
+        Self {
+            chapter_key: chapter_key.into(),
+            slides,
+            verse,
+            key: DiffKey::None,
+        }
+    }
+}
+
+impl KeyExt for PresentationControls {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+fn control(text: impl Into<String>, active: bool) -> Button {
+    Button::new()
+        .compact()
+        .background(if active {
+            Color::from_hex("#3A3A3A").unwrap()
+        } else {
+            Color::from_hex("#2C2C2C").unwrap()
+        })
+        .hover_background(Color::from_hex("#353535").unwrap())
+        .child(
+            label()
+                .color(Color::WHITE)
+                .font_size(12.0)
+                .text(text.into()),
+        )
+}
+
+fn swatch(color: &'static str, active: bool, mut on_press: impl FnMut() + 'static) -> Element {
+    Button::new()
+        .compact()
+        .background(if active {
+            Color::from_hex("#3A3A3A").unwrap()
+        } else {
+            Color::from_hex("#2C2C2C").unwrap()
+        })
+        .hover_background(Color::from_hex("#353535").unwrap())
+        .on_press(move |_| on_press())
+        .child(
+            rect()
+                .width(Size::px(12.))
+                .height(Size::px(12.))
+                .rounded_full()
+                .background(Color::from_hex(color).unwrap()),
+        )
+        .into_element()
+}
+
+impl Component for PresentationControls {
+    fn render(&self) -> impl IntoElement {
+        let settings = use_radio::<AppState, AppChannel>(AppChannel::Settings);
+        let mut presentation = use_state(Presentation::snapshot);
+        // Verse of the reader the projector was last moved to
+        let followed = use_hook(|| Rc::new(RefCell::new(None::<(String, u32)>)));
+
+        use_hook(|| {
+            spawn(async move {
+                let mut interval = Timer::interval(Duration::from_millis(100));
+                loop {
+                    interval.next().await;
+                    let latest = Presentation::snapshot();
+                    if latest.revision != presentation.read().revision {
+                        presentation.set(latest);
+                    }
+                }
+            });
+        });
+
+        let current = presentation.read().clone();
+        if !current.active {
+            *followed.borrow_mut() = None;
+            return rect().into_element();
+        }
+
+        let target = (self.chapter_key.clone(), self.verse);
+        if followed.borrow().as_ref() != Some(&target) {
+            if followed.borrow().is_some() {
+                let slides = self.slides.clone();
+                let (chapter_key, verse) = target.clone();
+                Presentation::update(move |p| {
+                    if p.chapter_key != chapter_key {
+                        p.chapter_key = chapter_key;
+                        p.slides = slides;
+                    }
+                    p.current = p.slides.iter().position(|s| s.verse == verse).unwrap_or(0);
+                    p.blank = false;
+                });
+            }
+            *followed.borrow_mut() = Some(target);
+        }
+
+        let style = settings.read().settings.presentation.clone();
+        let update_style = move |update: &dyn Fn(&mut PresentationStyle)| {
+            let mut settings = settings;
+            let mut state = settings.write();
+            update(&mut state.settings.presentation);
+            state.settings.save();
+            let style = state.settings.presentation.clone();
+            Presentation::update(|p| p.style = Some(style));
+        };
+
+        let position = current
+            .current_slide()
+            .map(|slide| {
+                format!(
+                    "{} · {}/{}",
+                    slide.reference,
+                    current.current + 1,
+                    current.slides.len()
+                )
+            })
+            .unwrap_or_default();
+
+        rect()
+            .width(Size::Fill)
+            .vertical()
+            .spacing(6.)
+            .padding(8.)
+            .rounded()
+            .background(Color::from_hex("#252525").unwrap())
+            .on_global_key_down(|e: Event<KeyboardEventData>| {
+                // Clickers send Page Up and Page Down, which the reader does not use
+                if matches!(e.key, Key::Named(NamedKey::PageUp | NamedKey::PageDown)) {
+                    Presentation::handle_key(&e.key);
+                }
+            })
+            .child(
+                rect()
+                    .horizontal()
+                    .content(Content::Wrap)
+                    .cross_align(Alignment::Center)
+                    .spacing(6.)
+                    .child(
+                        label()
+                            .color(Color::from_hex("#e74c3c").unwrap())
+                            .font_size(12.0)
+                            .font_weight(FontWeight::BOLD)
+                            .text("● Presenting"),
+                    )
+                    .child(
+                        label()
+                            .color(Color::from_hex("#CCCCCC").unwrap())
+                            .font_size(12.0)
+                            .text(position),
+                    )
+                    .child(
+                        control("‹ Previous", false)
+                            .on_press(|_| Presentation::update(Presentation::previous)),
+                    )
+                    .child(
+                        control("Next ›", false)
+                            .on_press(|_| Presentation::update(Presentation::next)),
+                    )
+                    .child(
+                        control("Blank", current.blank)
+                            .on_press(|_| Presentation::update(|p| p.blank = !p.blank)),
+                    )
+                    .child(control("Stop", false).on_press(|_| Presentation::stop())),
+            )
+            .child(
+                rect()
+                    .horizontal()
+                    .content(Content::Wrap)
+                    .cross_align(Alignment::Center)
+                    .spacing(6.)
+                    .children(BACKGROUNDS.into_iter().map(|color| {
+                        let update_style = update_style.clone();
+                        swatch(color, style.background == color, move || {
+                            update_style(&|s| s.background = color.to_string())
+                        })
+                    }))
+                    .child(rect().width(Size::px(8.)))
+                    .children(TEXT_COLORS.into_iter().map(|color| {
+                        let update_style = update_style.clone();
+                        swatch(color, style.color == color, move || {
+                            update_style(&|s| s.color = color.to_string())
+                        })
+                    }))
+                    .child(rect().width(Size::px(8.)))
+                    .children(FONT_FAMILIES.into_iter().map(|family| {
+                        let update_style = update_style.clone();
+                        control(family, style.font_family == family)
+                            .on_press(move |_| {
+                                update_style(&|s| s.font_family = family.to_string())
+                            })
+                            .into_element()
+                    }))
+                    .child(control("A−", false).on_press({
+                        let update_style = update_style.clone();
+                        move |_| update_style(&|s| s.font_size = (s.font_size - 4.).max(24.))
+                    }))
+                    .child(
+                        label()
+                            .color(Color::WHITE)
+                            .font_size(12.0)
+                            .text(format!("{} px", style.font_size)),
+                    )
+                    .child(control("A+", false).on_press(move |_| {
+                        update_style(&|s| s.font_size = (s.font_size + 4.).min(160.))
+                    })),
+            )
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
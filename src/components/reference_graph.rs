@@ -0,0 +1,505 @@
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::TAU;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use async_io::Timer;
+use freya::engine::prelude::{Paint, PaintStyle, Point};
+use freya::prelude::*;
+use freya::radio::*;
+use futures::StreamExt;
+use kanal::{Receiver, Sender, unbounded};
+use setup_core::{DbSink, TantivySink};
+
+use crate::catalog::{CanonicalBook, Catalog};
+use crate::components::empty_state;
+use crate::history::{NavigationHistory, navigate};
+use crate::reference::VerseRef;
+use crate::{AppChannel, AppState};
+
+/// Nodes added at most, so three hops out of a popular verse stays readable
+const MAX_NODES: usize = 80;
+
+#[derive(Clone, Debug)]
+struct GraphNode {
+    key: VerseRef,
+    hop: usize,
+    degree: usize,
+    book_order: usize,
+    /// Position inside a `-1..1` square, the root at the center
+    position: (f32, f32),
+}
+
+/// Verses reachable from a verse by following cross references
+#[derive(Default, Debug)]
+struct ReferenceGraph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl ReferenceGraph {
+    /// Walks `hops` levels of references breadth first, `neighbors` returning the references of
+    /// a verse
+    fn build(
+        root: VerseRef,
+        hops: usize,
+        mut neighbors: impl FnMut(&VerseRef) -> Vec<VerseRef>,
+    ) -> Self {
+        let book_order = |book_id: &str| {
+            CanonicalBook::by_id(book_id)
+                .map(|book| book.order())
+                .unwrap_or(usize::MAX)
+        };
+        let mut graph = Self::default();
+        let mut positions = HashMap::<VerseRef, usize>::new();
+        let mut edges = HashSet::<(usize, usize)>::new();
+
+        positions.insert(root.clone(), 0);
+        graph.nodes.push(GraphNode {
+            book_order: book_order(&root.book_id),
+            key: root,
+            hop: 0,
+            degree: 0,
+            position: (0., 0.),
+        });
+
+        let mut frontier = vec![0];
+        for hop in 1..=hops {
+            let mut next = Vec::new();
+            for node in frontier {
+                let key = graph.nodes[node].key.clone();
+                for target in neighbors(&key) {
+                    let target = match positions.get(&target) {
+                        Some(i) => *i,
+                        None if graph.nodes.len() < MAX_NODES => {
+                            let i = graph.nodes.len();
+                            positions.insert(target.clone(), i);
+                            graph.nodes.push(GraphNode {
+                                book_order: book_order(&target.book_id),
+                                key: target,
+                                hop,
+                                degree: 0,
+                                position: (0., 0.),
+                            });
+                            next.push(i);
+                            i
+                        }
+                        None => continue,
+                    };
+                    if target != node && edges.insert((node.min(target), node.max(target))) {
+                        graph.edges.push((node, target));
+                        graph.nodes[node].degree += 1;
+                        graph.nodes[target].degree += 1;
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        graph.layout();
+        graph
+    }
+
+    /// Graph around `root` in its Bible, reading the references of every verse from the index
+    fn load(database: &TantivySink, catalog: &Catalog, root: VerseRef, hops: usize) -> Self {
+        let bible_id = root.bible_id.clone();
+        Self::build(root, hops, |node| {
+            // Cross references are numbered as the KJV
+            let (chapter, verse) = node.to_standard(catalog);
+            let Ok(Some(found)) =
+                database.get_crossreferences(&bible_id, &node.book_id, chapter as _, verse as _)
+            else {
+                return Vec::new();
+            };
+            found
+                .verses
+                .iter()
+                .filter(|v| v.verse_number as u32 == verse)
+                .flat_map(|v| v.cross_references.iter())
+                .filter_map(|cross_ref| {
+                    let book = catalog.book_by_name(&bible_id, &cross_ref.book_name)?;
+                    Some(VerseRef::from_standard(
+                        catalog,
+                        bible_id.clone(),
+                        book.id,
+                        cross_ref.chapter as u32,
+                        cross_ref.verse as u32,
+                    ))
+                })
+                .collect()
+        })
+    }
+
+    /// Places every hop on its own ring, nodes of the same book next to each other
+    fn layout(&mut self) {
+        let max_hop = self.nodes.iter().map(|n| n.hop).max().unwrap_or_default();
+        for hop in 1..=max_hop {
+            let mut ring = (0..self.nodes.len())
+                .filter(|i| self.nodes[*i].hop == hop)
+                .collect::<Vec<_>>();
+            ring.sort_by_key(|i| (self.nodes[*i].book_order, self.nodes[*i].key.clone()));
+            let radius = hop as f32 / max_hop as f32;
+            // Rotate every ring a bit so edges between rings do not overlap
+            let offset = hop as f32 * 0.35;
+            for (k, i) in ring.iter().enumerate() {
+                let angle = offset + TAU * k as f32 / ring.len() as f32;
+                self.nodes[*i].position = (radius * angle.cos(), radius * angle.sin());
+            }
+        }
+    }
+
+    fn node_radius(&self, node: usize) -> f32 {
+        (4. + 2. * (self.nodes[node].degree as f32).sqrt()).min(14.)
+    }
+
+    /// Node drawn under `point`, in pixels relative to a canvas of `size`
+    fn node_at(&self, point: (f32, f32), size: (f32, f32)) -> Option<usize> {
+        (0..self.nodes.len())
+            .map(|i| {
+                let (x, y) = to_canvas(self.nodes[i].position, size);
+                (i, (x - point.0).hypot(y - point.1))
+            })
+            .filter(|(i, distance)| *distance <= self.node_radius(*i) + 4.)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+}
+
+fn to_canvas(position: (f32, f32), (width, height): (f32, f32)) -> (f32, f32) {
+    let margin = 20.;
+    let radius = (width.min(height) / 2. - margin).max(0.);
+    (
+        width / 2. + position.0 * radius,
+        height / 2. + position.1 * radius,
+    )
+}
+
+/// Color of a book, spreading the canonical order around the hue circle
+fn book_color(book_order: usize) -> Color {
+    if book_order == usize::MAX {
+        return Color::from_hex("#888888").unwrap();
+    }
+    let hue = (book_order * 137 % 360) as f32;
+    let (s, v) = (0.55, 0.9);
+    let c = v * s;
+    let x = c * (1. - ((hue / 60.) % 2. - 1.).abs());
+    let (r, g, b) = match (hue / 60.) as u32 {
+        0 => (c, x, 0.),
+        1 => (x, c, 0.),
+        2 => (0., c, x),
+        3 => (0., x, c),
+        4 => (x, 0., c),
+        _ => (c, 0., x),
+    };
+    let m = v - c;
+    let channel = |value: f32| ((value + m) * 255.) as u8;
+    Color::from_rgb(channel(r), channel(g), channel(b))
+}
+
+/// Network of the cross references around the selected verse
+pub struct ReferenceGraphView {
+    database: Arc<TantivySink>,
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+    key: DiffKey,
+}
+
+impl PartialEq for ReferenceGraphView {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.database, &other.database)
+            && self.selected_verse == other.selected_verse
+            && self.history == other.history
+    }
+}
+
+impl ReferenceGraphView {
+    pub fn new(
+        database: Arc<TantivySink>,
+        selected_verse: impl Into<State<Option<VerseRef>>>,
+        history: impl Into<State<NavigationHistory<VerseRef>>>,
+    ) -> Self {
+        Self {
+            database,
+            selected_verse: selected_verse.into(),
+            history: history.into(),
+            key: DiffKey::None,
+        }
+    }
+}
+
+impl KeyExt for ReferenceGraphView {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+/// Identifies the graph of a verse followed for a number of hops
+fn graph_key(root: &VerseRef, hops: usize) -> String {
+    format!("{}/{}/{hops}", root.chapter_key(), root.verse)
+}
+
+impl Component for ReferenceGraphView {
+    fn render(&self) -> impl IntoElement {
+        let catalog = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+        let mut hops = use_state(|| 2usize);
+        let mut hovered = use_state(|| None::<usize>);
+        // Building the graph queries the database once per node, so it is built in the
+        // background and kept until the verse changes
+        let mut built = use_state(|| None::<(String, Arc<ReferenceGraph>)>);
+        let mut building = use_state(|| None::<String>);
+        let canvas_size = use_hook(|| Rc::new(Cell::new((0f32, 0f32))));
+        let selected_verse = self.selected_verse;
+        let history = self.history;
+        let database = self.database.clone();
+
+        let (tx, rx): (
+            Sender<(String, ReferenceGraph)>,
+            Receiver<(String, ReferenceGraph)>,
+        ) = use_hook(unbounded);
+
+        use_hook(|| {
+            let rx = rx.clone();
+
+            spawn(async move {
+                let mut interval = Timer::interval(Duration::from_millis(120));
+                loop {
+                    interval.next().await;
+
+                    while let Ok(Some((key, graph))) = rx.try_recv() {
+                        // Graphs of verses left while they were built are dropped
+                        if building.peek().as_ref() == Some(&key) {
+                            building.set(None);
+                            built.set(Some((key, Arc::new(graph))));
+                        }
+                    }
+                }
+            });
+        });
+
+        use_side_effect(move || {
+            let Some(root) = selected_verse.read().clone() else {
+                return;
+            };
+            let hops = hops();
+            let cache_key = graph_key(&root, hops);
+            let is_built = built
+                .peek()
+                .as_ref()
+                .is_some_and(|(key, _)| *key == cache_key);
+            if is_built || building.peek().as_ref() == Some(&cache_key) {
+                return;
+            }
+            building.set(Some(cache_key.clone()));
+            let catalog = catalog.read().catalog.clone();
+            let database = database.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let graph = ReferenceGraph::load(&database, &catalog, root, hops);
+                let _ = tx.send((cache_key, graph));
+            });
+        });
+
+        let Some(root) = selected_verse.read().clone() else {
+            return empty_state("Select a verse to see its network");
+        };
+        let catalog = catalog.read().catalog.clone();
+        let cache_key = graph_key(&root, hops());
+        let current = built
+            .read()
+            .as_ref()
+            .filter(|(key, _)| *key == cache_key)
+            .map(|(_, graph)| graph.clone());
+        let Some(graph) = current else {
+            return empty_state("Following the cross references...");
+        };
+
+        if graph.nodes.len() < 2 {
+            return empty_state("This verse has no cross references");
+        }
+
+        let on_pointer_move = {
+            let graph = graph.clone();
+            let canvas_size = canvas_size.clone();
+            move |e: Event<PointerEventData>| {
+                let location = e.element_location();
+                let node = graph.node_at((location.x as f32, location.y as f32), canvas_size.get());
+                if *hovered.read() != node {
+                    hovered.set(node);
+                }
+            }
+        };
+        let on_press = {
+            let graph = graph.clone();
+            let canvas_size = canvas_size.clone();
+            move |e: Event<PointerEventData>| {
+                let location = e.element_location();
+                let Some(node) =
+                    graph.node_at((location.x as f32, location.y as f32), canvas_size.get())
+                else {
+                    return;
+                };
+                if node == 0 {
+                    return;
+                }
+                hovered.set(None);
+                navigate(selected_verse, history, graph.nodes[node].key.clone());
+            }
+        };
+
+        let render_graph = {
+            let graph = graph.clone();
+            let hovered = *hovered.read();
+            RenderCallback::new(move |context| {
+                let area = context.layout_node.visible_area();
+                let size = (area.width(), area.height());
+                canvas_size.set(size);
+                let canvas = context.canvas;
+                let position = |node: usize| {
+                    let (x, y) = to_canvas(graph.nodes[node].position, size);
+                    Point::new(area.min_x() + x, area.min_y() + y)
+                };
+
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true);
+                paint.set_style(PaintStyle::Stroke);
+                paint.set_stroke_width(1.);
+                for (from, to) in &graph.edges {
+                    let touches_hovered = hovered.is_some_and(|h| h == *from || h == *to);
+                    paint.set_color(if touches_hovered {
+                        Color::from_hex("#CCCCCC").unwrap()
+                    } else {
+                        Color::from_hex("#444444").unwrap()
+                    });
+                    canvas.draw_line(position(*from), position(*to), &paint);
+                }
+
+                paint.set_style(PaintStyle::Fill);
+                for (i, node) in graph.nodes.iter().enumerate() {
+                    paint.set_color(book_color(node.book_order));
+                    canvas.draw_circle(position(i), graph.node_radius(i), &paint);
+                    if i == 0 || hovered == Some(i) {
+                        let mut ring = Paint::default();
+                        ring.set_anti_alias(true);
+                        ring.set_style(PaintStyle::Stroke);
+                        ring.set_stroke_width(2.);
+                        ring.set_color(Color::WHITE);
+                        canvas.draw_circle(position(i), graph.node_radius(i) + 2., &ring);
+                    }
+                }
+            })
+        };
+
+        // Every node is named as the Bible names its book, so the legend matches the labels
+        let mut seen = HashSet::new();
+        let mut books = graph
+            .nodes
+            .iter()
+            .filter(|node| seen.insert(&node.key.book_id))
+            .map(|node| (node.book_order, node.key.book_name(&catalog)))
+            .collect::<Vec<_>>();
+        books.sort();
+
+        let status = match *hovered.read() {
+            Some(node) => {
+                let node = &graph.nodes[node];
+                format!(
+                    "{} · {} connections",
+                    node.key.localized(&catalog),
+                    node.degree
+                )
+            }
+            None => format!("{} verses · {} links", graph.nodes.len(), graph.edges.len()),
+        };
+
+        let hop_button = |count: usize| {
+            Button::new()
+                .compact()
+                .background(if hops() == count {
+                    Color::from_hex("#3A3A3A").unwrap()
+                } else {
+                    Color::from_hex("#2C2C2C").unwrap()
+                })
+                .hover_background(Color::from_hex("#353535").unwrap())
+                .on_press(move |_| {
+                    hovered.set(None);
+                    hops.set(count);
+                })
+                .child(
+                    label()
+                        .color(Color::WHITE)
+                        .font_size(12.0)
+                        .text(format!("{count} hops")),
+                )
+        };
+
+        rect()
+            .expanded()
+            .content(Content::Flex)
+            .vertical()
+            .spacing(10.)
+            .child(
+                rect()
+                    .horizontal()
+                    .width(Size::Fill)
+                    .main_align(Alignment::SpaceBetween)
+                    .cross_align(Alignment::Center)
+                    .child(
+                        label()
+                            .color(Color::from_hex("#888888").unwrap())
+                            .font_size(12.0)
+                            .font_weight(FontWeight::BOLD)
+                            .text(status),
+                    )
+                    .child(
+                        rect()
+                            .horizontal()
+                            .spacing(5.)
+                            .child(hop_button(2))
+                            .child(hop_button(3)),
+                    ),
+            )
+            .child(
+                canvas(render_graph)
+                    .expanded()
+                    .on_pointer_move(on_pointer_move)
+                    .on_pointer_leave(move |_| hovered.set(None))
+                    .on_pointer_down(on_press),
+            )
+            .child(
+                rect()
+                    .width(Size::Fill)
+                    .horizontal()
+                    .content(Content::Wrap)
+                    .spacing(8.)
+                    .children(books.into_iter().map(|(order, name)| {
+                        rect()
+                            .key(&name)
+                            .horizontal()
+                            .cross_align(Alignment::Center)
+                            .spacing(4.)
+                            .child(
+                                rect()
+                                    .width(Size::px(8.))
+                                    .height(Size::px(8.))
+                                    .rounded_full()
+                                    .background(book_color(order)),
+                            )
+                            .child(
+                                label()
+                                    .color(Color::from_hex("#AAAAAA").unwrap())
+                                    .font_size(11.0)
+                                    .text(name),
+                            )
+                            .into_element()
+                    })),
+            )
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
@@ -0,0 +1,389 @@
+#[cfg(not(target_os = "android"))]
+use std::sync::Mutex;
+
+use freya::prelude::*;
+use freya::radio::*;
+
+use crate::annotations::HighlightColor;
+use crate::citation::{Citation, CitationFormat, CitationLayout, ReferencePosition};
+use crate::reference::PassageRef;
+use crate::{AppChannel, AppState};
+
+/// Actions over the verses selected in the chapter reader
+#[derive(Clone, PartialEq)]
+pub struct SelectionBar {
+    passage: PassageRef,
+    citation: Citation,
+    on_close: Option<EventHandler<()>>,
+    key: DiffKey,
+}
+
+impl SelectionBar {
+    pub fn new(passage: PassageRef, citation: Citation) -> Self {
+        Self {
+            passage,
+            citation,
+            on_close: None,
+            key: DiffKey::None,
+        }
+    }
+
+    pub fn on_close(mut self, handler: impl Into<EventHandler<()>>) -> Self {
+        self.on_close = Some(handler.into());
+        self
+    }
+}
+
+impl KeyExt for SelectionBar {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+fn copy_to_clipboard(text: String) -> bool {
+    Clipboard::set(text)
+        .inspect_err(|e| tracing::error!("Failed to copy to the clipboard: {e:?}"))
+        .is_ok()
+}
+
+/// Copies HTML with a plain text fallback for apps that do not paste HTML. The clipboard of
+/// Freya only holds text, so it goes through arboard, kept alive so X11 can still serve the
+/// content after the copy.
+#[cfg(not(target_os = "android"))]
+fn copy_html_to_clipboard(html: String, plain: String) -> bool {
+    static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+    let mut clipboard = CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner());
+    if clipboard.is_none() {
+        *clipboard = arboard::Clipboard::new()
+            .inspect_err(|e| tracing::error!("Failed to open the clipboard: {e}"))
+            .ok();
+    }
+    clipboard.as_mut().is_some_and(|clipboard| {
+        clipboard
+            .set_html(html, Some(plain))
+            .inspect_err(|e| tracing::error!("Failed to copy to the clipboard: {e}"))
+            .is_ok()
+    })
+}
+
+#[cfg(target_os = "android")]
+fn copy_html_to_clipboard(html: String, plain: String) -> bool {
+    crate::android::copy_html(&html, &plain)
+        .inspect_err(|e| tracing::error!("Failed to copy to the clipboard: {e}"))
+        .is_ok()
+}
+
+/// Button that flips an option of the citation format
+fn format_toggle(
+    text: &'static str,
+    active: bool,
+    mut settings: Radio<AppState, AppChannel>,
+    update: fn(&mut CitationFormat),
+) -> Element {
+    Button::new()
+        .compact()
+        .background(if active {
+            Color::from_hex("#3A3A3A").unwrap()
+        } else {
+            Color::from_hex("#2C2C2C").unwrap()
+        })
+        .hover_background(Color::from_hex("#353535").unwrap())
+        .on_press(move |_| {
+            let mut state = settings.write();
+            update(&mut state.settings.citation);
+            state.settings.save();
+        })
+        .child(
+            label()
+                .color(if active {
+                    Color::WHITE
+                } else {
+                    Color::from_hex("#888888").unwrap()
+                })
+                .font_size(12.0)
+                .text(text),
+        )
+        .into_element()
+}
+
+fn action(text: &'static str) -> Button {
+    Button::new()
+        .compact()
+        .background(Color::from_hex("#2C2C2C").unwrap())
+        .hover_background(Color::from_hex("#353535").unwrap())
+        .child(label().color(Color::WHITE).font_size(12.0).text(text))
+}
+
+impl Component for SelectionBar {
+    fn render(&self) -> impl IntoElement {
+        let mut annotations = use_radio::<AppState, AppChannel>(AppChannel::Annotations);
+        let mut status = use_state(|| None::<&'static str>);
+        let mut editing_note = use_state(|| false);
+        let mut note = use_state(String::new);
+        let mut editing_format = use_state(|| false);
+        let settings = use_radio::<AppState, AppChannel>(AppChannel::Settings);
+        let mut memory = use_radio::<AppState, AppChannel>(AppChannel::Memory);
+        let catalog = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+        let format = settings.read().settings.citation;
+
+        let catalog = catalog.read().catalog.clone();
+        let passage = self.passage.clone();
+        let is_bookmarked = annotations
+            .read()
+            .annotations
+            .bookmarks
+            .iter()
+            .any(|b| b.passage == passage);
+        let notes = annotations
+            .read()
+            .annotations
+            .notes
+            .iter()
+            .filter(|n| {
+                passage
+                    .in_bible(&catalog, &n.passage.bible_id)
+                    .is_some_and(|p| n.passage.overlaps(&p))
+            })
+            .map(|n| n.text.clone())
+            .collect::<Vec<_>>();
+        let plain = self.citation.plain(&format);
+        let markdown = self.citation.markdown(&format);
+        let html = self.citation.html(&format);
+        let html_fallback = plain.clone();
+        // Only Android has a share sheet, elsewhere Share copies the verses with a link to them,
+        // ready to paste in any app
+        #[cfg(target_os = "android")]
+        let share_action = action("Share").on_press({
+            let text = plain.clone();
+            move |_| {
+                if let Err(e) = crate::android::share_text(&text) {
+                    tracing::error!("Failed to share: {e}");
+                    status.set(Some("Could not share"));
+                }
+            }
+        });
+        #[cfg(not(target_os = "android"))]
+        let share_action = action("Share").on_press({
+            let text = format!("{plain}\n{}", passage.first().to_uri());
+            move |_| {
+                if copy_to_clipboard(text.clone()) {
+                    status.set(Some("Copied with a link to share"));
+                }
+            }
+        });
+
+        let highlight_button = |color: HighlightColor| {
+            let passage = passage.clone();
+            Button::new()
+                .compact()
+                .background(Color::from_hex("#2C2C2C").unwrap())
+                .hover_background(Color::from_hex("#353535").unwrap())
+                .on_press(move |_| {
+                    let mut state = annotations.write();
+                    state.annotations.highlight(passage.clone(), color);
+                    state.annotations.save();
+                })
+                .child(
+                    rect()
+                        .width(Size::px(12.))
+                        .height(Size::px(12.))
+                        .rounded_full()
+                        .background(Color::from_hex(color.hex()).unwrap()),
+                )
+                .into_element()
+        };
+
+        rect()
+            .width(Size::Fill)
+            .background(Color::from_hex("#252525").unwrap())
+            .rounded()
+            .padding(8.)
+            .vertical()
+            .spacing(8.)
+            .child(
+                rect()
+                    .horizontal()
+                    .width(Size::Fill)
+                    .main_align(Alignment::SpaceBetween)
+                    .cross_align(Alignment::Center)
+                    .child(
+                        label()
+                            .color(Color::from_hex("#888888").unwrap())
+                            .font_size(12.0)
+                            .font_weight(FontWeight::BOLD)
+                            .text(match *status.read() {
+                                Some(status) => format!("{} · {status}", self.citation.reference),
+                                None => self.citation.reference.clone(),
+                            }),
+                    )
+                    .maybe_child(self.on_close.clone().map(|on_close| {
+                        action("✕")
+                            .on_press(move |_| on_close.call(()))
+                            .into_element()
+                    })),
+            )
+            .child(
+                rect()
+                    .horizontal()
+                    .content(Content::Wrap)
+                    .width(Size::Fill)
+                    .spacing(5.)
+                    .cross_align(Alignment::Center)
+                    .child(action("Copy").on_press(move |_| {
+                        if copy_to_clipboard(plain.clone()) {
+                            status.set(Some("Copied"));
+                        }
+                    }))
+                    .child(action("Markdown").on_press(move |_| {
+                        if copy_to_clipboard(markdown.clone()) {
+                            status.set(Some("Copied as Markdown"));
+                        }
+                    }))
+                    .child(action("HTML").on_press(move |_| {
+                        if copy_html_to_clipboard(html.clone(), html_fallback.clone()) {
+                            status.set(Some("Copied as HTML"));
+                        }
+                    }))
+                    .child(action("Link").on_press({
+                        let link = passage.first().to_uri();
+                        move |_| {
+                            if copy_to_clipboard(link.clone()) {
+                                status.set(Some("Link copied"));
+                            }
+                        }
+                    }))
+                    .child(
+                        action("Format").on_press(move |_| editing_format.set(!editing_format())),
+                    )
+                    .children(HighlightColor::ALL.into_iter().map(highlight_button))
+                    .child(action("Clear").on_press({
+                        let passage = passage.clone();
+                        move |_| {
+                            let mut state = annotations.write();
+                            state.annotations.clear_highlight(&passage);
+                            state.annotations.save();
+                        }
+                    }))
+                    .child(action("Note").on_press(move |_| editing_note.set(!editing_note())))
+                    .child(
+                        action(if is_bookmarked {
+                            "Remove bookmark"
+                        } else {
+                            "Bookmark"
+                        })
+                        .on_press({
+                            let passage = passage.clone();
+                            move |_| {
+                                let mut state = annotations.write();
+                                let bookmarked = state.annotations.toggle_bookmark(passage.clone());
+                                state.annotations.save();
+                                status.set(Some(if bookmarked {
+                                    "Bookmarked"
+                                } else {
+                                    "Bookmark removed"
+                                }));
+                            }
+                        }),
+                    )
+                    .child(action("Memorize").on_press({
+                        let passage = passage.clone();
+                        let reference = self.citation.reference.clone();
+                        let text = self
+                            .citation
+                            .verses
+                            .iter()
+                            .map(|v| v.text.trim())
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        move |_| {
+                            let mut state = memory.write();
+                            let added =
+                                state
+                                    .memory
+                                    .add(passage.clone(), reference.clone(), text.clone());
+                            state.memory.save();
+                            status.set(Some(if added {
+                                "Added to memory verses"
+                            } else {
+                                "Already in memory verses"
+                            }));
+                        }
+                    }))
+                    .child(share_action),
+            )
+            .maybe_child(editing_format().then(|| {
+                rect()
+                    .horizontal()
+                    .content(Content::Wrap)
+                    .width(Size::Fill)
+                    .spacing(5.)
+                    .child(format_toggle(
+                        "Reference first",
+                        format.reference == ReferencePosition::Before,
+                        settings,
+                        |f| {
+                            f.reference = match f.reference {
+                                ReferencePosition::Before => ReferencePosition::After,
+                                ReferencePosition::After => ReferencePosition::Before,
+                            }
+                        },
+                    ))
+                    .child(format_toggle(
+                        "Translation",
+                        format.translation,
+                        settings,
+                        |f| f.translation = !f.translation,
+                    ))
+                    .child(format_toggle(
+                        "Verse numbers",
+                        format.verse_numbers,
+                        settings,
+                        |f| f.verse_numbers = !f.verse_numbers,
+                    ))
+                    .child(format_toggle(
+                        "Line per verse",
+                        format.layout == CitationLayout::Lines,
+                        settings,
+                        |f| {
+                            f.layout = match f.layout {
+                                CitationLayout::Lines => CitationLayout::Paragraph,
+                                CitationLayout::Paragraph => CitationLayout::Lines,
+                            }
+                        },
+                    ))
+                    .into_element()
+            }))
+            .maybe_child(editing_note().then(|| {
+                let passage = passage.clone();
+                Input::new(note)
+                    .width(Size::Fill)
+                    .placeholder("Write a note and press Enter...")
+                    .on_submit(move |text: String| {
+                        if text.trim().is_empty() {
+                            return;
+                        }
+                        let mut state = annotations.write();
+                        state.annotations.add_note(passage.clone(), text.trim());
+                        state.annotations.save();
+                        drop(state);
+                        note.set(String::new());
+                        editing_note.set(false);
+                        status.set(Some("Note saved"));
+                    })
+                    .into_element()
+            }))
+            .children(notes.into_iter().enumerate().map(|(i, text)| {
+                label()
+                    .key(i)
+                    .color(Color::from_hex("#CCCCCC").unwrap())
+                    .font_size(13.0)
+                    .text(format!("✎ {text}"))
+                    .into_element()
+            }))
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
@@ -0,0 +1,212 @@
+use freya::prelude::*;
+use freya::radio::*;
+
+use crate::components::empty_state;
+use crate::history::{NavigationHistory, navigate};
+use crate::modules::TopicHit;
+use crate::reference::VerseRef;
+use crate::{AppChannel, AppState};
+
+/// Topics of the installed topical indexes citing the selected verse, or matching a search.
+/// Expanding a topic lists its passages to navigate to them.
+#[derive(Clone, PartialEq)]
+pub struct TopicsPanel {
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+    on_navigate: Option<EventHandler<()>>,
+    key: DiffKey,
+}
+
+impl TopicsPanel {
+    pub fn new(
+        selected_verse: impl Into<State<Option<VerseRef>>>,
+        history: impl Into<State<NavigationHistory<VerseRef>>>,
+    ) -> Self {
+        Self {
+            selected_verse: selected_verse.into(),
+            history: history.into(),
+            on_navigate: None,
+            key: DiffKey::None,
+        }
+    }
+
+    /// Called after opening one of the passages of a topic
+    pub fn on_navigate(mut self, handler: impl Into<EventHandler<()>>) -> Self {
+        self.on_navigate = Some(handler.into());
+        self
+    }
+}
+
+impl KeyExt for TopicsPanel {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+impl Component for TopicsPanel {
+    fn render(&self) -> impl IntoElement {
+        let modules = use_radio::<AppState, AppChannel>(AppChannel::Modules);
+        let catalog = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+        let mut search = use_state(String::new);
+        let mut expanded = use_state(|| None::<(String, String)>);
+        let selected_verse = self.selected_verse;
+        let history = self.history;
+        let on_navigate = self.on_navigate.clone();
+
+        if modules.read().modules.topics.is_empty() {
+            return empty_state("No topical index installed. Use Tools > Install Modules.");
+        }
+
+        let current = selected_verse.read().clone();
+        let Some(bible_id) = current
+            .as_ref()
+            .map(|verse| verse.bible_id.clone())
+            .or_else(|| catalog.read().catalog.bibles.first().map(|b| b.id.clone()))
+        else {
+            return empty_state("Install a Bible to browse topics");
+        };
+        let catalog = catalog.read().catalog.clone();
+        let query = search.read().trim().to_string();
+        let hits = match &current {
+            _ if !query.is_empty() => modules.read().modules.search_topics(&query, 100),
+            // Topical indexes are numbered as the KJV
+            Some(verse) => {
+                let (chapter, number) = verse.to_standard(&catalog);
+                modules
+                    .read()
+                    .modules
+                    .topics_citing(&verse.book_id, chapter, number)
+            }
+            None => Vec::new(),
+        };
+
+        let open = move |target: VerseRef| {
+            navigate(selected_verse, history, target);
+            if let Some(on_navigate) = &on_navigate {
+                on_navigate.call(());
+            }
+        };
+
+        rect()
+            .expanded()
+            .content(Content::Flex)
+            .vertical()
+            .spacing(10.)
+            .child(
+                Input::new(search)
+                    .width(Size::Fill)
+                    .placeholder("Search topics...")
+                    .on_submit(move |v| search.set(v)),
+            )
+            .child(if hits.is_empty() {
+                empty_state(if !query.is_empty() {
+                    "No topics found"
+                } else if selected_verse.read().is_some() {
+                    "No topic cites this verse"
+                } else {
+                    "Search a topic or select a verse"
+                })
+            } else {
+                ScrollView::new()
+                    .expanded()
+                    .direction(Direction::Vertical)
+                    .spacing(8.)
+                    .children(hits.into_iter().map(|hit| {
+                        let TopicHit {
+                            module,
+                            module_name,
+                            topic,
+                        } = hit;
+                        let id = (module, topic.name.clone());
+                        let is_expanded = expanded.read().as_ref() == Some(&id);
+                        rect()
+                            .key(format!("{}:{}", id.0, id.1))
+                            .background(Color::from_hex("#2C2C2C").unwrap())
+                            .rounded()
+                            .padding(10.0)
+                            .vertical()
+                            .spacing(6.0)
+                            .width(Size::Fill)
+                            .child(
+                                Button::new()
+                                    .compact()
+                                    .on_press({
+                                        let id = id.clone();
+                                        move |_| {
+                                            expanded.set((!is_expanded).then(|| id.clone()));
+                                        }
+                                    })
+                                    .child(
+                                        rect()
+                                            .horizontal()
+                                            .width(Size::Fill)
+                                            .main_align(Alignment::SpaceBetween)
+                                            .child(
+                                                label()
+                                                    .color(Color::WHITE)
+                                                    .font_weight(FontWeight::BOLD)
+                                                    .text(format!(
+                                                        "{} {}",
+                                                        if is_expanded { "▾" } else { "▸" },
+                                                        topic.name
+                                                    )),
+                                            )
+                                            .child(
+                                                label()
+                                                    .color(Color::from_hex("#888888").unwrap())
+                                                    .font_size(12.0)
+                                                    .text(format!(
+                                                        "{} · {} passages",
+                                                        module_name,
+                                                        topic.passages.len()
+                                                    )),
+                                            ),
+                                    ),
+                            )
+                            .maybe_child(is_expanded.then(|| {
+                                rect()
+                                    .horizontal()
+                                    .content(Content::Wrap)
+                                    .spacing(6.)
+                                    .children(topic.passages.iter().map(|passage| {
+                                        let book_name = catalog
+                                            .book(&bible_id, &passage.book_id)
+                                            .map(|b| b.name.clone());
+                                        let text = match book_name {
+                                            Some(name) => format!("{name} {}", passage.location()),
+                                            None => passage.label(),
+                                        };
+                                        let target = passage.first().in_bible(&catalog, &bible_id);
+                                        let open = open.clone();
+                                        Button::new()
+                                            .compact()
+                                            .on_press(move |_| open(target.clone()))
+                                            .child(
+                                                label()
+                                                    .color(Color::from_hex("#6FA8DC").unwrap())
+                                                    .font_size(12.0)
+                                                    .text(text),
+                                            )
+                                            .into_element()
+                                    }))
+                                    .into_element()
+                            }))
+                            .maybe_child((is_expanded && !topic.see_also.is_empty()).then(|| {
+                                let see_also = topic.see_also.join(", ");
+                                label()
+                                    .color(Color::from_hex("#888888").unwrap())
+                                    .font_size(12.0)
+                                    .text(format!("See also: {see_also}"))
+                                    .into_element()
+                            }))
+                            .into_element()
+                    }))
+                    .into_element()
+            })
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
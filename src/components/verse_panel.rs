@@ -1,18 +1,44 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ops::{Range, RangeInclusive};
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use freya::engine::prelude::{RectHeightStyle, RectWidthStyle};
 use freya::prelude::*;
+use freya::radio::*;
 use setup_core::{DbSink, TantivySink};
 
+use crate::catalog::{CanonicalBook, Catalog};
+use crate::citation::{Citation, CitedVerse};
+use crate::components::{
+    CommentaryPanel, CrossReference, CrossReferences, Glossary, Interlinear, InterlinearLines,
+    PresentationControls, ReferenceGraphView, SelectionBar, TopicsPanel, WordSelection,
+    WordStudyPanel, count_references,
+};
+use crate::history::{NavigationHistory, close, go_back, go_forward, navigate};
+use crate::modules::normalize_headword;
+use crate::presentation::{Presentation, Slide};
+use crate::reference::{PassageRef, VerseRef};
+use crate::{AppChannel, AppState};
+
 pub struct VersePanel {
     database: Arc<TantivySink>,
-    selected_verse: State<Option<(String, String, usize, usize)>>,
-    on_word_click: Box<dyn Fn(String) + 'static>,
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+    scroll_controller: Option<ScrollController>,
+    initial_scroll: Option<i32>,
     layout: LayoutData,
+    key: DiffKey,
 }
 
 impl PartialEq for VersePanel {
     fn eq(&self, other: &Self) -> bool {
-        self.selected_verse == other.selected_verse && self.layout == other.layout
+        self.selected_verse == other.selected_verse
+            && self.history == other.history
+            && self.layout == other.layout
+            && self.key == other.key
     }
 }
 
@@ -22,24 +48,221 @@ impl VersePanel {
             database,
             layout: LayoutData::default(),
             selected_verse: State::create(None),
-            on_word_click: Box::new(|_| {}),
+            history: State::create(NavigationHistory::default()),
+            scroll_controller: None,
+            initial_scroll: None,
+            key: DiffKey::None,
         }
     }
 
-    pub fn selected_verse(
-        mut self,
-        selected_verse: impl Into<State<Option<(String, String, usize, usize)>>>,
-    ) -> Self {
+    pub fn selected_verse(mut self, selected_verse: impl Into<State<Option<VerseRef>>>) -> Self {
         self.selected_verse = selected_verse.into();
         self
     }
 
-    pub fn on_word_click(mut self, on_click: impl Fn(String) + 'static) -> Self {
-        self.on_word_click = Box::new(on_click);
+    /// Verses visited by following references, shared with whoever else navigates
+    pub fn history(mut self, history: impl Into<State<NavigationHistory<VerseRef>>>) -> Self {
+        self.history = history.into();
+        self
+    }
+
+    /// Scroll of the chapter text, for the owner to read it
+    pub fn scroll_controller(mut self, scroll_controller: ScrollController) -> Self {
+        self.scroll_controller = Some(scroll_controller);
+        self
+    }
+
+    /// Offset the chapter text starts scrolled to instead of the selected verse, used when
+    /// restoring a tab
+    pub fn initial_scroll(mut self, offset: impl Into<Option<i32>>) -> Self {
+        self.initial_scroll = offset.into();
         self
     }
 }
 
+/// How long a touch has to be held to start selecting verses
+const LONG_PRESS: Duration = Duration::from_millis(500);
+
+/// Text of a chapter laid out as a single paragraph. Offsets are counted in UTF-16 code units,
+/// which is what Skia uses for glyph positions and highlight ranges, and always fall on
+/// character boundaries
+#[derive(Default)]
+struct ChapterText {
+    text: String,
+    len: usize,
+    verses: Vec<VerseSpan>,
+}
+
+struct VerseSpan {
+    number: u32,
+    /// Verse number and the space after it
+    label: Range<usize>,
+    text: Range<usize>,
+}
+
+impl ChapterText {
+    fn push_str(&mut self, text: &str) -> Range<usize> {
+        let start = self.len;
+        self.text.push_str(text);
+        self.len += text.encode_utf16().count();
+        start..self.len
+    }
+
+    fn push_verse(&mut self, number: u32, label: &str, text: &str) {
+        if !self.verses.is_empty() {
+            self.push_str(" ");
+        }
+        let label = self.push_str(label);
+        let text = self.push_str(text);
+        self.verses.push(VerseSpan {
+            number,
+            label,
+            text,
+        });
+    }
+
+    fn verse_at(&self, offset: usize) -> Option<&VerseSpan> {
+        self.verses.iter().find(|v| v.text.contains(&offset))
+    }
+
+    /// Verse whose number or text is at `offset`
+    fn verse_containing(&self, offset: usize) -> Option<&VerseSpan> {
+        self.verses
+            .iter()
+            .find(|v| (v.label.start..v.text.end).contains(&offset))
+    }
+
+    fn cited_verses(&self, verses: &RangeInclusive<u32>) -> Vec<CitedVerse> {
+        self.verses
+            .iter()
+            .filter(|v| verses.contains(&v.number))
+            .map(|v| CitedVerse {
+                number: v.number,
+                text: self.slice(v.text.clone()),
+            })
+            .collect()
+    }
+
+    fn slice(&self, range: Range<usize>) -> String {
+        let utf16 = self
+            .text
+            .encode_utf16()
+            .skip(range.start)
+            .take(range.len())
+            .collect::<Vec<_>>();
+        String::from_utf16_lossy(&utf16)
+    }
+
+    /// Range from the number of the first verse in `verses` to the end of the last one
+    fn highlight(&self, verses: &RangeInclusive<u32>) -> Option<Range<usize>> {
+        let mut selected = self.verses.iter().filter(|v| verses.contains(&v.number));
+        let first = selected.next()?;
+        let last = selected.last().unwrap_or(first);
+        Some(first.label.start..last.text.end)
+    }
+}
+
+/// Vertical position of the UTF-16 `range` inside the laid out paragraph
+fn range_top(holder: &ParagraphHolder, range: Range<usize>) -> Option<f32> {
+    let holder = holder.0.borrow();
+    let holder = holder.as_ref()?;
+    let rects =
+        holder
+            .paragraph
+            .get_rects_for_range(range, RectHeightStyle::Tight, RectWidthStyle::Tight);
+    Some(rects.first()?.rect.top / holder.scale_factor)
+}
+
+/// UTF-16 offset of the glyph laid out under `location`, relative to the paragraph
+fn offset_at(holder: &ParagraphHolder, location: CursorPoint) -> Option<usize> {
+    let holder = holder.0.borrow();
+    let holder = holder.as_ref()?;
+    let scale = holder.scale_factor;
+    let position = holder.paragraph.get_glyph_position_at_coordinate((
+        (location.x * scale) as i32,
+        (location.y * scale) as i32,
+    ));
+    Some(position.position.max(0) as usize)
+}
+
+/// Returns the word around the UTF-16 `offset` and the offset where it starts
+fn word_at(holder: &ParagraphHolder, text: &str, offset: usize) -> Option<(String, usize)> {
+    let holder = holder.0.borrow();
+    let holder = holder.as_ref()?;
+    // Skia works with UTF-16 offsets
+    let boundary = holder.paragraph.get_word_boundary(offset as u32);
+    let utf16 = text.encode_utf16().collect::<Vec<_>>();
+    let word = String::from_utf16_lossy(utf16.get(boundary.start..boundary.end)?);
+    let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+    (!word.is_empty()).then(|| (word.to_string(), boundary.start))
+}
+
+/// Counts how many times `word` appears in `text` before the UTF-16 offset `end`
+fn occurrences_before(text: &str, end: usize, word: &str) -> usize {
+    let prefix = text.encode_utf16().take(end).collect::<Vec<_>>();
+    let word = normalize_headword(word);
+    String::from_utf16_lossy(&prefix)
+        .split_whitespace()
+        .filter(|w| normalize_headword(w) == word)
+        .count()
+}
+
+/// Shown when the selected book is not part of the Bible, e.g. Sirach in a Protestant Bible,
+/// offering the installed Bibles that contain it
+fn missing_book(
+    catalog: &Catalog,
+    verse: &VerseRef,
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+) -> Element {
+    let name = CanonicalBook::by_id(&verse.book_id)
+        .and_then(|b| b.names.first().copied())
+        .unwrap_or(verse.book_id.as_str());
+    rect()
+        .expanded()
+        .center()
+        .vertical()
+        .spacing(10.)
+        .child(
+            label()
+                .color(Color::from_hex("#888888").unwrap())
+                .font_size(16.0)
+                .text(format!(
+                    "{name} is not part of the {} canon of {}",
+                    catalog
+                        .bible(&verse.bible_id)
+                        .map_or("", |b| b.canon.label())
+                        .to_lowercase(),
+                    verse.bible_id.to_uppercase()
+                )),
+        )
+        .children(
+            catalog
+                .bibles
+                .iter()
+                .filter(|b| b.book(&verse.book_id).is_some())
+                .map(|bible| {
+                    let target = verse.in_bible(catalog, &bible.id);
+                    Button::new()
+                        .compact()
+                        .on_press(move |_| navigate(selected_verse, history, target.clone()))
+                        .child(
+                            label()
+                                .color(Color::WHITE)
+                                .text(format!("Open in {}", bible.id.to_uppercase())),
+                        )
+                        .into_element()
+                }),
+        )
+        .into_element()
+}
+
+impl KeyExt for VersePanel {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
 impl LayoutExt for VersePanel {
     fn get_layout(&mut self) -> &mut LayoutData {
         &mut self.layout
@@ -51,75 +274,412 @@ impl ContainerWithContentExt for VersePanel {}
 impl Component for VersePanel {
     fn render(&self) -> impl IntoElement {
         let mut active_tab = use_state(|| 0);
+        let mut glossary_word = use_state(|| None::<String>);
+        let mut word_selection = use_state(|| None::<WordSelection>);
+        let mut interlinear = use_state(|| false);
+        let interlinear_lines = use_state(InterlinearLines::default);
+        let whole_chapter = use_state(|| false);
+        // Chapter, anchor verse and range of the verses selected by the user
+        let mut verse_selection = use_state(|| None::<(String, u32, RangeInclusive<u32>)>);
+        let mut shift_held = use_state(|| false);
+        let press_started = use_hook(|| Rc::new(Cell::new(None::<Instant>)));
+        let modules = use_radio::<AppState, AppChannel>(AppChannel::Modules);
+        let annotations = use_radio::<AppState, AppChannel>(AppChannel::Annotations);
+        let settings = use_radio::<AppState, AppChannel>(AppChannel::Settings);
+        let catalog = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+        let holder = use_state(ParagraphHolder::default);
+        let own_scroll_controller = use_scroll_controller(ScrollConfig::default);
+        let mut scroll_controller = self.scroll_controller.unwrap_or(own_scroll_controller);
+        let initial_scroll = use_hook(|| Rc::new(Cell::new(self.initial_scroll)));
+        // Selection the chapter was last scrolled to, the chapter the holder was laid out for
+        // and the text of the last rendered chapter
+        let scrolled_to = use_hook(|| Rc::new(RefCell::new(None::<(String, RangeInclusive<u32>)>)));
+        let laid_out = use_hook(|| Rc::new(RefCell::new(String::new())));
+        let rendered = use_hook(|| Rc::new(RefCell::new(None::<(String, Rc<ChapterText>)>)));
+        let mut layout_version = use_state(|| 0u32);
         let mut selected_verse = self.selected_verse;
+        let mut history = self.history;
         let database = self.database.clone();
 
+        // Bring the selected verse into view once the paragraph of its chapter is laid out
+        use_side_effect({
+            let scrolled_to = scrolled_to.clone();
+            let laid_out = laid_out.clone();
+            let rendered = rendered.clone();
+            let initial_scroll = initial_scroll.clone();
+            move || {
+                let _ = layout_version();
+                let Some(verse) = selected_verse.read().clone() else {
+                    return;
+                };
+                let target = (verse.chapter_key(), verse.verse..=verse.verse);
+                if *laid_out.borrow() != target.0 || scrolled_to.borrow().as_ref() == Some(&target)
+                {
+                    return;
+                }
+                if let Some(offset) = initial_scroll.take() {
+                    *scrolled_to.borrow_mut() = Some(target);
+                    scroll_controller.scroll_to_y(offset);
+                    return;
+                }
+                let Some(top) = rendered
+                    .borrow()
+                    .as_ref()
+                    .filter(|(key, _)| *key == target.0)
+                    .and_then(|(_, text)| text.highlight(&target.1))
+                    .and_then(|range| range_top(&holder.peek(), range))
+                else {
+                    return;
+                };
+                *scrolled_to.borrow_mut() = Some(target);
+                // Leave a line of context above the verse
+                scroll_controller.scroll_to_y(-((top - 30.).max(0.) as i32));
+            }
+        });
+
         let search = selected_verse.read();
         let Some(search) = search.as_ref() else {
             return rect().into_element();
         };
-        let (bible_id, book_id, chapter_idx, verse_idx) = search;
+        let catalog = catalog.read().catalog.clone();
+        if catalog
+            .bible(&search.bible_id)
+            .is_some_and(|bible| bible.book(&search.book_id).is_none())
+        {
+            return missing_book(&catalog, search, selected_verse, history);
+        }
+        let VerseRef {
+            bible_id,
+            book_id,
+            verse: verse_number,
+            ..
+        } = search;
+        let verse_number = *verse_number;
         let Ok(Some(chapter)) = database
-            .get_crossreferences(&bible_id, &book_id, *chapter_idx as _, *verse_idx as _)
+            .get_crossreferences(bible_id, book_id, search.chapter as _, verse_number as _)
             .inspect_err(|e| println!("Failed to found crossref: {e}"))
         else {
             return rect().into_element();
         };
 
+        let highlighted_verses = verse_number..=verse_number;
+        let chapter_key = search.chapter_key();
+        let selected_range = verse_selection
+            .read()
+            .as_ref()
+            .filter(|(key, _, _)| *key == chapter_key)
+            .map(|(_, _, range)| range.clone());
+
         // Construir los spans del párrafo con todos los versículos
         let mut paragraph_spans = Vec::new();
-        let mut char_count = 0;
-        let mut highlight_start = 0;
-        let mut highlight_end = 0;
+        let mut chapter_text = ChapterText::default();
+        let mut slides = Vec::new();
+        let chapter_number = chapter.chapter as u32;
+        let user_annotations = annotations.read();
+        let annotations = &user_annotations.annotations;
 
         for (idx, v) in chapter.verses.iter().enumerate() {
-            let is_clicked = idx == v.verse_number as usize;
-            let base_color = if is_clicked {
-                Color::from_hex("#FFFFFF").unwrap()
-            } else {
-                Color::from_hex("#CCCCCC").unwrap()
+            let number = v.verse_number as u32;
+            let verse = VerseRef::new(bible_id.clone(), book_id.clone(), chapter_number, number);
+            let is_highlighted = selected_range
+                .as_ref()
+                .unwrap_or(&highlighted_verses)
+                .contains(&number);
+            let base_color = match annotations.highlight_at(&verse) {
+                Some(color) => Color::from_hex(color.hex()).unwrap(),
+                None if is_highlighted => Color::from_hex("#FFFFFF").unwrap(),
+                None => Color::from_hex("#CCCCCC").unwrap(),
             };
 
-            let verse_number_text = format!(
-                "{space}{} ",
-                v.verse_number,
-                space = if idx > 0 { " " } else { "" }
-            );
+            let mut verse_number_text = number.to_string();
+            if !annotations.notes_at(&catalog, &verse).is_empty() {
+                verse_number_text.push_str(" ✎");
+            }
+            if annotations.is_bookmarked(&verse) {
+                verse_number_text.push_str(" ★");
+            }
+            verse_number_text.push(' ');
             paragraph_spans.push(
-                Span::new(verse_number_text.clone())
-                    .color(Color::LIGHT_GRAY.with_a(75))
-                    .font_weight(FontWeight::BOLD),
+                Span::new(format!(
+                    "{space}{verse_number_text}",
+                    space = if idx > 0 { " " } else { "" }
+                ))
+                .color(Color::LIGHT_GRAY.with_a(75))
+                .font_weight(FontWeight::BOLD),
             );
+            paragraph_spans.push(Span::new(v.text.clone()).color(base_color));
+            chapter_text.push_verse(v.verse_number as u32, &verse_number_text, &v.text);
+            slides.push(Slide {
+                reference: format!(
+                    "{} {chapter_number}:{number} ({})",
+                    chapter.book_name,
+                    bible_id.to_uppercase()
+                ),
+                verse: number,
+                text: v.text.trim().to_string(),
+            });
+        }
+
+        let chapter_text = Rc::new(chapter_text);
+        *rendered.borrow_mut() = Some((chapter_key.clone(), chapter_text.clone()));
+        let highlights = chapter_text
+            .highlight(selected_range.as_ref().unwrap_or(&highlighted_verses))
+            .map(|range| vec![(range.start, range.end)])
+            .unwrap_or_default();
 
-            if v.highlighted {
-                highlight_start = char_count;
+        let on_paragraph_sized = {
+            let laid_out = laid_out.clone();
+            let chapter_key = chapter_key.clone();
+            move |_| {
+                *laid_out.borrow_mut() = chapter_key.clone();
+                *layout_version.write() += 1;
             }
-            char_count += verse_number_text.len();
+        };
 
-            paragraph_spans.push(Span::new(v.text.clone()).color(base_color));
+        // Cross references are numbered as the KJV, read the ones of the KJV verse matching
+        // every verse and number their targets as this Bible
+        let mut standard_chapters = HashMap::new();
+        let mut cross_refs = Vec::new();
+        for v in &chapter.verses {
+            let (standard_chapter, standard_verse) = search
+                .with_verse(v.verse_number as u32)
+                .to_standard(&catalog);
+            let standard = if standard_chapter == chapter_number {
+                Some(&chapter)
+            } else {
+                standard_chapters
+                    .entry(standard_chapter)
+                    .or_insert_with(|| {
+                        database
+                            .get_crossreferences(
+                                bible_id,
+                                book_id,
+                                standard_chapter as _,
+                                standard_verse as _,
+                            )
+                            .ok()
+                            .flatten()
+                    })
+                    .as_ref()
+            };
+            let references = standard
+                .into_iter()
+                .flat_map(|c| c.verses.iter())
+                .filter(|s| s.verse_number as u32 == standard_verse)
+                .flat_map(|s| s.cross_references.iter());
+            // References to books the catalog does not know cannot be opened, they are left out
+            for (cross_ref, book) in references.filter_map(|cross_ref| {
+                catalog
+                    .book_by_name(bible_id, &cross_ref.book_name)
+                    .map(|book| (cross_ref, book))
+            }) {
+                let (target_chapter, target_verse) =
+                    (cross_ref.chapter as u32, cross_ref.verse as u32);
+                let votes = modules.read().modules.reference_votes(
+                    (book_id, standard_chapter, standard_verse),
+                    (&book.id, target_chapter, target_verse),
+                );
+                cross_refs.push(CrossReference {
+                    from: search.with_verse(v.verse_number as u32),
+                    target: VerseRef::from_standard(
+                        &catalog,
+                        bible_id.clone(),
+                        book.id,
+                        target_chapter,
+                        target_verse,
+                    ),
+                    text: cross_ref.text.clone(),
+                    votes,
+                });
+            }
+        }
+        let selected_verses = selected_range
+            .clone()
+            .unwrap_or(verse_number..=verse_number);
+        let cross_refs_len = count_references(
+            &cross_refs,
+            (!*whole_chapter.read()).then_some(&selected_verses),
+        );
 
-            char_count += v.text.len();
+        let (can_go_back, can_go_forward) = {
+            let history = history.read();
+            (history.can_go_back(), history.can_go_forward())
+        };
+        let history_button = |text: &'static str, enabled: bool| {
+            Button::new()
+                .background(Color::from_hex("#2C2C2C").unwrap())
+                .hover_background(Color::from_hex("#353535").unwrap())
+                .padding(5.0)
+                .child(
+                    label()
+                        .color(if enabled {
+                            Color::WHITE
+                        } else {
+                            Color::from_hex("#555555").unwrap()
+                        })
+                        .text(text),
+                )
+        };
+        // Tabs of the study panel, the count shown as a badge before the name
+        let tab_button = |text: &'static str, tab: usize, count: Option<usize>| {
+            Button::new()
+                .height(Size::Fill)
+                .on_press(move |_| active_tab.set(tab))
+                .background(if *active_tab.read() == tab {
+                    Color::from_hex("#3A3A3A").unwrap()
+                } else {
+                    Color::from_hex("#2C2C2C").unwrap()
+                })
+                .hover_background(Color::from_hex("#353535").unwrap())
+                .padding(8.0)
+                .child(
+                    rect()
+                        .center()
+                        .horizontal()
+                        .spacing(5.)
+                        .maybe_child(count.map(|count| {
+                            rect()
+                                .center()
+                                .text_align(TextAlign::Center)
+                                .rounded_full()
+                                .padding(8.)
+                                .background(Color::DARK_GRAY)
+                                .child(label().color(Color::WHITE).text(count.to_string()))
+                                .into_element()
+                        }))
+                        .child(
+                            label()
+                                .color(Color::WHITE)
+                                .text_align(TextAlign::Center)
+                                .text(text),
+                        ),
+                )
+        };
 
-            if v.highlighted {
-                highlight_end = char_count;
+        let has_tagged_text = modules.read().modules.tagged_bible(bible_id).is_some();
+        let (bible_id, book_id) = (bible_id.clone(), book_id.clone());
+        let selection_bar = selected_range.clone().map(|range| {
+            let reference = if range.start() == range.end() {
+                format!("{} {chapter_number}:{}", chapter.book_name, range.start())
+            } else {
+                format!(
+                    "{} {chapter_number}:{}-{}",
+                    chapter.book_name,
+                    range.start(),
+                    range.end()
+                )
+            };
+            SelectionBar::new(
+                PassageRef::new(
+                    bible_id.clone(),
+                    book_id.clone(),
+                    chapter_number,
+                    range.clone(),
+                ),
+                Citation {
+                    reference,
+                    translation: bible_id.to_uppercase(),
+                    verses: chapter_text.cited_verses(&range),
+                },
+            )
+            .on_close(move |_| verse_selection.set(None))
+        });
+        let show_interlinear = has_tagged_text && *interlinear.read();
+        let interlinear_view = show_interlinear.then(|| {
+            Interlinear::new(
+                bible_id.clone(),
+                book_id.clone(),
+                chapter_number,
+                interlinear_lines,
+            )
+            .selected_verse(verse_number)
+            .on_word_click(move |selection: WordSelection| {
+                glossary_word.set(Some(selection.word.clone()));
+                word_selection.set(Some(selection));
+                active_tab.set(2);
+            })
+        });
+        let on_paragraph_down = {
+            let press_started = press_started.clone();
+            move |_| press_started.set(Some(Instant::now()))
+        };
+        let presented_chapter = chapter_key.clone();
+        let presented_verse = selected_range
+            .as_ref()
+            .map_or(verse_number, |range| *range.start());
+        // Verse numbers, shift-click and long-press select verses, any other click studies a word
+        let on_paragraph_press = move |e: Event<PointerEventData>| {
+            let long_press = press_started
+                .take()
+                .is_some_and(|started| started.elapsed() >= LONG_PRESS);
+            let Some(offset) = offset_at(&holder.read(), e.element_location()) else {
+                return;
+            };
+            let Some(verse) = chapter_text.verse_containing(offset) else {
+                return;
+            };
+            let on_number = verse.label.contains(&offset);
+            if long_press || on_number || shift_held() {
+                let current = verse_selection
+                    .read()
+                    .clone()
+                    .filter(|(key, _, _)| *key == chapter_key);
+                let extend = shift_held() || (long_press && current.is_some());
+                let selection = match current {
+                    Some((_, anchor, _)) if extend => {
+                        Some((anchor, anchor.min(verse.number)..=anchor.max(verse.number)))
+                    }
+                    Some((_, _, range)) if on_number && range == (verse.number..=verse.number) => {
+                        None
+                    }
+                    _ => Some((verse.number, verse.number..=verse.number)),
+                };
+                verse_selection
+                    .set(selection.map(|(anchor, range)| (chapter_key.clone(), anchor, range)));
+                return;
             }
-        }
 
-        let highlights = vec![(highlight_start + 1, highlight_end)];
-        let cross_refs = chapter
-            .verses
-            .iter()
-            .flat_map(|v| v.cross_references.clone())
-            .collect::<Vec<_>>();
-        let cross_refs_len = cross_refs.len();
+            let Some((word, offset)) = word_at(&holder.read(), &chapter_text.text, offset) else {
+                return;
+            };
+            if let Some(verse) = chapter_text.verse_at(offset) {
+                word_selection.set(Some(WordSelection {
+                    verse: VerseRef::new(
+                        bible_id.clone(),
+                        book_id.clone(),
+                        chapter_number,
+                        verse.number,
+                    ),
+                    word: word.clone(),
+                    occurrence: occurrences_before(
+                        &chapter_text.slice(verse.text.clone()),
+                        offset - verse.text.start,
+                        &word,
+                    ),
+                }));
+            }
+            glossary_word.set(Some(word));
+            active_tab.set(if has_tagged_text { 2 } else { 1 });
+        };
 
         rect()
+            .width(Size::Fill)
             .height(Size::Fill)
             .content(Content::Flex)
             .vertical()
             .background(Color::from_hex("#1E1E1E").unwrap())
             .padding(15.0)
             .spacing(10.0)
+            .on_global_key_down(move |e: Event<KeyboardEventData>| {
+                if e.key == Key::Named(NamedKey::Shift) {
+                    shift_held.set(true);
+                }
+            })
+            .on_global_key_up(move |e: Event<KeyboardEventData>| {
+                if e.key == Key::Named(NamedKey::Shift) {
+                    shift_held.set(false);
+                }
+            })
             .child(
                 rect()
                     .content(Content::Flex)
@@ -135,27 +695,92 @@ impl Component for VersePanel {
                             .text(format!("{} {}", chapter.book_name, chapter.chapter)),
                     )
                     .child(
-                        Button::new()
-                            .on_press(move |_| selected_verse.set(None))
-                            .background(Color::from_hex("#2C2C2C").unwrap())
-                            .hover_background(Color::from_hex("#353535").unwrap())
-                            .padding(5.0)
-                            .child(label().color(Color::WHITE).text("✕")),
+                        rect()
+                            .horizontal()
+                            .spacing(5.0)
+                            .child(history_button("‹", can_go_back).on_press(move |_| {
+                                go_back(selected_verse, history);
+                            }))
+                            .child(history_button("›", can_go_forward).on_press(move |_| {
+                                go_forward(selected_verse, history);
+                            }))
+                            .maybe_child(has_tagged_text.then(|| {
+                                Button::new()
+                                    .on_press(move |_| interlinear.set(!interlinear()))
+                                    .background(if show_interlinear {
+                                        Color::from_hex("#3A3A3A").unwrap()
+                                    } else {
+                                        Color::from_hex("#2C2C2C").unwrap()
+                                    })
+                                    .hover_background(Color::from_hex("#353535").unwrap())
+                                    .padding(5.0)
+                                    .child(label().color(Color::WHITE).text("Interlinear"))
+                                    .into_element()
+                            }))
+                            // Android shows a single window, so there is no second screen to present on
+                            .maybe_child(cfg!(not(target_os = "android")).then(|| {
+                                Button::new()
+                                    .on_press({
+                                        let chapter_key = presented_chapter.clone();
+                                        let slides = slides.clone();
+                                        move |_| {
+                                            Presentation::start(
+                                                chapter_key.clone(),
+                                                slides.clone(),
+                                                presented_verse,
+                                                settings.read().settings.presentation.clone(),
+                                            )
+                                        }
+                                    })
+                                    .background(Color::from_hex("#2C2C2C").unwrap())
+                                    .hover_background(Color::from_hex("#353535").unwrap())
+                                    .padding(5.0)
+                                    .child(label().color(Color::WHITE).text("Present"))
+                                    .into_element()
+                            }))
+                            .child(
+                                Button::new()
+                                    .on_press(move |_| close(selected_verse, history))
+                                    .background(Color::from_hex("#2C2C2C").unwrap())
+                                    .hover_background(Color::from_hex("#353535").unwrap())
+                                    .padding(5.0)
+                                    .child(label().color(Color::WHITE).text("✕")),
+                            ),
                     ),
             )
+            .child(PresentationControls::new(
+                presented_chapter,
+                slides,
+                presented_verse,
+            ))
             .child(
-                ScrollView::new()
+                ScrollView::new_controlled(scroll_controller)
                     .height(Size::percent(50.0))
                     .direction(Direction::Vertical)
-                    .child(
-                        paragraph()
+                    .child(match interlinear_view {
+                        Some(view) => rect()
                             .width(Size::Fill)
                             .padding(10.0)
-                            .spans_iter(paragraph_spans.into_iter())
-                            .highlights(Some(highlights))
-                            .highlight_color(Color::from_hex("#3A3A3A").unwrap()),
-                    ),
+                            .child(view)
+                            .into_element(),
+                        None => rect()
+                            .width(Size::Fill)
+                            .padding(10.0)
+                            .child(
+                                paragraph()
+                                    .holder(holder.read().clone())
+                                    .width(Size::Fill)
+                                    .on_pointer_down(on_paragraph_down)
+                                    .on_pointer_up(on_paragraph_press)
+                                    .on_sized(on_paragraph_sized)
+                                    .spans_iter(paragraph_spans.into_iter())
+                                    .highlights(Some(highlights))
+                                    .highlight_color(Color::from_hex("#3A3A3A").unwrap()),
+                            )
+                            .into_element(),
+                    }),
             )
+            .maybe_child(selection_bar.map(|bar| bar.into_element()))
             .child(
                 rect()
                     .height(Size::px(1.0))
@@ -168,63 +793,21 @@ impl Component for VersePanel {
                     .horizontal()
                     .padding(5.0)
                     .child(
-                        Button::new()
-                            .height(Size::Fill)
-                            .on_press(move |_| active_tab.set(0))
-                            .corner_radius(CornerRadius {
+                        tab_button("Cross References", 0, Some(cross_refs_len)).corner_radius(
+                            CornerRadius {
                                 top_left: 8.,
                                 ..Default::default()
-                            })
-                            .background(if *active_tab.read() == 0 {
-                                Color::from_hex("#3A3A3A").unwrap()
-                            } else {
-                                Color::from_hex("#2C2C2C").unwrap()
-                            })
-                            .hover_background(Color::from_hex("#353535").unwrap())
-                            .padding(8.0)
-                            .child(
-                                rect()
-                                    .center()
-                                    .horizontal()
-                                    .spacing(5.)
-                                    .child(
-                                        rect()
-                                            .center()
-                                            .text_align(TextAlign::Center)
-                                            .rounded_full()
-                                            .padding(8.)
-                                            .background(Color::DARK_GRAY)
-                                            .child(
-                                                label()
-                                                    .color(Color::WHITE)
-                                                    .text(cross_refs_len.to_string()),
-                                            ),
-                                    )
-                                    .child(
-                                        label()
-                                            .color(Color::WHITE)
-                                            .text_align(TextAlign::Center)
-                                            .text("Cross References"),
-                                    ),
-                            ),
+                            },
+                        ),
                     )
-                    .child(
-                        Button::new()
-                            .height(Size::Fill)
-                            .on_press(move |_| active_tab.set(1))
-                            .corner_radius(CornerRadius {
-                                top_right: 8.,
-                                ..Default::default()
-                            })
-                            .background(if *active_tab.read() == 1 {
-                                Color::from_hex("#3A3A3A").unwrap()
-                            } else {
-                                Color::from_hex("#2C2C2C").unwrap()
-                            })
-                            .hover_background(Color::from_hex("#353535").unwrap())
-                            .padding(8.0)
-                            .child(label().color(Color::WHITE).text("Glossary")),
-                    ),
+                    .child(tab_button("Glossary", 1, None))
+                    .child(tab_button("Word Study", 2, None))
+                    .child(tab_button("Commentary", 3, None))
+                    .child(tab_button("Network", 4, None))
+                    .child(tab_button("Topics", 5, None).corner_radius(CornerRadius {
+                        top_right: 8.,
+                        ..Default::default()
+                    })),
             )
             .child(
                 rect()
@@ -232,62 +815,27 @@ impl Component for VersePanel {
                     .padding(10.0)
                     .content(Content::Flex)
                     .child(if *active_tab.read() == 0 {
-                        VirtualScrollView::new_with_data(cross_refs, move |i, cross_refs| {
-                            let cross_ref = &cross_refs[i];
-                            rect()
-                                .key(i)
-                                .padding((0., 0., 10., 0.))
-                                .child(
-                                    rect()
-                                        .background(Color::from_hex("#2C2C2C").unwrap())
-                                        .rounded()
-                                        .padding(10.0)
-                                        .content(Content::Flex)
-                                        .vertical()
-                                        .spacing(5.0)
-                                        .width(Size::Fill)
-                                        .child(
-                                            label()
-                                                .color(Color::from_hex("#888888").unwrap())
-                                                .font_size(12.0)
-                                                .font_weight(FontWeight::BOLD)
-                                                .text(format!(
-                                                    "{} {}:{}",
-                                                    cross_ref.book_name,
-                                                    cross_ref.chapter,
-                                                    cross_ref.verse
-                                                )),
-                                        )
-                                        .child(
-                                            label()
-                                                .color(Color::from_hex("#CCCCCC").unwrap())
-                                                .font_size(14.0)
-                                                .text(cross_ref.text.clone()),
-                                        ),
-                                )
-                                .into_element()
-                        })
-                        .length(cross_refs_len as i32)
-                        .item_size(80.)
-                        .expanded()
-                        .direction(Direction::Vertical)
-                        .into_element()
-                    } else {
-                        rect()
-                            .content(Content::Flex)
-                            .center()
-                            .main_align(Alignment::Center)
-                            .cross_align(Alignment::Center)
-                            .height(Size::Fill)
-                            .child(
-                                label()
-                                    .color(Color::from_hex("#888888").unwrap())
-                                    .font_size(16.0)
-                                    .text("Under construction..."),
-                            )
+                        CrossReferences::new(database.clone(), cross_refs, selected_verse, history)
+                            .verses(selected_verses)
+                            .whole_chapter(whole_chapter)
+                            .into_element()
+                    } else if *active_tab.read() == 1 {
+                        Glossary::new(glossary_word).into_element()
+                    } else if *active_tab.read() == 2 {
+                        WordStudyPanel::new(word_selection, selected_verse, history).into_element()
+                    } else if *active_tab.read() == 3 {
+                        CommentaryPanel::new(selected_verse, history).into_element()
+                    } else if *active_tab.read() == 4 {
+                        ReferenceGraphView::new(database.clone(), selected_verse, history)
                             .into_element()
+                    } else {
+                        TopicsPanel::new(selected_verse, history).into_element()
                     }),
             )
             .into_element()
     }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
 }
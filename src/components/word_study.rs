@@ -0,0 +1,233 @@
+use freya::prelude::*;
+use freya::radio::*;
+
+use crate::components::empty_state;
+use crate::history::{NavigationHistory, navigate};
+use crate::reference::VerseRef;
+use crate::{AppChannel, AppState};
+
+/// Word clicked in the text of a chapter
+#[derive(Clone, PartialEq, Debug)]
+pub struct WordSelection {
+    pub verse: VerseRef,
+    pub word: String,
+    /// How many times the word appears in the verse before the clicked one
+    pub occurrence: usize,
+}
+
+/// Original language word behind the selected word, with its concordance
+#[derive(Clone, PartialEq)]
+pub struct WordStudyPanel {
+    selection: State<Option<WordSelection>>,
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+    key: DiffKey,
+}
+
+impl WordStudyPanel {
+    pub fn new(
+        selection: impl Into<State<Option<WordSelection>>>,
+        selected_verse: impl Into<State<Option<VerseRef>>>,
+        history: impl Into<State<NavigationHistory<VerseRef>>>,
+    ) -> Self {
+        Self {
+            selection: selection.into(),
+            selected_verse: selected_verse.into(),
+            history: history.into(),
+            key: DiffKey::None,
+        }
+    }
+}
+
+impl KeyExt for WordStudyPanel {
+    fn write_key(&mut self) -> &mut DiffKey {
+        &mut self.key
+    }
+}
+
+fn field(name: &str, value: String) -> Element {
+    rect()
+        .horizontal()
+        .spacing(5.)
+        .child(
+            label()
+                .color(Color::from_hex("#888888").unwrap())
+                .font_size(13.0)
+                .text(format!("{name}:")),
+        )
+        .child(
+            label()
+                .color(Color::from_hex("#CCCCCC").unwrap())
+                .font_size(13.0)
+                .text(value),
+        )
+        .into_element()
+}
+
+impl Component for WordStudyPanel {
+    fn render(&self) -> impl IntoElement {
+        let modules = use_radio::<AppState, AppChannel>(AppChannel::Modules);
+        let catalog = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+        let selected_verse = self.selected_verse;
+        let history = self.history;
+
+        let Some(selection) = self.selection.read().clone() else {
+            return empty_state("Click a word to study it");
+        };
+        let Some(study) = modules.read().modules.study(
+            &selection.verse.bible_id,
+            &selection.verse.book_id,
+            selection.verse.chapter,
+            selection.verse.verse,
+            &selection.word,
+            selection.occurrence,
+        ) else {
+            return empty_state(&format!(
+                "No Strong's number found for \"{}\"",
+                selection.word
+            ));
+        };
+
+        let lemma = study
+            .lexicon
+            .as_ref()
+            .map(|l| l.lemma.clone())
+            .unwrap_or_else(|| study.strong.clone());
+        let concordance = study.concordance.clone();
+        let concordance_len = concordance.len();
+        let bible_id = selection.verse.bible_id.clone();
+        let catalog = catalog.read().catalog.clone();
+
+        rect()
+            .expanded()
+            .content(Content::Flex)
+            .vertical()
+            .spacing(10.)
+            .child(
+                rect()
+                    .background(Color::from_hex("#2C2C2C").unwrap())
+                    .rounded()
+                    .padding(10.0)
+                    .vertical()
+                    .spacing(5.0)
+                    .width(Size::Fill)
+                    .child(
+                        rect()
+                            .horizontal()
+                            .width(Size::Fill)
+                            .main_align(Alignment::SpaceBetween)
+                            .cross_align(Alignment::Center)
+                            .child(
+                                label()
+                                    .color(Color::WHITE)
+                                    .font_size(22.0)
+                                    .font_weight(FontWeight::BOLD)
+                                    .text(lemma),
+                            )
+                            .child(
+                                label()
+                                    .color(Color::from_hex("#6FA8DC").unwrap())
+                                    .font_weight(FontWeight::BOLD)
+                                    .text(study.strong.clone()),
+                            ),
+                    )
+                    .child(field("Word", study.word.text.clone()))
+                    .maybe_child(study.lexicon.as_ref().and_then(|l| {
+                        (!l.transliteration.is_empty()).then(|| {
+                            let mut value = l.transliteration.clone();
+                            if !l.pronunciation.is_empty() {
+                                value.push_str(&format!(" ({})", l.pronunciation));
+                            }
+                            field("Transliteration", value)
+                        })
+                    }))
+                    .maybe_child(
+                        study
+                            .parsing
+                            .clone()
+                            .or_else(|| study.word.morph.clone())
+                            .map(|parsing| field("Parsing", parsing)),
+                    )
+                    .maybe_child(study.lexicon.as_ref().and_then(|l| {
+                        (!l.gloss.is_empty()).then(|| field("Gloss", l.gloss.clone()))
+                    }))
+                    .maybe_child(study.lexicon.as_ref().and_then(|l| {
+                        (!l.definition.is_empty()).then(|| {
+                            label()
+                                .color(Color::from_hex("#CCCCCC").unwrap())
+                                .font_size(14.0)
+                                .text(l.definition.clone())
+                                .into_element()
+                        })
+                    })),
+            )
+            .child(
+                label()
+                    .color(Color::from_hex("#888888").unwrap())
+                    .font_size(12.0)
+                    .font_weight(FontWeight::BOLD)
+                    .text(format!("Concordance · {concordance_len} verses")),
+            )
+            .child(
+                VirtualScrollView::new_with_data(concordance, move |i, concordance| {
+                    let verse = &concordance[i];
+                    let book_name = catalog
+                        .book(&bible_id, &verse.book)
+                        .map(|b| b.name.clone())
+                        .unwrap_or_else(|| verse.book.clone());
+                    let target = VerseRef::new(
+                        bible_id.clone(),
+                        verse.book.clone(),
+                        verse.chapter,
+                        verse.verse,
+                    );
+                    rect()
+                        .key(i)
+                        .padding((0., 0., 10., 0.))
+                        .child(
+                            Button::new()
+                                .width(Size::Fill)
+                                .background(Color::from_hex("#2C2C2C").unwrap())
+                                .hover_background(Color::from_hex("#353535").unwrap())
+                                .on_press(move |_| {
+                                    navigate(selected_verse, history, target.clone())
+                                })
+                                .child(
+                                    rect()
+                                        .vertical()
+                                        .spacing(5.0)
+                                        .width(Size::Fill)
+                                        .child(
+                                            label()
+                                                .color(Color::from_hex("#888888").unwrap())
+                                                .font_size(12.0)
+                                                .font_weight(FontWeight::BOLD)
+                                                .text(format!(
+                                                    "{book_name} {}:{}",
+                                                    verse.chapter, verse.verse
+                                                )),
+                                        )
+                                        .child(
+                                            label()
+                                                .color(Color::from_hex("#CCCCCC").unwrap())
+                                                .font_size(14.0)
+                                                .max_lines(2)
+                                                .text_overflow(TextOverflow::Ellipsis)
+                                                .text(verse.text()),
+                                        ),
+                                ),
+                        )
+                        .into_element()
+                })
+                .length(concordance_len as i32)
+                .item_size(80.)
+                .expanded()
+                .direction(Direction::Vertical),
+            )
+            .into_element()
+    }
+
+    fn render_key(&self) -> DiffKey {
+        self.key.clone().or(self.default_key())
+    }
+}
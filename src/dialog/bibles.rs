@@ -6,10 +6,11 @@ use async_io::Timer;
 use freya::{prelude::*, radio::*};
 use futures::StreamExt;
 use kanal::{Receiver, Sender, unbounded};
-use setup_core::{Selection, TantivySink, event};
+use setup_core::{DbSink, Selection, TantivySink, event};
 
 use crate::dialog::Dialog;
 use crate::utils::data_dir;
+use crate::versification::Versification;
 use crate::{AppChannel, AppState};
 
 #[derive(Clone, PartialEq, PartialOrd, Debug)]
@@ -22,11 +23,43 @@ pub struct BibleItem {
     pub progress: f32, // 0.0 .. 1.0
 }
 
-pub fn manage_bibles(mut show_dialog: State<bool>, database: Arc<TantivySink>) -> impl IntoElement {
+/// Shows the verse numbering of an installed Bible and switches to the next scheme when
+/// pressed, for Bibles whose numbering is not recognized from their id
+fn versification_button(mut radio: Radio<AppState, AppChannel>, bible_id: String) -> Element {
+    let current = radio.read().catalog.versification(&bible_id);
+    Button::new()
+        .compact()
+        .on_press(move |_| {
+            let position = Versification::ALL.iter().position(|v| *v == current);
+            let next = Versification::ALL[position.map_or(0, |i| i + 1) % Versification::ALL.len()];
+            {
+                let mut state = radio.write_channel(AppChannel::Settings);
+                state.settings.versifications.insert(bible_id.clone(), next);
+                state.settings.save();
+            }
+            let mut state = radio.write_channel(AppChannel::Catalog);
+            if let Some(bible) = state.catalog.bibles.iter_mut().find(|b| b.id == bible_id) {
+                bible.set_versification(next);
+            }
+        })
+        .child(
+            label()
+                .text(format!("Numbering: {}", current.label()))
+                .color(Color::from_hex("#bdbdbd").unwrap())
+                .font_size(12.),
+        )
+        .into_element()
+}
+
+pub fn manage_bibles(
+    mut show_dialog: State<bool>,
+    database: Arc<TantivySink>,
+    mut installs: State<usize>,
+) -> impl IntoElement {
     let mut search = use_state(String::new);
     let mut all_bibles = use_state(Vec::<BibleItem>::new);
     let mut filtered = use_state(Vec::<BibleItem>::new);
-    let mut radio = use_radio::<AppState, AppChannel>(AppChannel::BooksSuggesions);
+    let radio = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
 
     let cache_dir = data_dir(&["cache"]);
     let (tx, rx): (Sender<(String, u64, u64)>, Receiver<(String, u64, u64)>) = unbounded();
@@ -66,15 +99,6 @@ pub fn manage_bibles(mut show_dialog: State<bool>, database: Arc<TantivySink>) -
         let setup = setup.clone();
         let database = database.clone();
 
-        let books = setup
-            .list_installed_books()
-            .unwrap_or_default()
-            .into_iter()
-            .flat_map(|(_, books)| books)
-            .collect::<Vec<_>>();
-
-        radio.write_channel(AppChannel::BooksSuggesions).books = books;
-
         let list_res: Result<
             Vec<(
                 String,
@@ -110,10 +134,12 @@ pub fn manage_bibles(mut show_dialog: State<bool>, database: Arc<TantivySink>) -
             loop {
                 interval.next().await;
 
+                let mut finished = false;
                 while let Ok(Some((step_id, current, total))) = rx.try_recv() {
                     all_bibles.with_mut(|mut bibles| {
                         if let Some(bible) = bibles.iter_mut().find(|b| b.id == step_id) {
                             let is_complete = current == total;
+                            finished |= is_complete && !bible.installed;
                             bible.installing = !is_complete;
                             bible.installed = is_complete;
                             bible.progress = if total > 0 {
@@ -124,6 +150,11 @@ pub fn manage_bibles(mut show_dialog: State<bool>, database: Arc<TantivySink>) -
                         }
                     });
                 }
+
+                // The app loads the catalog again with the new Bible
+                if finished {
+                    *installs.write() += 1;
+                }
             }
         });
     });
@@ -269,6 +300,12 @@ pub fn manage_bibles(mut show_dialog: State<bool>, database: Arc<TantivySink>) -
                                         rect()
                                             .horizontal()
                                             .spacing(8.)
+                                            .cross_align(Alignment::Center)
+                                            .maybe_child(
+                                                b.installed.then(|| {
+                                                    versification_button(radio, b.id.clone())
+                                                }),
+                                            )
                                             .child(if b.installed {
                                                 label()
                                                     .text("Installed")
@@ -0,0 +1,281 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use async_io::Timer;
+use freya::{prelude::*, radio::*};
+use futures::StreamExt;
+use kanal::{Receiver, Sender, unbounded};
+use setup_core::TantivySink;
+
+use crate::catalog::CanonicalBook;
+use crate::dialog::Dialog;
+use crate::export::{ExportDocument, ExportFormat, ExportOptions, export_path, parse_passages};
+use crate::reference::VerseRef;
+use crate::{AppChannel, AppState};
+
+fn toggle(text: String, active: bool, mut on_press: impl FnMut() + 'static) -> Element {
+    Button::new()
+        .compact()
+        .background(if active {
+            Color::from_hex("#3A3A3A").unwrap()
+        } else {
+            Color::from_hex("#2C2C2C").unwrap()
+        })
+        .hover_background(Color::from_hex("#353535").unwrap())
+        .on_press(move |_| on_press())
+        .child(
+            label()
+                .color(if active {
+                    Color::WHITE
+                } else {
+                    Color::from_hex("#888888").unwrap()
+                })
+                .font_size(13.)
+                .text(text),
+        )
+        .into_element()
+}
+
+fn section(title: &'static str, children: impl IntoIterator<Item = Element>) -> Element {
+    rect()
+        .vertical()
+        .spacing(6.)
+        .child(
+            label()
+                .text(title)
+                .font_weight(FontWeight::BOLD)
+                .color(Color::from_hex("#cfcfcf").unwrap())
+                .font_size(13.),
+        )
+        .child(
+            rect()
+                .horizontal()
+                .content(Content::Wrap)
+                .spacing(6.)
+                .children(children),
+        )
+        .into_element()
+}
+
+pub fn export_passages(
+    mut show_dialog: State<bool>,
+    database: Arc<TantivySink>,
+    selected_verse: State<Option<VerseRef>>,
+) -> impl IntoElement {
+    let radio = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+    let annotations = use_radio::<AppState, AppChannel>(AppChannel::Annotations);
+    let title = use_state(|| "Passages".to_string());
+    let mut passages = use_state(String::new);
+    let mut translations = use_state(Vec::<String>::new);
+    let mut format = use_state(|| ExportFormat::Pdf);
+    let mut options = use_state(ExportOptions::default);
+    let mut status = use_state(|| None::<Result<String, String>>);
+    let mut exporting = use_state(|| false);
+
+    let (tx, rx): (
+        Sender<Result<String, String>>,
+        Receiver<Result<String, String>>,
+    ) = use_hook(unbounded);
+
+    use_hook(|| {
+        let rx = rx.clone();
+
+        spawn(async move {
+            let mut interval = Timer::interval(Duration::from_millis(120));
+            loop {
+                interval.next().await;
+
+                while let Ok(Some(result)) = rx.try_recv() {
+                    exporting.set(false);
+                    status.set(Some(result));
+                }
+            }
+        });
+    });
+
+    // Start from the verse being read every time the dialog opens
+    use_side_effect(move || {
+        if !*show_dialog.read() {
+            return;
+        }
+        if let Some(verse) = selected_verse.peek().clone() {
+            let book = CanonicalBook::by_id(&verse.book_id)
+                .and_then(|b| b.names.first().copied())
+                .unwrap_or(verse.book_id.as_str())
+                .to_string();
+            // Passages are typed numbered as the KJV
+            let (chapter, number) = verse.to_standard(&radio.read().catalog);
+            passages.set(format!("{book} {chapter}:{number}"));
+            translations.set(vec![verse.bible_id]);
+        }
+        status.set(None);
+    });
+
+    if !*show_dialog.read() {
+        return rect().into_element();
+    }
+
+    let bibles = radio
+        .read()
+        .catalog
+        .bibles
+        .iter()
+        .map(|b| b.id.clone())
+        .collect::<Vec<_>>();
+    let selected_translations = translations.read().clone();
+    let current_format = *format.read();
+    let current_options = *options.read();
+
+    // Reading the passages and writing the file can take a while for long exports, so it is
+    // done in the background like the installs
+    let export_action = move |_| {
+        if exporting() {
+            return;
+        }
+        let title = title.read().trim().to_string();
+        let passages = passages.read().clone();
+        let translations = translations.read().clone();
+        let format = *format.read();
+        let options = *options.read();
+        let catalog = radio.read().catalog.clone();
+        let annotations = annotations.read().annotations.clone();
+        exporting.set(true);
+        status.set(None);
+        thread::spawn({
+            let database = database.clone();
+            let tx = tx.clone();
+            move || {
+                let result = parse_passages(&passages)
+                    .and_then(|passages| {
+                        ExportDocument::collect(
+                            &database,
+                            &catalog,
+                            title.clone(),
+                            &passages,
+                            &translations,
+                            &annotations,
+                        )
+                    })
+                    .and_then(|document| {
+                        let path = export_path(&title, format);
+                        document.export(format, &options, &path)?;
+                        Ok(path.display().to_string())
+                    })
+                    .inspect_err(|e| tracing::error!("Error exporting passages: {e}"))
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(result);
+            }
+        });
+    };
+
+    Dialog::new("Export Passages".to_string())
+        .width(Size::px(640.))
+        .on_close_request(move |()| show_dialog.set(false))
+        .child(
+            rect()
+                .vertical()
+                .spacing(12.)
+                .padding(8.)
+                .child(Input::new(title).width(Size::Fill).placeholder("Title"))
+                .child(
+                    Input::new(passages)
+                        .width(Size::Fill)
+                        .placeholder("Passages, e.g. John 3:16-18; Romans 8"),
+                )
+                .child(section(
+                    "Translations",
+                    bibles.into_iter().map(|bible_id| {
+                        let active = selected_translations.contains(&bible_id);
+                        toggle(bible_id.to_uppercase(), active, move || {
+                            translations.with_mut(|mut translations| {
+                                if let Some(i) = translations.iter().position(|t| *t == bible_id) {
+                                    translations.remove(i);
+                                } else {
+                                    translations.push(bible_id.clone());
+                                }
+                            });
+                        })
+                    }),
+                ))
+                .child(section(
+                    "Format",
+                    ExportFormat::ALL.into_iter().map(|f| {
+                        toggle(f.label().to_string(), f == current_format, move || {
+                            format.set(f)
+                        })
+                    }),
+                ))
+                .child(section(
+                    "Layout",
+                    [
+                        toggle(
+                            "1 column".to_string(),
+                            current_options.columns == 1,
+                            move || options.write().columns = 1,
+                        ),
+                        toggle(
+                            "2 columns".to_string(),
+                            current_options.columns == 2,
+                            move || options.write().columns = 2,
+                        ),
+                        toggle("A−".to_string(), false, move || {
+                            let mut options = options.write();
+                            options.font_size = (options.font_size - 1.).max(6.);
+                        }),
+                        label()
+                            .text(format!("{} pt", current_options.font_size))
+                            .color(Color::WHITE)
+                            .font_size(13.)
+                            .into_element(),
+                        toggle("A+".to_string(), false, move || {
+                            let mut options = options.write();
+                            options.font_size = (options.font_size + 1.).min(32.);
+                        }),
+                        toggle(
+                            "Notes".to_string(),
+                            current_options.include_notes,
+                            move || {
+                                let mut options = options.write();
+                                options.include_notes = !options.include_notes;
+                            },
+                        ),
+                        toggle(
+                            "Cross references".to_string(),
+                            current_options.include_cross_references,
+                            move || {
+                                let mut options = options.write();
+                                options.include_cross_references =
+                                    !options.include_cross_references;
+                            },
+                        ),
+                    ],
+                ))
+                .maybe_child(exporting().then(|| {
+                    label()
+                        .text("Exporting...")
+                        .color(Color::from_hex("#f39c12").unwrap())
+                        .font_size(13.)
+                        .into_element()
+                }))
+                .maybe_child(status.read().clone().map(|status| {
+                    let (text, color) = match status {
+                        Ok(path) => (format!("Saved to {path}"), "#27ae60"),
+                        Err(e) => (e, "#e74c3c"),
+                    };
+                    label()
+                        .text(text)
+                        .color(Color::from_hex(color).unwrap())
+                        .font_size(13.)
+                        .into_element()
+                })),
+        )
+        .action(
+            Button::new()
+                .expanded()
+                .filled()
+                .on_press(export_action)
+                .child(label().text("Export")),
+        )
+        .into_element()
+}
@@ -0,0 +1,281 @@
+use freya::{prelude::*, radio::*};
+
+use crate::dialog::Dialog;
+use crate::history::{NavigationHistory, navigate};
+use crate::memory::{
+    RecallScore, ReviewMode, fill_in_the_blank, first_letters, grade_blanks, grade_recall,
+};
+use crate::reference::VerseRef;
+use crate::utils::today;
+use crate::{AppChannel, AppState};
+
+/// Grades offered after checking an answer, with their SM-2 quality
+const GRADES: [(&str, u8); 4] = [("Again", 1), ("Hard", 3), ("Good", 4), ("Easy", 5)];
+
+fn small_button(text: impl Into<String>, active: bool) -> Button {
+    Button::new()
+        .compact()
+        .background(if active {
+            Color::from_hex("#3A3A3A").unwrap()
+        } else {
+            Color::from_hex("#2C2C2C").unwrap()
+        })
+        .hover_background(Color::from_hex("#353535").unwrap())
+        .child(label().color(Color::WHITE).font_size(13.).text(text.into()))
+}
+
+/// Reviews the memory verses that are due and lists every verse with its schedule
+pub fn memory_verses(
+    mut show_dialog: State<bool>,
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+) -> impl IntoElement {
+    let mut memory = use_radio::<AppState, AppChannel>(AppChannel::Memory);
+    let mut mode = use_state(|| ReviewMode::FirstLetters);
+    let mut answer = use_state(String::new);
+    let mut score = use_state(|| None::<RecallScore>);
+
+    if !*show_dialog.read() {
+        return rect().into_element();
+    }
+
+    let today = today();
+    let verses = memory.read().memory.clone();
+    let stats = verses.stats(today);
+    let current = verses.due(today).first().map(|v| (*v).clone());
+    let next_due = verses.verses.iter().map(|v| v.due).min();
+
+    let review =
+        current.map(|verse| {
+            let current_mode = *mode.read();
+            let (prompt, hidden) = match current_mode {
+                ReviewMode::FirstLetters => (first_letters(&verse.text), Vec::new()),
+                ReviewMode::FillInTheBlank => fill_in_the_blank(&verse.text, verse.reviews as u64),
+                ReviewMode::FullRecall => ("Type the verse from memory".to_string(), Vec::new()),
+            };
+            let check = {
+                let text = verse.text.clone();
+                move || {
+                    let typed = answer.read().clone();
+                    score.set(Some(match current_mode {
+                        ReviewMode::FillInTheBlank => grade_blanks(&hidden, &typed),
+                        _ => grade_recall(&text, &typed),
+                    }));
+                }
+            };
+            let checked = *score.read();
+
+            rect()
+                .vertical()
+                .spacing(8.)
+                .padding(10.)
+                .rounded()
+                .width(Size::Fill)
+                .background(Color::from_hex("#2C2C2C").unwrap())
+                .child(
+                    rect()
+                        .horizontal()
+                        .width(Size::Fill)
+                        .main_align(Alignment::SpaceBetween)
+                        .cross_align(Alignment::Center)
+                        .child(
+                            label()
+                                .text(verse.reference.clone())
+                                .font_weight(FontWeight::BOLD)
+                                .color(Color::WHITE),
+                        )
+                        .child(rect().horizontal().spacing(6.).children(
+                            ReviewMode::ALL.into_iter().map(|m| {
+                                small_button(m.label(), m == current_mode)
+                                    .on_press(move |_| {
+                                        mode.set(m);
+                                        score.set(None);
+                                    })
+                                    .into_element()
+                            }),
+                        )),
+                )
+                .child(
+                    label()
+                        .width(Size::Fill)
+                        .color(Color::from_hex("#CCCCCC").unwrap())
+                        .font_size(16.)
+                        .text(prompt),
+                )
+                .child(
+                    Input::new(answer)
+                        .width(Size::Fill)
+                        .placeholder(match current_mode {
+                            ReviewMode::FillInTheBlank => "Type the missing words in order...",
+                            _ => "Type the verse and press Enter...",
+                        })
+                        .on_submit({
+                            let mut check = check.clone();
+                            move |_: String| check()
+                        }),
+                )
+                .child(match checked {
+                    None => rect()
+                        .horizontal()
+                        .spacing(6.)
+                        .child(small_button("Check", false).on_press({
+                            let mut check = check.clone();
+                            move |_| check()
+                        }))
+                        .child(small_button("Show verse", false).on_press(move |_| {
+                            score.set(Some(RecallScore {
+                                accuracy: 0.,
+                                quality: 0,
+                            }))
+                        }))
+                        .into_element(),
+                    Some(checked) => {
+                        let passage = verse.passage.clone();
+                        rect()
+                            .vertical()
+                            .spacing(8.)
+                            .child(
+                                label()
+                                    .text(format!("{:.0}% recalled", checked.accuracy * 100.))
+                                    .font_weight(FontWeight::BOLD)
+                                    .color(if checked.quality >= 3 {
+                                        Color::from_hex("#27ae60").unwrap()
+                                    } else {
+                                        Color::from_hex("#e74c3c").unwrap()
+                                    }),
+                            )
+                            .child(
+                                label()
+                                    .width(Size::Fill)
+                                    .color(Color::WHITE)
+                                    .font_size(15.)
+                                    .text(verse.text.clone()),
+                            )
+                            .child(rect().horizontal().spacing(6.).children(
+                                GRADES.into_iter().map(|(text, quality)| {
+                                    let suggested = match checked.quality {
+                                        0..=2 => 1,
+                                        q => q,
+                                    };
+                                    let passage = passage.clone();
+                                    small_button(text, quality == suggested)
+                                        .on_press(move |_| {
+                                            let mut state = memory.write();
+                                            state.memory.review(&passage, quality);
+                                            state.memory.save();
+                                            drop(state);
+                                            answer.set(String::new());
+                                            score.set(None);
+                                        })
+                                        .into_element()
+                                }),
+                            ))
+                            .into_element()
+                    }
+                })
+                .into_element()
+        });
+
+    Dialog::new("Memory Verses".to_string())
+        .width(Size::px(640.))
+        .on_close_request(move |()| show_dialog.set(false))
+        .child(
+            rect()
+                .vertical()
+                .spacing(10.)
+                .padding(8.)
+                .max_height(Size::window_percent(70.))
+                .child(
+                    label()
+                        .text(format!(
+                            "{} verses · {} due · {} memorized · {} reviews · {:.0}% retention",
+                            stats.total,
+                            stats.due,
+                            stats.memorized,
+                            stats.reviews,
+                            stats.retention * 100.
+                        ))
+                        .color(Color::from_hex("#cfcfcf").unwrap())
+                        .font_size(13.),
+                )
+                .child(review.unwrap_or_else(|| {
+                    label()
+                        .text(match next_due {
+                            Some(due) => format!(
+                                "Nothing to review today, next review in {} days",
+                                due.saturating_sub(today)
+                            ),
+                            None => "Select verses in the reader and press Memorize to add them"
+                                .to_string(),
+                        })
+                        .color(Color::from_hex("#888888").unwrap())
+                        .into_element()
+                }))
+                .child(
+                    ScrollView::new()
+                        .direction(Direction::Vertical)
+                        .height(Size::px(200.))
+                        .spacing(6.)
+                        .children(verses.verses.iter().map(|verse| {
+                            let passage = verse.passage.clone();
+                            rect()
+                                .key(&verse.reference)
+                                .rounded()
+                                .width(Size::Fill)
+                                .padding(8.)
+                                .background(Color::from_hex("#2C2C2C").unwrap())
+                                .horizontal()
+                                .main_align(Alignment::SpaceBetween)
+                                .cross_align(Alignment::Center)
+                                .child(
+                                    rect()
+                                        .vertical()
+                                        .child(
+                                            label()
+                                                .text(verse.reference.clone())
+                                                .color(Color::WHITE),
+                                        )
+                                        .child(
+                                            label()
+                                                .text(if verse.is_due(today) {
+                                                    "Due today".to_string()
+                                                } else {
+                                                    format!(
+                                                        "Next review in {} days",
+                                                        verse.due - today
+                                                    )
+                                                })
+                                                .color(Color::from_hex("#bdbdbd").unwrap())
+                                                .font_size(12.),
+                                        ),
+                                )
+                                .child(
+                                    rect()
+                                        .horizontal()
+                                        .spacing(6.)
+                                        .child(small_button("Open", false).on_press({
+                                            let passage = passage.clone();
+                                            move |_| {
+                                                navigate(selected_verse, history, passage.first());
+                                                show_dialog.set(false);
+                                            }
+                                        }))
+                                        .child(small_button("Remove", false).on_press(move |_| {
+                                            let mut state = memory.write();
+                                            state.memory.remove(&passage);
+                                            state.memory.save();
+                                        })),
+                                )
+                                .into_element()
+                        })),
+                ),
+        )
+        .action(
+            Button::new()
+                .expanded()
+                .filled()
+                .on_press(move |_| show_dialog.set(false))
+                .child(label().text("Done")),
+        )
+        .into_element()
+}
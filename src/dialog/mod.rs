@@ -1,8 +1,22 @@
 use freya::prelude::*;
 
+use crate::components::{is_mobile_layout, safe_area};
+
 mod bibles;
+mod export;
+mod memory;
+mod modules;
+mod plans;
+mod storage;
+mod topics;
 
 pub use bibles::*;
+pub use export::*;
+pub use memory::*;
+pub use modules::*;
+pub use plans::*;
+pub use storage::*;
+pub use topics::*;
 
 /// Dialog base component that compone el `Popup` (freya-components) y ofrece
 /// una API simple para título, contenido y botones de acción.
@@ -87,39 +101,73 @@ impl Component for Dialog {
             }
         };
 
-        Popup::new()
-            .on_close_request({
-                let invoke_close = invoke_close.clone();
-                move |()| {
-                    invoke_close();
-                }
-            })
+        let title_bar = rect()
+            .spacing(8.)
+            .padding(8.)
+            .width(Size::Fill)
+            .horizontal()
+            .main_align(Alignment::SpaceBetween)
+            .cross_align(Alignment::Center)
+            .child(
+                rect().font_size(18.).child(
+                    label()
+                        .a11y_role(AccessibilityRole::TitleBar)
+                        .width(Size::fill())
+                        .text(self.title.read().to_string()),
+                ),
+            )
             .child(
-                rect()
-                    .spacing(8.)
-                    .padding(8.)
-                    .width(Size::Fill)
-                    .horizontal()
-                    .main_align(Alignment::SpaceBetween)
-                    .cross_align(Alignment::Center)
-                    .child(
-                        rect().font_size(18.).child(
-                            label()
-                                .a11y_role(AccessibilityRole::TitleBar)
-                                .width(Size::fill())
-                                .text(self.title.read().to_string()),
-                        ),
-                    )
-                    .child(
-                        Button::new()
-                            .compact()
-                            .on_press(move |_| {
-                                invoke_close();
-                            })
-                            .child(label().text("✕")),
-                    )
-                    .into_element(),
+                Button::new()
+                    .compact()
+                    .on_press({
+                        let invoke_close = invoke_close.clone();
+                        move |_| {
+                            invoke_close();
+                        }
+                    })
+                    .child(label().text("✕")),
             )
+            .into_element();
+
+        // Phones have no room around a popup, so there the dialog covers the whole window and
+        // the back key closes it
+        if is_mobile_layout(Platform::get().root_size.read().width) {
+            return rect()
+                .layer(Layer::Overlay)
+                .position(Position::new_absolute().top(0.).left(0.))
+                .width(Size::window_percent(100.))
+                .height(Size::window_percent(100.))
+                .padding(safe_area())
+                .content(Content::Flex)
+                .vertical()
+                .theme_background()
+                .child(title_bar)
+                .child(
+                    rect()
+                        .width(Size::Fill)
+                        .height(Size::flex(1.))
+                        .vertical()
+                        .spacing(8.)
+                        .padding(8.)
+                        .children(self.children.clone()),
+                )
+                .child(
+                    rect()
+                        .width(Size::Fill)
+                        .horizontal()
+                        .main_align(Alignment::End)
+                        .spacing(8.)
+                        .padding(8.)
+                        .children(self.actions.clone()),
+                )
+                .into_element();
+        }
+
+        Popup::new()
+            .on_close_request(move |()| {
+                invoke_close();
+            })
+            .child(title_bar)
             .child(
                 PopupContent::new().child(
                     rect()
@@ -130,6 +178,7 @@ impl Component for Dialog {
                 ),
             )
             .child(PopupButtons::new().children(self.actions.clone()))
+            .into_element()
     }
 
     fn render_key(&self) -> DiffKey {
@@ -0,0 +1,208 @@
+use std::thread;
+use std::time::Duration;
+
+use async_io::Timer;
+use freya::{prelude::*, radio::*};
+use futures::StreamExt;
+use kanal::{Receiver, Sender, unbounded};
+
+use crate::dialog::Dialog;
+use crate::modules::{MODULE_SOURCES, ModuleLibrary, ModuleSource};
+use crate::{AppChannel, AppState};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ModuleItem {
+    pub source: ModuleSource,
+    pub installed: bool,
+    pub installing: bool,
+    pub error: Option<String>,
+}
+
+pub fn manage_modules(mut show_dialog: State<bool>) -> impl IntoElement {
+    let mut modules = use_state(|| {
+        MODULE_SOURCES
+            .iter()
+            .map(|source| ModuleItem {
+                source: *source,
+                installed: source.is_installed(),
+                installing: false,
+                error: None,
+            })
+            .collect::<Vec<_>>()
+    });
+    let mut radio = use_radio::<AppState, AppChannel>(AppChannel::Modules);
+
+    let (tx, rx): (
+        Sender<(String, Result<(), String>)>,
+        Receiver<(String, Result<(), String>)>,
+    ) = use_hook(unbounded);
+
+    let (library_tx, library_rx): (Sender<ModuleLibrary>, Receiver<ModuleLibrary>) =
+        use_hook(unbounded);
+
+    // Reading the modules and indexing new commentaries is slow, so the library is loaded in
+    // the background and published by the loop below
+    let load_library = move || {
+        let library_tx = library_tx.clone();
+        let index_dir = radio.read().index_dir.clone();
+        thread::spawn(move || {
+            let _ = library_tx.send(ModuleLibrary::load(&index_dir));
+        });
+    };
+
+    use_hook(|| load_library());
+
+    use_hook(|| {
+        let rx = rx.clone();
+        let library_rx = library_rx.clone();
+
+        spawn(async move {
+            let mut interval = Timer::interval(Duration::from_millis(120));
+            loop {
+                interval.next().await;
+
+                let mut finished = false;
+                while let Ok(Some((module_id, result))) = rx.try_recv() {
+                    modules.with_mut(|mut modules| {
+                        if let Some(module) = modules.iter_mut().find(|m| m.source.id == module_id)
+                        {
+                            module.installing = false;
+                            module.installed = result.is_ok();
+                            module.error = result.err();
+                        }
+                    });
+                    finished = true;
+                }
+
+                if finished {
+                    load_library();
+                }
+
+                while let Ok(Some(library)) = library_rx.try_recv() {
+                    radio.write_channel(AppChannel::Modules).modules = library;
+                }
+            }
+        });
+    });
+
+    let install_action = move |source: ModuleSource| {
+        modules.with_mut(|mut modules| {
+            if let Some(module) = modules.iter_mut().find(|m| m.source == source) {
+                module.installing = true;
+                module.error = None;
+            }
+        });
+        thread::spawn({
+            let tx = tx.clone();
+            let index_dir = radio.read().index_dir.clone();
+            move || {
+                let result = source
+                    .install(&index_dir)
+                    .inspect_err(|e| tracing::error!("Error installing module {}: {e}", source.id))
+                    .map_err(|e| e.to_string());
+                let _ = tx.send((source.id.to_string(), result));
+            }
+        });
+    };
+
+    if !*show_dialog.read() {
+        return rect().into_element();
+    }
+
+    let modules = modules.read().clone();
+
+    Dialog::new("Manage Modules".to_string())
+        .width(Size::px(640.))
+        .on_close_request(move |()| show_dialog.set(false))
+        .child(
+            rect()
+                .vertical()
+                .spacing(10.)
+                .padding(8.)
+                .max_height(Size::window_percent(60.))
+                .child(
+                    label()
+                        .text("Download dictionaries and other study modules")
+                        .color(Color::from_hex("#cfcfcf").unwrap())
+                        .font_size(14.),
+                )
+                .child(
+                    ScrollView::new()
+                        .direction(Direction::Vertical)
+                        .spacing(10.)
+                        .children(modules.into_iter().map(|m| {
+                            rect()
+                                .key(m.source.id)
+                                .rounded()
+                                .width(Size::Fill)
+                                .padding(8.)
+                                .background(Color::from_hex("#2C2C2C").unwrap())
+                                .horizontal()
+                                .main_align(Alignment::SpaceBetween)
+                                .cross_align(Alignment::Center)
+                                .child(
+                                    rect()
+                                        .max_width(Size::px(400.))
+                                        .vertical()
+                                        .child(
+                                            label()
+                                                .text(m.source.name)
+                                                .max_lines(1)
+                                                .text_overflow(TextOverflow::Ellipsis)
+                                                .font_weight(FontWeight::BOLD)
+                                                .color(Color::WHITE),
+                                        )
+                                        .child(
+                                            label()
+                                                .text(match &m.error {
+                                                    Some(e) => e.clone(),
+                                                    None => format!(
+                                                        "{} · {}",
+                                                        m.source.kind.label(),
+                                                        m.source.language
+                                                    ),
+                                                })
+                                                .max_lines(1)
+                                                .text_overflow(TextOverflow::Ellipsis)
+                                                .color(if m.error.is_some() {
+                                                    Color::from_hex("#e74c3c").unwrap()
+                                                } else {
+                                                    Color::from_hex("#bdbdbd").unwrap()
+                                                })
+                                                .font_size(13.),
+                                        ),
+                                )
+                                .child(if m.installed {
+                                    label()
+                                        .text("Installed")
+                                        .color(Color::from_hex("#27ae60").unwrap())
+                                        .font_weight(FontWeight::BOLD)
+                                        .into_element()
+                                } else if m.installing {
+                                    label()
+                                        .text("Installing...")
+                                        .color(Color::from_hex("#f39c12").unwrap())
+                                        .into_element()
+                                } else {
+                                    Button::new()
+                                        .compact()
+                                        .on_press({
+                                            let mut install_action = install_action.clone();
+                                            move |_| install_action(m.source)
+                                        })
+                                        .child(label().text("Install"))
+                                        .into_element()
+                                })
+                                .into_element()
+                        })),
+                ),
+        )
+        .action(
+            Button::new()
+                .expanded()
+                .filled()
+                .on_press(move |_| show_dialog.set(false))
+                .child(label().text("Done")),
+        )
+        .into_element()
+}
@@ -0,0 +1,253 @@
+use std::path::PathBuf;
+
+use freya::{prelude::*, radio::*};
+
+use crate::dialog::Dialog;
+use crate::history::{NavigationHistory, navigate};
+use crate::plans::{PlanProgress, ReadingPlan};
+use crate::reference::{PassageRef, VerseRef};
+use crate::{AppChannel, AppState};
+
+fn small_button(text: impl Into<String>, active: bool) -> Button {
+    Button::new()
+        .compact()
+        .background(if active {
+            Color::from_hex("#3A3A3A").unwrap()
+        } else {
+            Color::from_hex("#2C2C2C").unwrap()
+        })
+        .hover_background(Color::from_hex("#353535").unwrap())
+        .child(label().color(Color::WHITE).font_size(13.).text(text.into()))
+}
+
+/// Reading plans with today's reading of the active plan, opened on startup when it is
+/// still pending
+pub fn reading_plans(
+    mut show_dialog: State<bool>,
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+) -> impl IntoElement {
+    let radio = use_radio::<AppState, AppChannel>(AppChannel::Catalog);
+    let mut plans = use_state(ReadingPlan::all);
+    let progress = use_state(PlanProgress::load);
+    let import_path = use_state(String::new);
+    let mut status = use_state(|| None::<Result<String, String>>);
+
+    if !*show_dialog.read() {
+        return rect().into_element();
+    }
+
+    let all_plans = plans.read().clone();
+    let current = progress.read().clone();
+    let bible_id = selected_verse
+        .read()
+        .as_ref()
+        .map(|verse| verse.bible_id.clone())
+        .or_else(|| radio.read().catalog.bibles.first().map(|b| b.id.clone()));
+
+    let update_progress = move |update: &dyn Fn(&mut PlanProgress)| {
+        let mut progress = progress;
+        let mut progress = progress.write();
+        update(&mut progress);
+        progress.save();
+    };
+
+    let open_passage = move |passage: PassageRef| {
+        let Some(bible_id) = bible_id.clone() else {
+            status.set(Some(Err("Install a Bible to read the plan".to_string())));
+            return;
+        };
+        // Plans are numbered as the KJV
+        let target = passage.first().in_bible(&radio.read().catalog, &bible_id);
+        navigate(selected_verse, history, target);
+        show_dialog.set(false);
+    };
+
+    let today = current
+        .active
+        .as_ref()
+        .and_then(|id| all_plans.iter().find(|p| p.id == *id))
+        .and_then(|plan| Some((plan, current.plans.get(&plan.id)?)))
+        .map(|(plan, state)| {
+            let day = state.current_day().min(plan.days.len());
+            let read = state.completed.contains(&day);
+            let behind = (1..day).filter(|d| !state.completed.contains(d)).count();
+            let plan_id = plan.id.clone();
+            rect()
+                .vertical()
+                .spacing(8.)
+                .padding(10.)
+                .rounded()
+                .width(Size::Fill)
+                .background(Color::from_hex("#2C2C2C").unwrap())
+                .child(
+                    label()
+                        .text(format!("Today's reading · {}", plan.name))
+                        .font_weight(FontWeight::BOLD)
+                        .color(Color::WHITE),
+                )
+                .child(
+                    label()
+                        .text(format!(
+                            "Day {day} of {} · {} days read{}",
+                            plan.days.len(),
+                            state.completed.len(),
+                            if behind > 0 {
+                                format!(" · {behind} days behind")
+                            } else {
+                                String::new()
+                            }
+                        ))
+                        .color(Color::from_hex("#bdbdbd").unwrap())
+                        .font_size(13.),
+                )
+                .child(
+                    rect()
+                        .horizontal()
+                        .content(Content::Wrap)
+                        .spacing(6.)
+                        .children(plan.days[day.saturating_sub(1)].iter().map(|passage| {
+                            let passage = passage.clone();
+                            let mut open_passage = open_passage.clone();
+                            small_button(passage.label(), false)
+                                .on_press(move |_| open_passage(passage.clone()))
+                                .into_element()
+                        })),
+                )
+                .child(
+                    small_button(if read { "✓ Read" } else { "Mark as read" }, read)
+                        .on_press(move |_| update_progress(&|p| p.toggle_day(&plan_id, day))),
+                )
+                .into_element()
+        });
+
+    let import_action = move |_| {
+        let path = PathBuf::from(import_path.read().trim());
+        let result = ReadingPlan::import(&path)
+            .inspect_err(|e| tracing::error!("Error importing plan {path:?}: {e}"))
+            .map(|plan| format!("Imported {}", plan.name))
+            .map_err(|e| e.to_string());
+        if result.is_ok() {
+            plans.set(ReadingPlan::all());
+        }
+        status.set(Some(result));
+    };
+
+    Dialog::new("Reading Plans".to_string())
+        .width(Size::px(640.))
+        .on_close_request(move |()| show_dialog.set(false))
+        .child(
+            rect()
+                .vertical()
+                .spacing(10.)
+                .padding(8.)
+                .max_height(Size::window_percent(70.))
+                .maybe_child(today)
+                .child(
+                    ScrollView::new()
+                        .direction(Direction::Vertical)
+                        .height(Size::px(260.))
+                        .spacing(8.)
+                        .children(all_plans.iter().map(|plan| {
+                            let state = current.plans.get(&plan.id);
+                            let is_active = current.active.as_ref() == Some(&plan.id);
+                            let plan_id = plan.id.clone();
+                            rect()
+                                .key(&plan.id)
+                                .rounded()
+                                .width(Size::Fill)
+                                .padding(8.)
+                                .background(Color::from_hex("#2C2C2C").unwrap())
+                                .horizontal()
+                                .main_align(Alignment::SpaceBetween)
+                                .cross_align(Alignment::Center)
+                                .child(
+                                    rect()
+                                        .max_width(Size::px(420.))
+                                        .vertical()
+                                        .child(
+                                            label()
+                                                .text(plan.name.clone())
+                                                .font_weight(FontWeight::BOLD)
+                                                .color(Color::WHITE),
+                                        )
+                                        .child(
+                                            label()
+                                                .text(match state {
+                                                    Some(state) => format!(
+                                                        "{} of {} days read",
+                                                        state.completed.len(),
+                                                        plan.days.len()
+                                                    ),
+                                                    None if plan.description.is_empty() => {
+                                                        format!("{} days", plan.days.len())
+                                                    }
+                                                    None => format!(
+                                                        "{} · {} days",
+                                                        plan.description,
+                                                        plan.days.len()
+                                                    ),
+                                                })
+                                                .max_lines(2)
+                                                .color(Color::from_hex("#bdbdbd").unwrap())
+                                                .font_size(13.),
+                                        ),
+                                )
+                                .child(
+                                    rect()
+                                        .horizontal()
+                                        .spacing(6.)
+                                        .maybe_child(state.is_some().then(|| {
+                                            let plan_id = plan_id.clone();
+                                            small_button("Restart", false)
+                                                .on_press(move |_| {
+                                                    update_progress(&|p| p.restart(&plan_id))
+                                                })
+                                                .into_element()
+                                        }))
+                                        .child(
+                                            small_button(
+                                                if is_active { "Active" } else { "Follow" },
+                                                is_active,
+                                            )
+                                            .on_press(
+                                                move |_| update_progress(&|p| p.start(&plan_id)),
+                                            ),
+                                        ),
+                                )
+                                .into_element()
+                        })),
+                )
+                .child(
+                    rect()
+                        .horizontal()
+                        .spacing(6.)
+                        .cross_align(Alignment::Center)
+                        .child(
+                            Input::new(import_path)
+                                .width(Size::px(480.))
+                                .placeholder("Path of a JSON or CSV plan to import"),
+                        )
+                        .child(small_button("Import", false).on_press(import_action)),
+                )
+                .maybe_child(status.read().clone().map(|status| {
+                    let (text, color) = match status {
+                        Ok(text) => (text, "#27ae60"),
+                        Err(e) => (e, "#e74c3c"),
+                    };
+                    label()
+                        .text(text)
+                        .color(Color::from_hex(color).unwrap())
+                        .font_size(13.)
+                        .into_element()
+                })),
+        )
+        .action(
+            Button::new()
+                .expanded()
+                .filled()
+                .on_press(move |_| show_dialog.set(false))
+                .child(label().text("Done")),
+        )
+        .into_element()
+}
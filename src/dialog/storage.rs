@@ -0,0 +1,182 @@
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use async_io::Timer;
+use freya::{prelude::*, radio::*};
+use futures::StreamExt;
+use kanal::{Receiver, Sender, unbounded};
+
+use crate::dialog::Dialog;
+use crate::modules::ModuleLibrary;
+use crate::storage::{StorageLocation, external_data_dir, index_dir, move_indexes};
+use crate::{AppChannel, AppState};
+
+/// Result of moving the indexes, with the modules opened again from the folder in use after it
+type MoveResult = (StorageLocation, PathBuf, Result<(), String>, ModuleLibrary);
+
+/// Chooses where the search indexes are kept, moving the installed ones to the new location
+pub fn storage_location(mut show_dialog: State<bool>) -> impl IntoElement {
+    let mut settings = use_radio::<AppState, AppChannel>(AppChannel::Settings);
+    let mut moving = use_state(|| false);
+    let mut error = use_state(|| None::<String>);
+
+    let (tx, rx): (Sender<MoveResult>, Receiver<MoveResult>) = use_hook(unbounded);
+
+    use_hook(|| {
+        let rx = rx.clone();
+
+        spawn(async move {
+            let mut interval = Timer::interval(Duration::from_millis(120));
+            loop {
+                interval.next().await;
+
+                while let Ok(Some((location, dir, result, library))) = rx.try_recv() {
+                    moving.set(false);
+                    match result {
+                        Ok(()) => {
+                            // The app opens the verse index again once the folder changes
+                            let mut state = settings.write();
+                            state.settings.index_storage = location;
+                            state.settings.save();
+                            state.index_dir = dir;
+                            error.set(None);
+                        }
+                        Err(e) => error.set(Some(e)),
+                    }
+                    settings.write_channel(AppChannel::Modules).modules = library;
+                }
+            }
+        });
+    });
+
+    if !*show_dialog.read() {
+        return rect().into_element();
+    }
+
+    let current = settings.read().settings.index_storage;
+    let has_external = external_data_dir().is_some();
+    let move_to = move |location: StorageLocation| {
+        moving.set(true);
+        error.set(None);
+        // The commentary index is closed while its files move and opened again afterwards
+        settings
+            .write_channel(AppChannel::Modules)
+            .modules
+            .commentary_index = None;
+        thread::spawn({
+            let tx = tx.clone();
+            move || {
+                let result = move_indexes(current, location)
+                    .inspect_err(|e| tracing::error!("Failed to move the indexes: {e}"))
+                    .map_err(|e| e.to_string());
+                let dir = index_dir(if result.is_ok() { location } else { current });
+                let library = ModuleLibrary::load(&dir);
+                let _ = tx.send((location, dir, result, library));
+            }
+        });
+    };
+
+    Dialog::new("Storage".to_string())
+        .width(Size::px(640.))
+        .on_close_request(move |()| {
+            if !moving() {
+                show_dialog.set(false);
+            }
+        })
+        .child(
+            rect()
+                .vertical()
+                .spacing(10.)
+                .padding(8.)
+                .child(
+                    label()
+                        .text("Installed Bibles and commentaries are indexed to be searched. The indexes can take a lot of space, so they can be kept in the external storage instead.")
+                        .color(Color::from_hex("#cfcfcf").unwrap())
+                        .font_size(14.),
+                )
+                .children(
+                    [StorageLocation::Internal, StorageLocation::External]
+                        .into_iter()
+                        .map(|location| {
+                            let path = location.index_root();
+                            rect()
+                                .key(location.label())
+                                .rounded()
+                                .width(Size::Fill)
+                                .padding(8.)
+                                .background(Color::from_hex("#2C2C2C").unwrap())
+                                .horizontal()
+                                .main_align(Alignment::SpaceBetween)
+                                .cross_align(Alignment::Center)
+                                .child(
+                                    rect()
+                                        .max_width(Size::px(400.))
+                                        .vertical()
+                                        .child(
+                                            label()
+                                                .text(location.label())
+                                                .font_weight(FontWeight::BOLD)
+                                                .color(Color::WHITE),
+                                        )
+                                        .child(
+                                            label()
+                                                .text(path.as_ref().map_or_else(
+                                                    || "Not available on this device".to_string(),
+                                                    |path| path.display().to_string(),
+                                                ))
+                                                .max_lines(1)
+                                                .text_overflow(TextOverflow::Ellipsis)
+                                                .color(Color::from_hex("#bdbdbd").unwrap())
+                                                .font_size(13.),
+                                        ),
+                                )
+                                .child(if location == current {
+                                    label()
+                                        .text(if moving() { "Moving..." } else { "In use" })
+                                        .color(Color::from_hex("#27ae60").unwrap())
+                                        .font_weight(FontWeight::BOLD)
+                                        .into_element()
+                                } else if path.is_none() || moving() {
+                                    rect().into_element()
+                                } else {
+                                    Button::new()
+                                        .compact()
+                                        .on_press({
+                                            let mut move_to = move_to.clone();
+                                            move |_| move_to(location)
+                                        })
+                                        .child(label().text("Move here"))
+                                        .into_element()
+                                })
+                                .into_element()
+                        }),
+                )
+                .maybe_child((!has_external).then(|| {
+                    label()
+                        .text("This device has no external storage for the app")
+                        .color(Color::from_hex("#888888").unwrap())
+                        .font_size(13.)
+                        .into_element()
+                }))
+                .maybe_child(error.read().clone().map(|e| {
+                    label()
+                        .text(e)
+                        .color(Color::from_hex("#e74c3c").unwrap())
+                        .font_size(13.)
+                        .into_element()
+                })),
+        )
+        .action(
+            Button::new()
+                .expanded()
+                .filled()
+                .on_press(move |_| {
+                    if !moving() {
+                        show_dialog.set(false);
+                    }
+                })
+                .child(label().text("Done")),
+        )
+        .into_element()
+}
@@ -0,0 +1,32 @@
+use freya::prelude::*;
+
+use crate::components::TopicsPanel;
+use crate::dialog::Dialog;
+use crate::history::NavigationHistory;
+use crate::reference::VerseRef;
+
+/// Browses the installed topical indexes, closing once a passage is opened
+pub fn topic_browser(
+    mut show_dialog: State<bool>,
+    selected_verse: State<Option<VerseRef>>,
+    history: State<NavigationHistory<VerseRef>>,
+) -> impl IntoElement {
+    if !*show_dialog.read() {
+        return rect().into_element();
+    }
+
+    Dialog::new("Topics".to_string())
+        .width(Size::px(640.))
+        .on_close_request(move |()| show_dialog.set(false))
+        .child(rect().padding(8.).height(Size::window_percent(60.)).child(
+            TopicsPanel::new(selected_verse, history).on_navigate(move |()| show_dialog.set(false)),
+        ))
+        .action(
+            Button::new()
+                .expanded()
+                .filled()
+                .on_press(move |_| show_dialog.set(false))
+                .child(label().text("Done")),
+        )
+        .into_element()
+}
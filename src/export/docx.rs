@@ -0,0 +1,108 @@
+use std::io::{Cursor, Write};
+
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+use super::{ExportDocument, ExportError, ExportOptions, escape_xml};
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="xml" ContentType="application/xml"/>
+  <Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+</Types>
+"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+</Relationships>
+"#;
+
+/// Run of text, sizes in half points as Word expects
+fn run(text: &str, half_points: u32, bold: bool, superscript: bool, color: Option<&str>) -> String {
+    let mut properties = format!("<w:sz w:val=\"{half_points}\"/>");
+    if bold {
+        properties.push_str("<w:b/>");
+    }
+    if superscript {
+        properties.push_str("<w:vertAlign w:val=\"superscript\"/>");
+    }
+    if let Some(color) = color {
+        properties.push_str(&format!("<w:color w:val=\"{color}\"/>"));
+    }
+    format!(
+        "<w:r><w:rPr>{properties}</w:rPr><w:t xml:space=\"preserve\">{}</w:t></w:r>",
+        escape_xml(text)
+    )
+}
+
+fn paragraph(runs: &[String]) -> String {
+    format!("<w:p>{}</w:p>", runs.join(""))
+}
+
+pub(super) fn render(
+    document: &ExportDocument,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let size = (options.font_size * 2.).round() as u32;
+    let mut body = paragraph(&[run(&document.title, size * 3 / 2, true, false, None)]);
+    for section in &document.sections {
+        body.push_str(&paragraph(&[
+            run(&section.reference, size * 5 / 4, true, false, None),
+            run(
+                &format!(" {}", section.translation),
+                size,
+                false,
+                false,
+                Some("666666"),
+            ),
+        ]));
+        for verse in &section.verses {
+            body.push_str(&paragraph(&[
+                run(&verse.number.to_string(), size, true, true, Some("888888")),
+                run(&format!(" {}", verse.text), size, false, false, None),
+            ]));
+            if options.include_notes && !verse.notes.is_empty() {
+                body.push_str(&paragraph(&[run(
+                    &format!("✎ {}", verse.notes.join(" · ")),
+                    size * 9 / 10,
+                    false,
+                    false,
+                    Some("555555"),
+                )]));
+            }
+            if options.include_cross_references && !verse.cross_references.is_empty() {
+                body.push_str(&paragraph(&[run(
+                    &format!("→ {}", verse.cross_references.join("; ")),
+                    size * 9 / 10,
+                    false,
+                    false,
+                    Some("555555"),
+                )]));
+            }
+        }
+    }
+    let columns = options.columns.max(1);
+    let document_xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:body>
+{body}
+<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1134" w:right="1134" w:bottom="1134" w:left="1134"/><w:cols w:num="{columns}" w:space="567"/></w:sectPr>
+</w:body>
+</w:document>
+"#
+    );
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, data) in [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", RELATIONSHIPS.to_string()),
+        ("word/document.xml", document_xml),
+    ] {
+        zip.start_file(name, SimpleFileOptions::default())?;
+        zip.write_all(data.as_bytes())?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
@@ -0,0 +1,108 @@
+use std::io::{Cursor, Write};
+
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+use super::{ExportDocument, ExportError, ExportOptions, escape_xml, html};
+
+const CONTAINER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+pub(super) fn render(
+    document: &ExportDocument,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let title = escape_xml(&document.title);
+    let identifier = format!(
+        "urn:biblion:{}",
+        document
+            .title
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase()
+    );
+    let content = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="id">{identifier}</dc:identifier>
+    <dc:title>{title}</dc:title>
+    <dc:language>und</dc:language>
+    <meta property="dcterms:modified">2000-01-01T00:00:00Z</meta>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="style" href="style.css" media-type="text/css"/>
+    <item id="passages" href="passages.xhtml" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine>
+    <itemref idref="passages"/>
+  </spine>
+</package>
+"#
+    );
+    let nav_items = document
+        .sections
+        .iter()
+        .enumerate()
+        .map(|(i, section)| {
+            format!(
+                "<li><a href=\"passages.xhtml#s{i}\">{} ({})</a></li>",
+                escape_xml(&section.reference),
+                escape_xml(&section.translation)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let nav = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops">
+<head><title>{title}</title></head>
+<body>
+<nav epub:type="toc"><ol>
+{nav_items}
+</ol></nav>
+</body>
+</html>
+"#
+    );
+    // Sections get anchors for the table of contents
+    let mut body = html::body(document, options);
+    for i in 0..document.sections.len() {
+        body = body.replacen("<section>", &format!("<section id=\"s{i}\">"), 1);
+    }
+    let passages = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml">
+<head><title>{title}</title><link rel="stylesheet" href="style.css"/></head>
+<body>
+{body}</body>
+</html>
+"#
+    );
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    // The mimetype has to be the first entry and stay uncompressed
+    zip.start_file(
+        "mimetype",
+        SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored),
+    )?;
+    zip.write_all(b"application/epub+zip")?;
+    for (name, data) in [
+        ("META-INF/container.xml", CONTAINER.to_string()),
+        ("OEBPS/content.opf", content),
+        ("OEBPS/nav.xhtml", nav),
+        ("OEBPS/style.css", html::style(options)),
+        ("OEBPS/passages.xhtml", passages),
+    ] {
+        zip.start_file(name, SimpleFileOptions::default())?;
+        zip.write_all(data.as_bytes())?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
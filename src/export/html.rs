@@ -0,0 +1,58 @@
+use super::{ExportDocument, ExportOptions, escape_xml};
+
+/// Style sheet shared by the HTML and EPUB exports
+pub(super) fn style(options: &ExportOptions) -> String {
+    format!(
+        "body {{ font-family: serif; font-size: {size}pt; line-height: 1.5; margin: 2em; }}\n\
+         h1 {{ font-size: 1.6em; }}\n\
+         h2 {{ font-size: 1.2em; margin-bottom: 0.2em; }}\n\
+         .translation {{ color: #666; font-size: 0.8em; font-weight: normal; }}\n\
+         .passage {{ column-count: {columns}; column-gap: 2em; }}\n\
+         sup {{ color: #888; font-weight: bold; }}\n\
+         .notes, .refs {{ color: #555; font-size: 0.85em; margin: 0.2em 0 0.6em 1em; }}\n",
+        size = options.font_size,
+        columns = options.columns.max(1),
+    )
+}
+
+/// Sections of the document as XHTML compatible markup
+pub(super) fn body(document: &ExportDocument, options: &ExportOptions) -> String {
+    let mut body = format!("<h1>{}</h1>\n", escape_xml(&document.title));
+    for section in &document.sections {
+        body.push_str(&format!(
+            "<section>\n<h2>{} <span class=\"translation\">{}</span></h2>\n<div class=\"passage\">\n",
+            escape_xml(&section.reference),
+            escape_xml(&section.translation)
+        ));
+        for verse in &section.verses {
+            body.push_str(&format!(
+                "<p><sup>{}</sup> {}</p>\n",
+                verse.number,
+                escape_xml(&verse.text)
+            ));
+            if options.include_notes && !verse.notes.is_empty() {
+                body.push_str(&format!(
+                    "<p class=\"notes\">✎ {}</p>\n",
+                    escape_xml(&verse.notes.join(" · "))
+                ));
+            }
+            if options.include_cross_references && !verse.cross_references.is_empty() {
+                body.push_str(&format!(
+                    "<p class=\"refs\">→ {}</p>\n",
+                    escape_xml(&verse.cross_references.join("; "))
+                ));
+            }
+        }
+        body.push_str("</div>\n</section>\n");
+    }
+    body
+}
+
+pub(super) fn render(document: &ExportDocument, options: &ExportOptions) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_xml(&document.title),
+        style(options),
+        body(document, options)
+    )
+}
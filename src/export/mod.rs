@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use setup_core::{DbSink, TantivySink};
+
+use crate::annotations::Annotations;
+use crate::catalog::Catalog;
+use crate::reference::{END_OF_CHAPTER, PassageRef, STANDARD_BIBLE, VerseRef};
+use crate::storage::external_data_dir;
+use crate::utils::data_dir;
+
+mod docx;
+mod epub;
+mod html;
+mod pdf;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ExportFormat {
+    Pdf,
+    Docx,
+    Epub,
+    Html,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::Pdf,
+        ExportFormat::Docx,
+        ExportFormat::Epub,
+        ExportFormat::Html,
+    ];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Docx => "docx",
+            ExportFormat::Epub => "epub",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "PDF",
+            ExportFormat::Docx => "DOCX",
+            ExportFormat::Epub => "EPUB",
+            ExportFormat::Html => "HTML",
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct ExportOptions {
+    /// Text columns of every page, 1 or 2
+    pub columns: u8,
+    /// Size of the text in points
+    pub font_size: f32,
+    pub include_notes: bool,
+    pub include_cross_references: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            columns: 1,
+            font_size: 11.,
+            include_notes: false,
+            include_cross_references: false,
+        }
+    }
+}
+
+/// Parses passages like `John 3:16-18; Rom 8` separated by `;`
+pub fn parse_passages(input: &str) -> Result<Vec<PassageRef>, ExportError> {
+    PassageRef::parse_list(STANDARD_BIBLE, input).map_err(ExportError::InvalidPassage)
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct ExportVerse {
+    pub number: u32,
+    pub text: String,
+    pub notes: Vec<String>,
+    pub cross_references: Vec<String>,
+}
+
+/// A passage in one translation
+#[derive(PartialEq, Clone, Debug)]
+pub struct ExportSection {
+    pub reference: String,
+    pub translation: String,
+    pub verses: Vec<ExportVerse>,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct ExportDocument {
+    pub title: String,
+    pub sections: Vec<ExportSection>,
+}
+
+impl ExportDocument {
+    /// Reads the passages of every translation from the local index. Passages are numbered as
+    /// the KJV and mapped to the versification of every translation.
+    pub fn collect(
+        database: &TantivySink,
+        catalog: &Catalog,
+        title: impl Into<String>,
+        passages: &[PassageRef],
+        translations: &[String],
+        annotations: &Annotations,
+    ) -> Result<Self, ExportError> {
+        if passages.is_empty() || translations.is_empty() {
+            return Err(ExportError::Empty);
+        }
+        let mut sections = Vec::new();
+        for passage in passages {
+            for bible_id in translations {
+                let versification = catalog.versification(bible_id);
+                // Both ends are mapped, so the passage may span other chapters in the translation
+                let mapped = passage
+                    .in_bible(catalog, bible_id)
+                    .ok_or_else(|| ExportError::InvalidPassage(passage.label()))?;
+                for chapter_number in mapped.chapters() {
+                    let Some(range) = mapped.verses_in(chapter_number) else {
+                        continue;
+                    };
+                    let chapter = database
+                        .get_crossreferences(
+                            bible_id,
+                            &passage.book_id,
+                            chapter_number as _,
+                            *range.start() as _,
+                        )
+                        .map_err(|e| ExportError::Database(e.to_string()))?
+                        .ok_or_else(|| ExportError::MissingPassage(passage.book_id.clone()))?;
+                    // Cross references are numbered as the KJV
+                    let mut standard_chapters = HashMap::new();
+                    let mut cross_references = |number: u32| {
+                        let (standard_chapter, standard_verse) =
+                            versification.to_standard(&passage.book_id, chapter_number, number);
+                        let standard = if standard_chapter == chapter_number {
+                            Some(&chapter)
+                        } else {
+                            standard_chapters
+                                .entry(standard_chapter)
+                                .or_insert_with(|| {
+                                    database
+                                        .get_crossreferences(
+                                            bible_id,
+                                            &passage.book_id,
+                                            standard_chapter as _,
+                                            standard_verse as _,
+                                        )
+                                        .ok()
+                                        .flatten()
+                                })
+                                .as_ref()
+                        };
+                        standard
+                            .into_iter()
+                            .flat_map(|c| c.verses.iter())
+                            .filter(|v| v.verse_number as u32 == standard_verse)
+                            .flat_map(|v| v.cross_references.iter())
+                            .map(|r| {
+                                let (chapter, verse) = catalog
+                                    .book_by_name(bible_id, &r.book_name)
+                                    .map(|book| {
+                                        versification.from_standard(
+                                            &book.id,
+                                            r.chapter as u32,
+                                            r.verse as u32,
+                                        )
+                                    })
+                                    .unwrap_or((r.chapter as u32, r.verse as u32));
+                                format!("{} {chapter}:{verse}", r.book_name)
+                            })
+                            .collect::<Vec<_>>()
+                    };
+                    let verses = chapter
+                        .verses
+                        .iter()
+                        .filter(|v| range.contains(&(v.verse_number as u32)))
+                        .map(|v| {
+                            let number = v.verse_number as u32;
+                            ExportVerse {
+                                number,
+                                text: v.text.trim().to_string(),
+                                notes: annotations
+                                    .notes_at(
+                                        catalog,
+                                        &VerseRef::new(
+                                            bible_id.as_str(),
+                                            passage.book_id.as_str(),
+                                            chapter_number,
+                                            number,
+                                        ),
+                                    )
+                                    .into_iter()
+                                    .map(|n| n.text.clone())
+                                    .collect(),
+                                cross_references: cross_references(number),
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    // The verses read, since a chapter the passage goes on to the end of has no
+                    // known last verse
+                    let whole = range == (1..=END_OF_CHAPTER);
+                    let reference = match (whole, verses.first(), verses.last()) {
+                        (false, Some(first), Some(last)) if first.number == last.number => {
+                            format!("{} {chapter_number}:{}", chapter.book_name, first.number)
+                        }
+                        (false, Some(first), Some(last)) => format!(
+                            "{} {chapter_number}:{}-{}",
+                            chapter.book_name, first.number, last.number
+                        ),
+                        _ => format!("{} {chapter_number}", chapter.book_name),
+                    };
+                    sections.push(ExportSection {
+                        reference,
+                        translation: bible_id.to_uppercase(),
+                        verses,
+                    });
+                }
+            }
+        }
+        Ok(Self {
+            title: title.into(),
+            sections,
+        })
+    }
+
+    /// Writes the document in `format` to `path`
+    pub fn export(
+        &self,
+        format: ExportFormat,
+        options: &ExportOptions,
+        path: &Path,
+    ) -> Result<(), ExportError> {
+        let bytes = match format {
+            ExportFormat::Html => html::render(self, options).into_bytes(),
+            ExportFormat::Epub => epub::render(self, options)?,
+            ExportFormat::Docx => docx::render(self, options)?,
+            ExportFormat::Pdf => pdf::render(self, options)?,
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+}
+
+/// File in the exports folder, named after the title. On Android the folder is in the external
+/// storage of the app, which file managers can reach, unlike the private data dir.
+pub fn export_path(title: &str, format: ExportFormat) -> PathBuf {
+    let name = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    let name = if name.trim_matches('_').is_empty() {
+        "passages".to_string()
+    } else {
+        name
+    };
+    let file = format!("{name}.{}", format.extension());
+    match external_data_dir() {
+        Some(dir) => dir.join("exports").join(file),
+        None => data_dir(&["exports", file.as_str()]),
+    }
+}
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(std::io::Error),
+    Zip(String),
+    Database(String),
+    InvalidPassage(String),
+    MissingPassage(String),
+    /// Character the standard PDF fonts have no glyph for
+    UnsupportedCharacter(char),
+    Empty,
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Io(e) => write!(f, "io error: {e}"),
+            ExportError::Zip(e) => write!(f, "archive error: {e}"),
+            ExportError::Database(e) => write!(f, "database error: {e}"),
+            ExportError::InvalidPassage(p) => write!(f, "invalid passage: {p}"),
+            ExportError::MissingPassage(p) => write!(f, "passage not installed: {p}"),
+            ExportError::UnsupportedCharacter(c) => write!(
+                f,
+                "the PDF fonts cannot show \"{c}\" (U+{:04X}), export as DOCX, EPUB or HTML instead",
+                *c as u32
+            ),
+            ExportError::Empty => write!(f, "nothing to export"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<std::io::Error> for ExportError {
+    fn from(e: std::io::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+impl From<zip::result::ZipError> for ExportError {
+    fn from(e: zip::result::ZipError) -> Self {
+        ExportError::Zip(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verse_text_is_escaped() {
+        let document = ExportDocument {
+            title: "Fish & loaves".to_string(),
+            sections: vec![ExportSection {
+                reference: "John 6:9".to_string(),
+                translation: "KJV".to_string(),
+                verses: vec![ExportVerse {
+                    number: 9,
+                    text: "The lad's <five> \"loaves\"".to_string(),
+                    notes: Vec::new(),
+                    cross_references: Vec::new(),
+                }],
+            }],
+        };
+        let body = html::body(&document, &ExportOptions::default());
+        assert!(body.contains("<h1>Fish &amp; loaves</h1>"));
+        assert!(body.contains("The lad&apos;s &lt;five&gt; &quot;loaves&quot;"));
+        assert!(!body.contains('\''));
+    }
+}
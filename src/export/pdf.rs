@@ -0,0 +1,343 @@
+//! Minimal PDF writer using the standard Helvetica fonts, so exports need no font files.
+//! Those fonts only cover the Latin scripts, so documents with other characters are refused
+//! instead of being written with missing letters.
+
+use super::{ExportDocument, ExportError, ExportOptions};
+
+const PAGE_WIDTH: f32 = 595.;
+const PAGE_HEIGHT: f32 = 842.;
+const MARGIN: f32 = 56.;
+const COLUMN_GAP: f32 = 20.;
+
+/// Widths of the printable ASCII characters in Helvetica, in thousandths of the font size
+#[rustfmt::skip]
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+/// Maps a character to WinAnsiEncoding, the encoding of the standard fonts
+fn win_ansi(c: char) -> Option<u8> {
+    Some(match c {
+        ' '..='~' => c as u8,
+        '\u{A0}'..='\u{FF}' => c as u32 as u8,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '…' => 0x85,
+        _ => return None,
+    })
+}
+
+/// First character of the document the standard fonts cannot show
+fn unsupported_char(document: &ExportDocument, options: &ExportOptions) -> Option<char> {
+    let texts = document.sections.iter().flat_map(|section| {
+        [&section.reference, &section.translation]
+            .into_iter()
+            .chain(section.verses.iter().flat_map(|verse| {
+                std::iter::once(&verse.text)
+                    .chain(verse.notes.iter().filter(|_| options.include_notes))
+                    .chain(
+                        verse
+                            .cross_references
+                            .iter()
+                            .filter(|_| options.include_cross_references),
+                    )
+            }))
+    });
+    std::iter::once(&document.title)
+        .chain(texts)
+        .flat_map(|text| text.chars())
+        .find(|c| !c.is_whitespace() && win_ansi(*c).is_none())
+}
+
+fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    let units = text
+        .chars()
+        .map(|c| match c {
+            ' '..='~' => HELVETICA_WIDTHS[c as usize - 32] as f32,
+            _ => 556.,
+        })
+        .sum::<f32>();
+    let bold = if font == Font::Bold { 1.05 } else { 1. };
+    units * size * bold / 1000.
+}
+
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut bytes = vec![b'('];
+    // Every character was checked by `unsupported_char` before the layout
+    for byte in text.chars().filter_map(win_ansi) {
+        if matches!(byte, b'(' | b')' | b'\\') {
+            bytes.push(b'\\');
+        }
+        bytes.push(byte);
+    }
+    bytes.push(b')');
+    bytes
+}
+
+struct Run {
+    font: Font,
+    size: f32,
+    /// Gray level of the text, 0 is black
+    gray: f32,
+    text: String,
+}
+
+impl Run {
+    fn new(font: Font, size: f32, gray: f32, text: impl Into<String>) -> Self {
+        Self {
+            font,
+            size,
+            gray,
+            text: text.into(),
+        }
+    }
+}
+
+/// Splits a word wider than the column into pieces that fit in it
+fn split_to_width(word: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    if text_width(word, font, size) <= width {
+        return vec![word.to_string()];
+    }
+    let mut pieces = vec![String::new()];
+    for c in word.chars() {
+        let piece = pieces.last_mut().expect("there is always a piece");
+        piece.push(c);
+        if piece.chars().count() > 1 && text_width(piece, font, size) > width {
+            piece.pop();
+            pieces.push(c.to_string());
+        }
+    }
+    pieces
+}
+
+/// Flows lines through the columns of every page
+struct Layout {
+    columns: u8,
+    pages: Vec<Vec<u8>>,
+    column: u8,
+    y: f32,
+}
+
+impl Layout {
+    fn new(columns: u8) -> Self {
+        Self {
+            columns: columns.max(1),
+            pages: vec![Vec::new()],
+            column: 0,
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn column_width(&self) -> f32 {
+        let columns = self.columns as f32;
+        (PAGE_WIDTH - 2. * MARGIN - COLUMN_GAP * (columns - 1.)) / columns
+    }
+
+    fn column_x(&self) -> f32 {
+        MARGIN + self.column as f32 * (self.column_width() + COLUMN_GAP)
+    }
+
+    /// Moves down `height`, jumping to the next column or page when it does not fit
+    fn advance(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            self.column += 1;
+            if self.column >= self.columns {
+                self.column = 0;
+                self.pages.push(Vec::new());
+            }
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+        self.y -= height;
+    }
+
+    fn draw_line(&mut self, words: &[(Font, f32, f32, String)]) {
+        let height = words.iter().map(|w| w.1).fold(0., f32::max) * 1.35;
+        self.advance(height);
+        let mut x = self.column_x();
+        let page = self.pages.last_mut().expect("there is always a page");
+        for (font, size, gray, word) in words {
+            page.extend_from_slice(
+                format!(
+                    "BT /{} {size} Tf {gray} g {x:.2} {:.2} Td ",
+                    font.resource(),
+                    self.y
+                )
+                .as_bytes(),
+            );
+            page.extend_from_slice(&pdf_string(word));
+            page.extend_from_slice(b" Tj ET\n");
+            x += text_width(word, *font, *size) + text_width(" ", *font, *size);
+        }
+    }
+
+    /// Lays out the runs as a paragraph wrapped to the column width
+    fn paragraph(&mut self, runs: &[Run], space_before: f32) {
+        self.advance(space_before);
+        let width = self.column_width();
+        let mut line = Vec::new();
+        let mut line_width = 0.;
+        for run in runs {
+            for word in run
+                .text
+                .split_whitespace()
+                .flat_map(|word| split_to_width(word, run.font, run.size, width))
+            {
+                let word_width = text_width(&word, run.font, run.size);
+                let space = text_width(" ", run.font, run.size);
+                if !line.is_empty() && line_width + word_width > width {
+                    self.draw_line(&line);
+                    line.clear();
+                    line_width = 0.;
+                }
+                line.push((run.font, run.size, run.gray, word));
+                line_width += word_width + space;
+            }
+        }
+        if !line.is_empty() {
+            self.draw_line(&line);
+        }
+    }
+}
+
+pub(super) fn render(
+    document: &ExportDocument,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    if let Some(c) = unsupported_char(document, options) {
+        return Err(ExportError::UnsupportedCharacter(c));
+    }
+    let size = options.font_size;
+    let mut layout = Layout::new(options.columns);
+    layout.paragraph(&[Run::new(Font::Bold, size * 1.6, 0., &document.title)], 0.);
+    for section in &document.sections {
+        layout.paragraph(
+            &[
+                Run::new(Font::Bold, size * 1.25, 0., &section.reference),
+                Run::new(Font::Regular, size * 0.9, 0.4, &section.translation),
+            ],
+            size,
+        );
+        for verse in &section.verses {
+            layout.paragraph(
+                &[
+                    Run::new(Font::Bold, size * 0.75, 0.5, verse.number.to_string()),
+                    Run::new(Font::Regular, size, 0., &verse.text),
+                ],
+                size * 0.2,
+            );
+            if options.include_notes && !verse.notes.is_empty() {
+                layout.paragraph(
+                    &[Run::new(
+                        Font::Regular,
+                        size * 0.85,
+                        0.35,
+                        format!("Note: {}", verse.notes.join(" · ")),
+                    )],
+                    0.,
+                );
+            }
+            if options.include_cross_references && !verse.cross_references.is_empty() {
+                layout.paragraph(
+                    &[Run::new(
+                        Font::Regular,
+                        size * 0.85,
+                        0.35,
+                        format!("See: {}", verse.cross_references.join("; ")),
+                    )],
+                    0.,
+                );
+            }
+        }
+    }
+    Ok(write_document(&layout.pages))
+}
+
+/// Assembles the catalog, fonts, pages and cross reference table
+fn write_document(pages: &[Vec<u8>]) -> Vec<u8> {
+    let mut objects = Vec::<Vec<u8>>::new();
+    let page_ids = (0..pages.len()).map(|i| 5 + i * 2).collect::<Vec<_>>();
+    objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+    objects.push(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{id} 0 R"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+    );
+    for font in ["Helvetica", "Helvetica-Bold"] {
+        objects.push(
+            format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{font} /Encoding /WinAnsiEncoding >>"
+            )
+            .into_bytes(),
+        );
+    }
+    for (page, id) in pages.iter().zip(&page_ids) {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                id + 1
+            )
+            .into_bytes(),
+        );
+        let mut stream = format!("<< /Length {} >>\nstream\n", page.len()).into_bytes();
+        stream.extend_from_slice(page);
+        stream.extend_from_slice(b"\nendstream");
+        objects.push(stream);
+    }
+
+    let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .as_bytes(),
+    );
+    pdf
+}
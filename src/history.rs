@@ -0,0 +1,111 @@
+use freya::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Maximum amount of locations kept in each direction
+const MAX_HISTORY: usize = 100;
+
+/// Back and forward stacks of visited locations
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct NavigationHistory<T> {
+    back: Vec<T>,
+    forward: Vec<T>,
+}
+
+impl<T> Default for NavigationHistory<T> {
+    fn default() -> Self {
+        Self {
+            back: Vec::new(),
+            forward: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> NavigationHistory<T> {
+    /// Records `from` as the location being left by a new navigation
+    pub fn visit(&mut self, from: T) {
+        if self.back.last() != Some(&from) {
+            self.back.push(from);
+        }
+        if self.back.len() > MAX_HISTORY {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    /// Previous location. `current` is missing when nothing is open, and then there is
+    /// nothing to go forward to.
+    pub fn back(&mut self, current: Option<T>) -> Option<T> {
+        let previous = self.back.pop()?;
+        self.forward.extend(current);
+        Some(previous)
+    }
+
+    pub fn forward(&mut self, current: Option<T>) -> Option<T> {
+        let next = self.forward.pop()?;
+        self.back.extend(current);
+        Some(next)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+}
+
+/// Selects `target`, recording the location being left in the history
+pub fn navigate<T: Clone + PartialEq + 'static>(
+    mut selected: State<Option<T>>,
+    mut history: State<NavigationHistory<T>>,
+    target: T,
+) {
+    let current = selected.read().clone();
+    if current.as_ref() == Some(&target) {
+        return;
+    }
+    if let Some(current) = current {
+        history.write().visit(current);
+    }
+    selected.set(Some(target));
+}
+
+/// Closes the selected location, recording it in the history so going back reopens it
+pub fn close<T: Clone + PartialEq + 'static>(
+    mut selected: State<Option<T>>,
+    mut history: State<NavigationHistory<T>>,
+) {
+    let Some(current) = selected.read().clone() else {
+        return;
+    };
+    history.write().visit(current);
+    selected.set(None);
+}
+
+/// Returns to the previous location. Returns whether there was one
+pub fn go_back<T: Clone + PartialEq + 'static>(
+    mut selected: State<Option<T>>,
+    mut history: State<NavigationHistory<T>>,
+) -> bool {
+    let current = selected.read().clone();
+    let Some(previous) = history.write().back(current) else {
+        return false;
+    };
+    selected.set(Some(previous));
+    true
+}
+
+/// Returns to the location left by going back. Returns whether there was one
+pub fn go_forward<T: Clone + PartialEq + 'static>(
+    mut selected: State<Option<T>>,
+    mut history: State<NavigationHistory<T>>,
+) -> bool {
+    let current = selected.read().clone();
+    let Some(next) = history.write().forward(current) else {
+        return false;
+    };
+    selected.set(Some(next));
+    true
+}
@@ -1,21 +1,59 @@
+#[cfg(target_os = "android")]
+pub mod android;
+pub mod annotations;
 pub mod app;
+pub mod catalog;
+pub mod citation;
 pub mod components;
 pub mod dialog;
+pub mod export;
+pub mod history;
+pub mod memory;
+pub mod modules;
+pub mod plans;
+pub mod presentation;
+pub mod reference;
+pub mod session;
+pub mod settings;
+pub mod storage;
 pub mod utils;
+pub mod versification;
+
+use std::path::PathBuf;
 
 use freya::radio::RadioChannel;
 
+use crate::annotations::Annotations;
+use crate::catalog::Catalog;
+use crate::memory::MemoryVerses;
+use crate::modules::ModuleLibrary;
+use crate::settings::Settings;
+
 pub const APP_NAME: &str = env!("CARGO_CRATE_NAME");
 
 #[derive(Default, Clone)]
-#[allow(dead_code)]
-struct AppState {
-    books: Vec<String>,
+pub struct AppState {
+    /// Installed Bibles and their books
+    pub catalog: Catalog,
+    /// Installed dictionaries and other study modules
+    pub modules: ModuleLibrary,
+    /// Highlights, notes and bookmarks of the user
+    pub annotations: Annotations,
+    /// Verses being memorized and their review schedule
+    pub memory: MemoryVerses,
+    pub settings: Settings,
+    /// Folder of the search indexes, resolved from the settings when the app starts and after
+    /// the indexes are moved
+    pub index_dir: PathBuf,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Copy, Hash)]
 pub enum AppChannel {
-    BooksSuggesions
+    Catalog,
+    Modules,
+    Annotations,
+    Memory,
+    Settings,
 }
 
 impl RadioChannel<AppState> for AppChannel {}
@@ -28,13 +66,11 @@ use {app::init, freya::prelude::*, winit::platform::android::activity::AndroidAp
 fn android_main(droid_app: AndroidApp) {
     use winit::platform::android::EventLoopBuilderExtAndroid;
 
-    launch(
-        LaunchConfig::new().with_window(
-            WindowConfig::new(init)
-                .with_size(500., 450.)
-                .with_window_attributes(|_attr, event_loop_builder| {
-                    event_loop_builder.with_android_app(droid_app)
-                }),
-        ),
-    )
+    android::init(droid_app.clone());
+    launch(LaunchConfig::new().with_window(
+        // The activity decides the size of the window, which fills the screen
+        WindowConfig::new(init).with_window_attributes(|_attr, event_loop_builder| {
+            event_loop_builder.with_android_app(droid_app)
+        }),
+    ))
 }
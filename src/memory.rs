@@ -0,0 +1,417 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::reference::PassageRef;
+use crate::utils::{data_dir, load_json, save_json, today};
+
+/// Interval in days after which a verse is considered memorized
+const MATURE_INTERVAL: u32 = 21;
+
+/// A verse being memorized, scheduled with the SM-2 algorithm
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct MemoryVerse {
+    pub passage: PassageRef,
+    pub reference: String,
+    pub text: String,
+    /// How easy the verse is to recall, never below 1.3
+    pub ease: f32,
+    /// Days until the next review
+    pub interval: u32,
+    /// Reviews in a row recalled correctly
+    pub repetitions: u32,
+    /// Day of the next review, in days since the Unix epoch
+    pub due: u64,
+    #[serde(default)]
+    pub reviews: u32,
+    /// Reviews that were forgotten
+    #[serde(default)]
+    pub lapses: u32,
+}
+
+impl MemoryVerse {
+    pub fn new(passage: PassageRef, reference: String, text: String) -> Self {
+        Self {
+            passage,
+            reference,
+            text,
+            ease: 2.5,
+            interval: 0,
+            repetitions: 0,
+            due: today(),
+            reviews: 0,
+            lapses: 0,
+        }
+    }
+
+    pub fn is_due(&self, today: u64) -> bool {
+        self.due <= today
+    }
+
+    /// Schedules the next review from the quality of the recall, from 0 (forgotten) to 5
+    /// (perfect)
+    pub fn review(&mut self, quality: u8, today: u64) {
+        let quality = quality.min(5);
+        self.reviews += 1;
+        // Forgotten verses start over, only the recalled ones change how easy the verse is
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval = 1;
+            self.lapses += 1;
+        } else {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval as f32 * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+            let q = (5 - quality) as f32;
+            self.ease = (self.ease + 0.1 - q * (0.08 + q * 0.02)).max(1.3);
+        }
+        self.due = today + self.interval as u64;
+    }
+}
+
+/// Way a verse is asked during a review
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ReviewMode {
+    FirstLetters,
+    FillInTheBlank,
+    FullRecall,
+}
+
+impl ReviewMode {
+    pub const ALL: [ReviewMode; 3] = [
+        ReviewMode::FirstLetters,
+        ReviewMode::FillInTheBlank,
+        ReviewMode::FullRecall,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReviewMode::FirstLetters => "First letters",
+            ReviewMode::FillInTheBlank => "Fill in the blank",
+            ReviewMode::FullRecall => "Full recall",
+        }
+    }
+}
+
+/// Letters and digits of a word in lowercase, so answers are compared ignoring punctuation
+fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(normalize_word)
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Keeps the first letter of every word and its punctuation, e.g. `F G s l t w,`
+pub fn first_letters(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            let mut seen_letter = false;
+            word.chars()
+                .filter(|c| {
+                    if c.is_alphanumeric() {
+                        !std::mem::replace(&mut seen_letter, true)
+                    } else {
+                        true
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Hides about a third of the longer words, chosen from `seed` so every review asks others.
+/// Returns the text with blanks and the hidden words in order
+pub fn fill_in_the_blank(text: &str, seed: u64) -> (String, Vec<String>) {
+    let mut hidden = Vec::new();
+    let prompt = text
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, word)| {
+            let normalized = normalize_word(word);
+            if normalized.chars().count() > 3 && (i as u64 + seed).is_multiple_of(3) {
+                hidden.push(normalized.clone());
+                word.replace(
+                    word.trim_matches(|c: char| !c.is_alphanumeric()),
+                    &"_".repeat(normalized.chars().count()),
+                )
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    (prompt, hidden)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            current[j + 1] = (previous[j] + usize::from(ca != *cb))
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Words match when they differ by a typo, one edit every four letters, so shorter words
+/// must be exact
+fn similar(expected: &str, answer: &str) -> bool {
+    edit_distance(expected, answer) <= expected.chars().count() / 4
+}
+
+/// Result of checking an answer
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct RecallScore {
+    /// Share of the expected words recalled, from 0 to 1
+    pub accuracy: f32,
+    /// Suggested SM-2 quality
+    pub quality: u8,
+}
+
+impl RecallScore {
+    fn new(matched: usize, expected: usize) -> Self {
+        let accuracy = if expected == 0 {
+            1.
+        } else {
+            matched as f32 / expected as f32
+        };
+        let quality = match accuracy {
+            a if a >= 0.95 => 5,
+            a if a >= 0.85 => 4,
+            a if a >= 0.7 => 3,
+            a if a >= 0.5 => 2,
+            a if a >= 0.3 => 1,
+            _ => 0,
+        };
+        Self { accuracy, quality }
+    }
+}
+
+/// Compares a recalled text with the verse word by word, tolerating typos, missing and extra
+/// words
+pub fn grade_recall(expected: &str, answer: &str) -> RecallScore {
+    let expected = words(expected);
+    let answer = words(answer);
+    // Longest common subsequence of similar words
+    let mut table = vec![vec![0usize; answer.len() + 1]; expected.len() + 1];
+    for (i, e) in expected.iter().enumerate() {
+        for (j, a) in answer.iter().enumerate() {
+            table[i + 1][j + 1] = if similar(e, a) {
+                table[i][j] + 1
+            } else {
+                table[i][j + 1].max(table[i + 1][j])
+            };
+        }
+    }
+    let matched = table[expected.len()][answer.len()];
+    // Extra words count against the answer as well
+    let extra = answer.len().saturating_sub(matched);
+    RecallScore::new(matched.saturating_sub(extra / 2), expected.len())
+}
+
+/// Compares the words typed for the blanks, in order
+pub fn grade_blanks(hidden: &[String], answer: &str) -> RecallScore {
+    let answer = words(answer);
+    let matched = hidden
+        .iter()
+        .zip(&answer)
+        .filter(|(expected, answer)| similar(expected, answer))
+        .count();
+    RecallScore::new(matched, hidden.len())
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct MemoryStats {
+    pub total: usize,
+    pub due: usize,
+    /// Verses reviewed at intervals of three weeks or more
+    pub memorized: usize,
+    pub reviews: u32,
+    /// Share of reviews recalled correctly, from 0 to 1
+    pub retention: f32,
+}
+
+/// Verses the user is memorizing, stored in the data dir
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+pub struct MemoryVerses {
+    #[serde(default)]
+    pub verses: Vec<MemoryVerse>,
+}
+
+impl MemoryVerses {
+    fn path() -> PathBuf {
+        data_dir(&["memory.json"])
+    }
+
+    pub fn load() -> Self {
+        load_json(&Self::path())
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = save_json(&Self::path(), self) {
+            tracing::error!("Failed to save memory verses: {e}");
+        }
+    }
+
+    /// Adds the verses, returns false when they were already added
+    pub fn add(&mut self, passage: PassageRef, reference: String, text: String) -> bool {
+        if self.contains(&passage) {
+            return false;
+        }
+        self.verses.push(MemoryVerse::new(passage, reference, text));
+        true
+    }
+
+    pub fn remove(&mut self, passage: &PassageRef) {
+        self.verses.retain(|v| v.passage != *passage);
+    }
+
+    pub fn contains(&self, passage: &PassageRef) -> bool {
+        self.verses.iter().any(|v| v.passage == *passage)
+    }
+
+    /// Verses to review today, the most overdue first
+    pub fn due(&self, today: u64) -> Vec<&MemoryVerse> {
+        let mut due = self
+            .verses
+            .iter()
+            .filter(|v| v.is_due(today))
+            .collect::<Vec<_>>();
+        due.sort_by_key(|v| v.due);
+        due
+    }
+
+    pub fn review(&mut self, passage: &PassageRef, quality: u8) {
+        if let Some(verse) = self.verses.iter_mut().find(|v| v.passage == *passage) {
+            verse.review(quality, today());
+        }
+    }
+
+    pub fn stats(&self, today: u64) -> MemoryStats {
+        let reviews = self.verses.iter().map(|v| v.reviews).sum::<u32>();
+        let lapses = self.verses.iter().map(|v| v.lapses).sum::<u32>();
+        MemoryStats {
+            total: self.verses.len(),
+            due: self.verses.iter().filter(|v| v.is_due(today)).count(),
+            memorized: self
+                .verses
+                .iter()
+                .filter(|v| v.interval >= MATURE_INTERVAL)
+                .count(),
+            reviews,
+            retention: if reviews == 0 {
+                0.
+            } else {
+                1. - lapses as f32 / reviews as f32
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verse() -> MemoryVerse {
+        MemoryVerse::new(
+            PassageRef::new("kjv", "JHN", 11, 35..=35),
+            "John 11:35".to_string(),
+            "Jesus wept.".to_string(),
+        )
+    }
+
+    #[test]
+    fn similar_tolerates_a_typo_every_four_letters() {
+        assert!(similar("world", "wrld"));
+        assert!(!similar("world", "wrd"));
+        assert!(similar("everlasting", "evrlastng"));
+        assert!(!similar("everlasting", "evrlstng"));
+    }
+
+    #[test]
+    fn short_words_must_be_exact() {
+        assert!(similar("god", "god"));
+        assert!(!similar("god", "got"));
+        assert!(!similar("so", "to"));
+        assert!(!similar("a", "i"));
+    }
+
+    #[test]
+    fn recall_ignores_case_and_punctuation() {
+        let score = grade_recall("For God so loved the world,", "for god so loved the world");
+        assert_eq!(score.accuracy, 1.);
+        assert_eq!(score.quality, 5);
+    }
+
+    #[test]
+    fn recall_counts_missing_and_extra_words() {
+        let expected = "For God so loved the world that he gave";
+        assert_eq!(
+            grade_recall(expected, "For God so loved the world").quality,
+            2
+        );
+        let extra = grade_recall(
+            expected,
+            "For God so very much loved the world that he gave",
+        );
+        assert!(extra.accuracy < 1.);
+        assert_eq!(grade_recall(expected, "").quality, 0);
+    }
+
+    #[test]
+    fn blanks_are_compared_in_order() {
+        let hidden = vec!["loved".to_string(), "world".to_string()];
+        assert_eq!(grade_blanks(&hidden, "loved world").quality, 5);
+        assert_eq!(grade_blanks(&hidden, "world loved").quality, 0);
+    }
+
+    #[test]
+    fn good_reviews_grow_the_interval() {
+        let mut verse = verse();
+        verse.review(4, 100);
+        assert_eq!((verse.interval, verse.repetitions, verse.due), (1, 1, 101));
+        verse.review(4, 101);
+        assert_eq!((verse.interval, verse.repetitions, verse.due), (6, 2, 107));
+        verse.review(5, 107);
+        assert_eq!((verse.interval, verse.due), (15, 122));
+        assert!((verse.ease - 2.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn forgotten_reviews_start_over_keeping_the_ease() {
+        let mut verse = verse();
+        verse.review(5, 100);
+        verse.review(5, 101);
+        let ease = verse.ease;
+        verse.review(1, 107);
+        assert_eq!((verse.interval, verse.repetitions), (1, 0));
+        assert_eq!((verse.lapses, verse.reviews), (1, 3));
+        assert_eq!(verse.ease, ease);
+        assert_eq!(verse.due, 108);
+    }
+
+    #[test]
+    fn hard_reviews_lower_the_ease_to_a_minimum() {
+        let mut verse = verse();
+        for day in 0..10 {
+            verse.review(3, day);
+        }
+        assert_eq!(verse.ease, 1.3);
+    }
+}
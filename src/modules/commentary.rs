@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::QueryParser;
+use tantivy::schema::{Field, STORED, STRING, Schema, TEXT, Value};
+use tantivy::{Index, IndexWriter, TantivyDocument, Term, doc};
+
+use crate::modules::{ModuleError, ModuleFile};
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct CommentaryEntry {
+    pub book: String,
+    pub chapter: u32,
+    pub verse_start: u32,
+    /// Last chapter of the range, when it ends in another chapter
+    #[serde(default)]
+    pub chapter_end: Option<u32>,
+    #[serde(default)]
+    pub verse_end: Option<u32>,
+    #[serde(default)]
+    pub title: Option<String>,
+    pub text: String,
+}
+
+impl CommentaryEntry {
+    pub fn end(&self) -> (u32, u32) {
+        (
+            self.chapter_end.unwrap_or(self.chapter),
+            self.verse_end.unwrap_or(self.verse_start),
+        )
+    }
+
+    pub fn covers(&self, chapter: u32, verse: u32) -> bool {
+        (self.chapter, self.verse_start) <= (chapter, verse) && (chapter, verse) <= self.end()
+    }
+
+    /// Verse range of the entry, as `3:16-18` or `3:16-4:2`
+    pub fn range_label(&self) -> String {
+        let (chapter_end, verse_end) = self.end();
+        if chapter_end != self.chapter {
+            format!(
+                "{}:{}-{chapter_end}:{verse_end}",
+                self.chapter, self.verse_start
+            )
+        } else if verse_end != self.verse_start {
+            format!("{}:{}-{verse_end}", self.chapter, self.verse_start)
+        } else {
+            format!("{}:{}", self.chapter, self.verse_start)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Commentary {
+    pub id: String,
+    pub name: String,
+    entries: Vec<CommentaryEntry>,
+    books: HashMap<String, Vec<usize>>,
+}
+
+impl From<ModuleFile<CommentaryEntry>> for Commentary {
+    fn from(file: ModuleFile<CommentaryEntry>) -> Self {
+        let mut books = HashMap::<String, Vec<usize>>::new();
+        for (i, entry) in file.entries.iter().enumerate() {
+            books.entry(entry.book.to_uppercase()).or_default().push(i);
+        }
+        Self {
+            id: file.id,
+            name: file.name,
+            entries: file.entries,
+            books,
+        }
+    }
+}
+
+impl Commentary {
+    pub fn entries(&self) -> &[CommentaryEntry] {
+        &self.entries
+    }
+
+    /// Entries whose range includes the verse
+    pub fn covering(&self, book_id: &str, chapter: u32, verse: u32) -> Vec<&CommentaryEntry> {
+        self.books
+            .get(&book_id.to_uppercase())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .map(|i| &self.entries[*i])
+                    .filter(|e| e.covers(chapter, verse))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Commentary entry found by a full text search
+#[derive(PartialEq, Clone, Debug)]
+pub struct CommentaryHit {
+    pub module: String,
+    pub module_name: String,
+    pub entry: CommentaryEntry,
+}
+
+struct CommentaryFields {
+    module: Field,
+    module_name: Field,
+    book: Field,
+    chapter: Field,
+    verse_start: Field,
+    chapter_end: Field,
+    verse_end: Field,
+    title: Field,
+    text: Field,
+}
+
+/// Memory of the index writer. Commentaries are small, so a single thread with the minimum
+/// budget tantivy accepts is enough.
+const WRITER_MEMORY: usize = 15_000_000;
+
+/// Tantivy allows a single writer per index, and commentaries are indexed both while the
+/// library loads and after an install
+static WRITER_LOCK: Mutex<()> = Mutex::new(());
+
+/// Full text index of the installed commentaries, stored next to the verse index.
+///
+/// The `TantivySink` of setup_core only knows the schema of the Bible verses and has no way to
+/// store other documents, so commentaries keep their own index in a folder beside it.
+pub struct CommentaryIndex {
+    index: Index,
+    fields: CommentaryFields,
+    path: PathBuf,
+}
+
+impl std::fmt::Debug for CommentaryIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommentaryIndex").finish_non_exhaustive()
+    }
+}
+
+impl From<tantivy::TantivyError> for ModuleError {
+    fn from(e: tantivy::TantivyError) -> Self {
+        ModuleError::Index(e.to_string())
+    }
+}
+
+impl From<tantivy::directory::error::OpenDirectoryError> for ModuleError {
+    fn from(e: tantivy::directory::error::OpenDirectoryError) -> Self {
+        ModuleError::Index(e.to_string())
+    }
+}
+
+impl CommentaryIndex {
+    /// Opens the index kept in the `commentaries` folder of the search indexes
+    pub fn open(index_dir: &Path) -> Result<Self, ModuleError> {
+        let mut builder = Schema::builder();
+        let fields = CommentaryFields {
+            module: builder.add_text_field("module", STRING | STORED),
+            module_name: builder.add_text_field("module_name", STORED),
+            book: builder.add_text_field("book", STRING | STORED),
+            chapter: builder.add_u64_field("chapter", STORED),
+            verse_start: builder.add_u64_field("verse_start", STORED),
+            chapter_end: builder.add_u64_field("chapter_end", STORED),
+            verse_end: builder.add_u64_field("verse_end", STORED),
+            title: builder.add_text_field("title", TEXT | STORED),
+            text: builder.add_text_field("text", TEXT | STORED),
+        };
+        let path = index_dir.join("commentaries");
+        std::fs::create_dir_all(&path)?;
+        let index = Index::open_or_create(MmapDirectory::open(&path)?, builder.build())?;
+        Ok(Self {
+            index,
+            fields,
+            path,
+        })
+    }
+
+    fn marker_path(&self, module_id: &str) -> PathBuf {
+        self.path.join(format!("{module_id}.indexed"))
+    }
+
+    pub fn is_indexed(&self, module_id: &str) -> bool {
+        self.marker_path(module_id).exists()
+    }
+
+    /// Replaces the documents of the commentaries in the index, in a single commit. It blocks
+    /// while writing, so it must be called from a background thread.
+    pub fn index<'a>(
+        &self,
+        commentaries: impl IntoIterator<Item = &'a Commentary>,
+    ) -> Result<(), ModuleError> {
+        let f = &self.fields;
+        let _lock = WRITER_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut writer: IndexWriter = self.index.writer_with_num_threads(1, WRITER_MEMORY)?;
+        let mut indexed = Vec::new();
+        for commentary in commentaries {
+            writer.delete_term(Term::from_field_text(f.module, &commentary.id));
+            for entry in commentary.entries() {
+                let (chapter_end, verse_end) = entry.end();
+                writer.add_document(doc!(
+                    f.module => commentary.id.as_str(),
+                    f.module_name => commentary.name.as_str(),
+                    f.book => entry.book.to_uppercase(),
+                    f.chapter => entry.chapter as u64,
+                    f.verse_start => entry.verse_start as u64,
+                    f.chapter_end => chapter_end as u64,
+                    f.verse_end => verse_end as u64,
+                    f.title => entry.title.clone().unwrap_or_default(),
+                    f.text => entry.text.as_str(),
+                ))?;
+            }
+            indexed.push(commentary.id.as_str());
+        }
+        writer.commit()?;
+        for id in indexed {
+            std::fs::write(self.marker_path(id), [])?;
+        }
+        Ok(())
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<CommentaryHit>, ModuleError> {
+        let f = &self.fields;
+        let searcher = self.index.reader()?.searcher();
+        let (query, _) =
+            QueryParser::for_index(&self.index, vec![f.title, f.text]).parse_query_lenient(query);
+        let text = |doc: &TantivyDocument, field| {
+            doc.get_first(field)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let number = |doc: &TantivyDocument, field| {
+            doc.get_first(field)
+                .and_then(|v| v.as_u64())
+                .unwrap_or_default() as u32
+        };
+
+        searcher
+            .search(&query, &TopDocs::with_limit(limit))?
+            .into_iter()
+            .map(|(_, address)| {
+                let doc = searcher.doc::<TantivyDocument>(address)?;
+                let title = text(&doc, f.title);
+                Ok(CommentaryHit {
+                    module: text(&doc, f.module),
+                    module_name: text(&doc, f.module_name),
+                    entry: CommentaryEntry {
+                        book: text(&doc, f.book),
+                        chapter: number(&doc, f.chapter),
+                        verse_start: number(&doc, f.verse_start),
+                        chapter_end: Some(number(&doc, f.chapter_end)),
+                        verse_end: Some(number(&doc, f.verse_end)),
+                        title: (!title.is_empty()).then_some(title),
+                        text: text(&doc, f.text),
+                    },
+                })
+            })
+            .collect()
+    }
+}
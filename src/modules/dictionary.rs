@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::normalize_name;
+use crate::modules::ModuleFile;
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct DictionaryEntry {
+    pub headword: String,
+    pub definition: String,
+    /// Headwords of related entries
+    #[serde(default)]
+    pub see_also: Vec<String>,
+}
+
+/// Definition found in one of the installed dictionaries
+#[derive(PartialEq, Clone, Debug)]
+pub struct GlossaryEntry {
+    pub module: String,
+    pub entry: DictionaryEntry,
+}
+
+#[derive(Debug)]
+pub struct Dictionary {
+    pub id: String,
+    pub name: String,
+    pub language: String,
+    entries: Vec<DictionaryEntry>,
+    headwords: HashMap<String, usize>,
+}
+
+/// Normalizes a word so the clicked text and the headwords can be compared
+pub fn normalize_headword(word: &str) -> String {
+    let word = word
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .trim_end_matches("'s")
+        .trim_end_matches("’s");
+    normalize_name(word)
+}
+
+impl From<ModuleFile<DictionaryEntry>> for Dictionary {
+    fn from(file: ModuleFile<DictionaryEntry>) -> Self {
+        let headwords = file
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (normalize_headword(&entry.headword), i))
+            .collect();
+        Self {
+            id: file.id,
+            name: file.name,
+            language: file.language,
+            entries: file.entries,
+            headwords,
+        }
+    }
+}
+
+impl Dictionary {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entry(&self, headword: &str) -> Option<&DictionaryEntry> {
+        self.headwords
+            .get(&normalize_headword(headword))
+            .map(|i| &self.entries[*i])
+    }
+
+    /// Looks up a word as it appears in the text, falling back to its singular form
+    pub fn lookup(&self, word: &str) -> Option<&DictionaryEntry> {
+        let word = normalize_headword(word);
+        if word.is_empty() {
+            return None;
+        }
+        self.entry(&word)
+            .or_else(|| word.strip_suffix("es").and_then(|w| self.entry(w)))
+            .or_else(|| word.strip_suffix('s').and_then(|w| self.entry(w)))
+    }
+}
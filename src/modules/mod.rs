@@ -0,0 +1,462 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::data_dir;
+
+mod commentary;
+mod dictionary;
+mod morphology;
+mod strongs;
+mod topics;
+mod votes;
+
+pub use commentary::*;
+pub use dictionary::*;
+pub use morphology::*;
+pub use strongs::*;
+pub use topics::*;
+pub use votes::*;
+
+const MODULES_URL: &str =
+    "https://raw.githubusercontent.com/biblionlabs/extra_data_source/refs/heads/main/modules";
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleKind {
+    Dictionary,
+    Lexicon,
+    Tagged,
+    Commentary,
+    Topical,
+    Votes,
+}
+
+impl ModuleKind {
+    pub const ALL: &[ModuleKind] = &[
+        ModuleKind::Dictionary,
+        ModuleKind::Lexicon,
+        ModuleKind::Tagged,
+        ModuleKind::Commentary,
+        ModuleKind::Topical,
+        ModuleKind::Votes,
+    ];
+
+    /// Directory, inside `modules`, where the installed modules of this kind live
+    pub fn dir(&self) -> &'static str {
+        match self {
+            ModuleKind::Dictionary => "dictionaries",
+            ModuleKind::Lexicon => "lexicons",
+            ModuleKind::Tagged => "tagged",
+            ModuleKind::Commentary => "commentaries",
+            ModuleKind::Topical => "topics",
+            ModuleKind::Votes => "votes",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModuleKind::Dictionary => "Dictionary",
+            ModuleKind::Lexicon => "Lexicon",
+            ModuleKind::Tagged => "Tagged Bible",
+            ModuleKind::Commentary => "Commentary",
+            ModuleKind::Topical => "Topical Index",
+            ModuleKind::Votes => "Cross-reference votes",
+        }
+    }
+}
+
+/// Module that can be downloaded and installed from the data source
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ModuleSource {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub kind: ModuleKind,
+    pub language: &'static str,
+}
+
+pub const MODULE_SOURCES: &[ModuleSource] = &[
+    ModuleSource {
+        id: "eastons",
+        name: "Easton's Bible Dictionary",
+        kind: ModuleKind::Dictionary,
+        language: "en",
+    },
+    ModuleSource {
+        id: "smiths",
+        name: "Smith's Bible Dictionary",
+        kind: ModuleKind::Dictionary,
+        language: "en",
+    },
+    ModuleSource {
+        id: "strongs_greek",
+        name: "Strong's Greek Lexicon",
+        kind: ModuleKind::Lexicon,
+        language: "grc",
+    },
+    ModuleSource {
+        id: "strongs_hebrew",
+        name: "Strong's Hebrew Lexicon",
+        kind: ModuleKind::Lexicon,
+        language: "hbo",
+    },
+    ModuleSource {
+        id: "spa_rv1960_strongs",
+        name: "Reina Valera 1960 with Strong's numbers",
+        kind: ModuleKind::Tagged,
+        language: "es",
+    },
+    ModuleSource {
+        id: "mhc",
+        name: "Matthew Henry's Concise Commentary",
+        kind: ModuleKind::Commentary,
+        language: "en",
+    },
+    ModuleSource {
+        id: "jfb",
+        name: "Jamieson, Fausset and Brown Commentary",
+        kind: ModuleKind::Commentary,
+        language: "en",
+    },
+    ModuleSource {
+        id: "naves",
+        name: "Nave's Topical Bible",
+        kind: ModuleKind::Topical,
+        language: "en",
+    },
+    ModuleSource {
+        id: "torreys",
+        name: "Torrey's New Topical Textbook",
+        kind: ModuleKind::Topical,
+        language: "en",
+    },
+    ModuleSource {
+        id: "openbible",
+        name: "OpenBible.info cross-reference votes",
+        kind: ModuleKind::Votes,
+        language: "en",
+    },
+];
+
+impl ModuleSource {
+    pub fn url(&self) -> String {
+        format!("{MODULES_URL}/{}/{}.json", self.kind.dir(), self.id)
+    }
+
+    pub fn path(&self) -> PathBuf {
+        module_path(self.kind, self.id)
+    }
+
+    pub fn is_installed(&self) -> bool {
+        self.path().exists()
+    }
+
+    /// Downloads the module and stores it in the data directory.
+    /// The content is validated before being written so a broken download never replaces a
+    /// working module, and commentaries are indexed in the search indexes of `index_dir` once
+    /// their file is in place.
+    pub fn install(&self, index_dir: &Path) -> Result<(), ModuleError> {
+        let bytes = reqwest::blocking::get(self.url())?
+            .error_for_status()?
+            .bytes()?;
+        let mut commentary = None;
+        match self.kind {
+            ModuleKind::Dictionary => {
+                serde_json::from_slice::<ModuleFile<DictionaryEntry>>(&bytes)?;
+            }
+            ModuleKind::Lexicon => {
+                serde_json::from_slice::<ModuleFile<LexiconEntry>>(&bytes)?;
+            }
+            ModuleKind::Tagged => {
+                serde_json::from_slice::<ModuleFile<TaggedVerse>>(&bytes)?;
+            }
+            ModuleKind::Commentary => {
+                let file = serde_json::from_slice::<ModuleFile<CommentaryEntry>>(&bytes)?;
+                commentary = Some(Commentary::from(file));
+            }
+            ModuleKind::Topical => {
+                serde_json::from_slice::<ModuleFile<TopicEntry>>(&bytes)?;
+            }
+            ModuleKind::Votes => {
+                serde_json::from_slice::<ModuleFile<ReferenceVote>>(&bytes)?;
+            }
+        }
+
+        let path = self.path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, bytes)?;
+        if let Some(commentary) = commentary {
+            CommentaryIndex::open(index_dir)?.index([&commentary])?;
+        }
+        Ok(())
+    }
+}
+
+pub fn module_path(kind: ModuleKind, id: &str) -> PathBuf {
+    data_dir(&["modules", kind.dir(), format!("{id}.json").as_str()])
+}
+
+/// Files of the installed modules of a kind, including the ones copied by hand
+pub fn installed_files(kind: ModuleKind) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(data_dir(&["modules", kind.dir()])) else {
+        return Vec::new();
+    };
+    let mut files = entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+/// On disk format shared by every module kind
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModuleFile<T> {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub language: String,
+    #[serde(default)]
+    pub description: String,
+    /// Installed Bible the module belongs to, for modules tied to a translation
+    #[serde(default)]
+    pub bible: Option<String>,
+    pub entries: Vec<T>,
+}
+
+impl<T: for<'de> Deserialize<'de>> ModuleFile<T> {
+    pub fn read(path: &Path) -> Result<Self, ModuleError> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+}
+
+#[derive(Debug)]
+pub enum ModuleError {
+    Io(std::io::Error),
+    Http(reqwest::Error),
+    Format(serde_json::Error),
+    Index(String),
+}
+
+impl fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleError::Io(e) => write!(f, "io error: {e}"),
+            ModuleError::Http(e) => write!(f, "download error: {e}"),
+            ModuleError::Format(e) => write!(f, "invalid module: {e}"),
+            ModuleError::Index(e) => write!(f, "index error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ModuleError {}
+
+impl From<std::io::Error> for ModuleError {
+    fn from(e: std::io::Error) -> Self {
+        ModuleError::Io(e)
+    }
+}
+
+impl From<reqwest::Error> for ModuleError {
+    fn from(e: reqwest::Error) -> Self {
+        ModuleError::Http(e)
+    }
+}
+
+impl From<serde_json::Error> for ModuleError {
+    fn from(e: serde_json::Error) -> Self {
+        ModuleError::Format(e)
+    }
+}
+
+/// Installed modules loaded in memory
+#[derive(Default, Clone)]
+pub struct ModuleLibrary {
+    pub dictionaries: Vec<Arc<Dictionary>>,
+    pub lexicons: Vec<Arc<Lexicon>>,
+    pub tagged: Vec<Arc<TaggedBible>>,
+    pub commentaries: Vec<Arc<Commentary>>,
+    pub commentary_index: Option<Arc<CommentaryIndex>>,
+    pub topics: Vec<Arc<TopicalIndex>>,
+    pub votes: Vec<Arc<ReferenceVotes>>,
+}
+
+fn load_kind<T, M>(kind: ModuleKind) -> Vec<Arc<M>>
+where
+    T: for<'de> Deserialize<'de>,
+    M: From<ModuleFile<T>>,
+{
+    installed_files(kind)
+        .iter()
+        .filter_map(|path| {
+            ModuleFile::<T>::read(path)
+                .inspect_err(|e| tracing::error!("Failed to load {}: {e}", path.display()))
+                .ok()
+        })
+        .map(|file| Arc::new(M::from(file)))
+        .collect()
+}
+
+impl ModuleLibrary {
+    /// Reads the installed modules, with the commentary index kept in `index_dir`
+    pub fn load(index_dir: &Path) -> Self {
+        let commentaries = load_kind::<CommentaryEntry, Commentary>(ModuleKind::Commentary);
+        let commentary_index = CommentaryIndex::open(index_dir)
+            .inspect_err(|e| tracing::error!("Failed to open the commentary index: {e}"))
+            .ok();
+        // Commentaries copied by hand are indexed the first time they are loaded
+        if let Some(index) = &commentary_index {
+            let pending = commentaries
+                .iter()
+                .filter(|c| !index.is_indexed(&c.id))
+                .map(AsRef::as_ref)
+                .collect::<Vec<&Commentary>>();
+            if !pending.is_empty()
+                && let Err(e) = index.index(pending)
+            {
+                tracing::error!("Failed to index the new commentaries: {e}");
+            }
+        }
+
+        Self {
+            dictionaries: load_kind::<DictionaryEntry, _>(ModuleKind::Dictionary),
+            lexicons: load_kind::<LexiconEntry, _>(ModuleKind::Lexicon),
+            tagged: load_kind::<TaggedVerse, _>(ModuleKind::Tagged),
+            commentaries,
+            commentary_index: commentary_index.map(Arc::new),
+            topics: load_kind::<TopicEntry, _>(ModuleKind::Topical),
+            votes: load_kind::<ReferenceVote, _>(ModuleKind::Votes),
+        }
+    }
+
+    /// Commentary entries of every installed commentary that cover the verse
+    pub fn comments(&self, book_id: &str, chapter: u32, verse: u32) -> Vec<CommentaryHit> {
+        self.commentaries
+            .iter()
+            .flat_map(|commentary| {
+                commentary
+                    .covering(book_id, chapter, verse)
+                    .into_iter()
+                    .map(|entry| CommentaryHit {
+                        module: commentary.id.clone(),
+                        module_name: commentary.name.clone(),
+                        entry: entry.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    pub fn search_comments(&self, query: &str, limit: usize) -> Vec<CommentaryHit> {
+        let Some(index) = &self.commentary_index else {
+            return Vec::new();
+        };
+        index
+            .search(query, limit)
+            .inspect_err(|e| tracing::error!("Failed to search commentaries: {e}"))
+            .unwrap_or_default()
+    }
+
+    /// Topics of every installed topical index that cite the verse
+    pub fn topics_citing(&self, book_id: &str, chapter: u32, verse: u32) -> Vec<TopicHit> {
+        self.topics
+            .iter()
+            .flat_map(|index| {
+                index
+                    .citing(book_id, chapter, verse)
+                    .into_iter()
+                    .map(|topic| TopicHit {
+                        module: index.id.clone(),
+                        module_name: index.name.clone(),
+                        topic: topic.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Topics of every installed topical index whose name matches the query
+    pub fn search_topics(&self, query: &str, limit: usize) -> Vec<TopicHit> {
+        self.topics
+            .iter()
+            .flat_map(|index| {
+                index.search(query).into_iter().map(|topic| TopicHit {
+                    module: index.id.clone(),
+                    module_name: index.name.clone(),
+                    topic: topic.clone(),
+                })
+            })
+            .take(limit)
+            .collect()
+    }
+
+    /// Votes of a cross reference in the first installed dataset that has it, with both
+    /// verses numbered as the KJV
+    pub fn reference_votes(&self, from: (&str, u32, u32), to: (&str, u32, u32)) -> Option<i32> {
+        self.votes.iter().find_map(|votes| votes.votes(from, to))
+    }
+
+    /// Definitions of a word in every installed dictionary
+    pub fn define(&self, word: &str) -> Vec<GlossaryEntry> {
+        self.dictionaries
+            .iter()
+            .filter_map(|dictionary| {
+                dictionary.lookup(word).map(|entry| GlossaryEntry {
+                    module: dictionary.name.clone(),
+                    entry: entry.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Original language information of a word of a tagged Bible
+#[derive(PartialEq, Clone, Debug)]
+pub struct WordStudy {
+    pub word: TaggedWord,
+    pub strong: String,
+    pub lexicon: Option<LexiconEntry>,
+    /// Human readable morphology, when the code is known
+    pub parsing: Option<String>,
+    /// Verses of the tagged Bible that use the same Strong's number
+    pub concordance: Vec<TaggedVerse>,
+}
+
+impl ModuleLibrary {
+    pub fn tagged_bible(&self, bible_id: &str) -> Option<&Arc<TaggedBible>> {
+        self.tagged.iter().find(|t| t.bible == bible_id)
+    }
+
+    pub fn lexicon_entry(&self, number: &str) -> Option<&LexiconEntry> {
+        self.lexicons.iter().find_map(|l| l.entry(number))
+    }
+
+    /// Studies the `occurrence`-th appearance of `word` in a verse
+    pub fn study(
+        &self,
+        bible_id: &str,
+        book_id: &str,
+        chapter: u32,
+        verse: u32,
+        word: &str,
+        occurrence: usize,
+    ) -> Option<WordStudy> {
+        let tagged = self.tagged_bible(bible_id)?;
+        let word = tagged
+            .verse(book_id, chapter, verse)?
+            .find_word(word, occurrence)?
+            .clone();
+        let strong = normalize_strong(word.strong.as_deref()?)?;
+        Some(WordStudy {
+            lexicon: self.lexicon_entry(&strong).cloned(),
+            parsing: word.morph.as_deref().and_then(describe_morphology),
+            concordance: tagged.concordance(&strong).into_iter().cloned().collect(),
+            strong,
+            word,
+        })
+    }
+}
@@ -0,0 +1,377 @@
+//! Human readable parsing of the morphology codes used by tagged Bibles: Robinson codes for
+//! Greek (`V-AAI-3S`) and OSHB codes for Hebrew and Aramaic (`HVqp3ms`, `HR/Ncfsa`).
+
+fn case(c: char) -> Option<&'static str> {
+    Some(match c {
+        'N' => "nominative",
+        'G' => "genitive",
+        'D' => "dative",
+        'A' => "accusative",
+        'V' => "vocative",
+        _ => return None,
+    })
+}
+
+fn number(c: char) -> Option<&'static str> {
+    Some(match c {
+        'S' | 's' => "singular",
+        'P' | 'p' => "plural",
+        'd' => "dual",
+        _ => return None,
+    })
+}
+
+fn gender(c: char) -> Option<&'static str> {
+    Some(match c {
+        'M' | 'm' => "masculine",
+        'F' | 'f' => "feminine",
+        'N' => "neuter",
+        'b' => "both genders",
+        'c' => "common",
+        _ => return None,
+    })
+}
+
+fn person(c: char) -> Option<&'static str> {
+    Some(match c {
+        '1' => "1st person",
+        '2' => "2nd person",
+        '3' => "3rd person",
+        _ => return None,
+    })
+}
+
+/// Parses groups like `NSM` (case, number, gender) or `3S` (person, number)
+fn greek_inflection(code: &str) -> Vec<&'static str> {
+    let chars = code.chars().collect::<Vec<_>>();
+    match chars.as_slice() {
+        [p, n] if p.is_ascii_digit() => [person(*p), number(*n)].into_iter().flatten().collect(),
+        [c, n, g] => [case(*c), number(*n), gender(*g)]
+            .into_iter()
+            .flatten()
+            .collect(),
+        [c, n] => [case(*c), number(*n)].into_iter().flatten().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn greek_verb(code: &str) -> Vec<&'static str> {
+    let code = code.trim_start_matches('2');
+    let mut chars = code.chars();
+    let tense = match chars.next() {
+        Some('P') => "present",
+        Some('I') => "imperfect",
+        Some('F') => "future",
+        Some('A') => "aorist",
+        Some('R') => "perfect",
+        Some('L') => "pluperfect",
+        _ => return Vec::new(),
+    };
+    let voice = match chars.next() {
+        Some('A') => "active",
+        Some('M') => "middle",
+        Some('P') => "passive",
+        Some('E') => "middle or passive",
+        Some('D') => "middle deponent",
+        Some('O') => "passive deponent",
+        Some('N') => "middle or passive deponent",
+        _ => "",
+    };
+    let mood = match chars.next() {
+        Some('I') => "indicative",
+        Some('S') => "subjunctive",
+        Some('O') => "optative",
+        Some('M') => "imperative",
+        Some('N') => "infinitive",
+        Some('P') => "participle",
+        _ => "",
+    };
+    [tense, voice, mood]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn parse_greek(code: &str) -> Option<String> {
+    let mut parts = code.split('-');
+    let kind = parts.next()?;
+    let mut description = match kind {
+        "V" => {
+            let mut description = vec!["verb"];
+            description.extend(greek_verb(parts.next().unwrap_or_default()));
+            description
+        }
+        "N" => vec!["noun"],
+        "A" => vec!["adjective"],
+        "T" => vec!["article"],
+        "P" => vec!["personal pronoun"],
+        "R" => vec!["relative pronoun"],
+        "C" => vec!["reciprocal pronoun"],
+        "D" => vec!["demonstrative pronoun"],
+        "K" => vec!["correlative pronoun"],
+        "I" => vec!["interrogative pronoun"],
+        "X" => vec!["indefinite pronoun"],
+        "Q" => vec!["correlative or interrogative pronoun"],
+        "F" => vec!["reflexive pronoun"],
+        "S" => vec!["possessive pronoun"],
+        "ADV" => vec!["adverb"],
+        "CONJ" => vec!["conjunction"],
+        "COND" => vec!["conditional"],
+        "PRT" => vec!["particle"],
+        "PREP" => vec!["preposition"],
+        "INJ" => vec!["interjection"],
+        "ARAM" => vec!["Aramaic transliterated word"],
+        "HEB" => vec!["Hebrew transliterated word"],
+        _ => return None,
+    };
+    for part in parts {
+        match part {
+            "PRI" => description.push("proper indeclinable"),
+            "NUI" => description.push("indeclinable numeral"),
+            "LI" => description.push("indeclinable letter"),
+            "OI" => description.push("other indeclinable"),
+            "ATT" => description.push("attic"),
+            "C" => description.push("comparative"),
+            "S" => description.push("superlative"),
+            "N" => description.push("negative"),
+            "K" => description.push("with kai"),
+            "I" => description.push("interrogative"),
+            // Possessive pronouns carry the person of the possessor before the inflection
+            part if part.len() == 5 && part.starts_with(|c: char| c.is_ascii_digit()) => {
+                description.extend(person(part.chars().next()?));
+                description.extend(greek_inflection(part.get(2..).unwrap_or_default()));
+            }
+            // Personal pronouns carry the person before case and number
+            part if part.len() == 3 && part.starts_with(|c: char| c.is_ascii_digit()) => {
+                description.extend(person(part.chars().next()?));
+                description.extend(greek_inflection(part.get(1..).unwrap_or_default()));
+            }
+            part => description.extend(greek_inflection(part)),
+        }
+    }
+    Some(description.join(", "))
+}
+
+fn hebrew_stem(c: char) -> Option<&'static str> {
+    Some(match c {
+        'q' => "qal",
+        'N' => "niphal",
+        'p' => "piel",
+        'P' => "pual",
+        'h' => "hiphil",
+        'H' => "hophal",
+        't' => "hithpael",
+        'o' => "polel",
+        'O' => "polal",
+        'r' => "hithpolel",
+        'm' => "poel",
+        'M' => "poal",
+        'k' => "palel",
+        'K' => "pulal",
+        'Q' => "qal passive",
+        'l' => "pilpel",
+        'L' => "polpal",
+        'f' => "hithpalpel",
+        'D' => "nithpael",
+        'j' => "pealal",
+        'i' => "pilel",
+        'u' => "hothpaal",
+        'c' => "tiphil",
+        'v' => "hishtaphel",
+        'w' => "nithpalel",
+        'y' => "nithpoel",
+        'z' => "hithpoel",
+        _ => return None,
+    })
+}
+
+fn aramaic_stem(c: char) -> Option<&'static str> {
+    Some(match c {
+        'q' => "peal",
+        'Q' => "peil",
+        'u' => "hithpeel",
+        'N' => "niphal",
+        'p' => "pael",
+        'P' => "ithpaal",
+        'M' => "hithpaal",
+        'a' => "aphel",
+        'h' => "haphel",
+        's' => "saphel",
+        'e' => "shaphel",
+        'H' => "hophal",
+        'i' => "ithpeel",
+        't' => "hishtaphel",
+        'v' => "ishtaphel",
+        'w' => "hithaphel",
+        'o' => "polel",
+        'z' => "ithpoel",
+        'r' => "hithpolel",
+        'f' => "hithpalpel",
+        'b' => "hephal",
+        'c' => "tiphel",
+        'm' => "poel",
+        'l' => "palpel",
+        'L' => "ithpalpel",
+        'O' => "ithpolel",
+        'G' => "ittaphal",
+        _ => return None,
+    })
+}
+
+fn hebrew_conjugation(c: char) -> Option<&'static str> {
+    Some(match c {
+        'p' => "perfect",
+        'q' => "sequential perfect",
+        'i' => "imperfect",
+        'w' => "sequential imperfect",
+        'h' => "cohortative",
+        'j' => "jussive",
+        'v' => "imperative",
+        'r' => "active participle",
+        's' => "passive participle",
+        'a' => "infinitive absolute",
+        'c' => "infinitive construct",
+        _ => return None,
+    })
+}
+
+fn hebrew_state(c: char) -> Option<&'static str> {
+    Some(match c {
+        'a' => "absolute",
+        'c' => "construct",
+        'd' => "determined",
+        _ => return None,
+    })
+}
+
+/// Parses person, gender, number and state, in that order, skipping the ones not present
+fn hebrew_inflection(chars: &[char]) -> Vec<&'static str> {
+    let mut chars = chars.iter().copied().peekable();
+    let mut description = Vec::new();
+    if let Some(p) = chars.peek().copied().and_then(person) {
+        description.push(p);
+        chars.next();
+    }
+    if let Some(g) = chars.next().and_then(gender) {
+        description.push(g);
+    }
+    if let Some(n) = chars.next().and_then(number) {
+        description.push(n);
+    }
+    if let Some(s) = chars.next().and_then(hebrew_state) {
+        description.push(s);
+    }
+    description
+}
+
+fn hebrew_part(part: &str, aramaic: bool) -> Option<Vec<&'static str>> {
+    let chars = part.chars().collect::<Vec<_>>();
+    let (kind, rest) = chars.split_first()?;
+    let description = match kind {
+        'V' => {
+            let mut description = vec!["verb"];
+            description.extend(rest.first().and_then(|c| {
+                if aramaic {
+                    aramaic_stem(*c)
+                } else {
+                    hebrew_stem(*c)
+                }
+            }));
+            description.extend(rest.get(1).and_then(|c| hebrew_conjugation(*c)));
+            description.extend(hebrew_inflection(rest.get(2..).unwrap_or_default()));
+            description
+        }
+        'N' => {
+            let mut description = vec![match rest.first() {
+                Some('p') => "proper noun",
+                Some('g') => "gentilic noun",
+                _ => "noun",
+            }];
+            description.extend(hebrew_inflection(rest.get(1..).unwrap_or_default()));
+            description
+        }
+        'A' => {
+            let mut description = vec![match rest.first() {
+                Some('c') => "cardinal number",
+                Some('o') => "ordinal number",
+                _ => "adjective",
+            }];
+            description.extend(hebrew_inflection(rest.get(1..).unwrap_or_default()));
+            description
+        }
+        'P' => {
+            let mut description = vec![match rest.first() {
+                Some('d') => "demonstrative pronoun",
+                Some('f') => "indefinite pronoun",
+                Some('i') => "interrogative pronoun",
+                Some('r') => "relative pronoun",
+                _ => "personal pronoun",
+            }];
+            description.extend(hebrew_inflection(rest.get(1..).unwrap_or_default()));
+            description
+        }
+        'S' => {
+            let mut description = vec![match rest.first() {
+                Some('d') => "directional he",
+                Some('h') => "paragogic he",
+                Some('n') => "paragogic nun",
+                _ => "pronominal suffix",
+            }];
+            description.extend(hebrew_inflection(rest.get(1..).unwrap_or_default()));
+            description
+        }
+        'T' => vec![match rest.first() {
+            Some('d') => "definite article",
+            Some('n') => "negative particle",
+            Some('o') => "direct object marker",
+            Some('e') => "exhortation particle",
+            Some('i') => "interrogative particle",
+            Some('j') => "interjection",
+            Some('m') => "demonstrative particle",
+            Some('r') => "relative particle",
+            _ => "particle",
+        }],
+        'C' => vec!["conjunction"],
+        'D' => vec!["adverb"],
+        'R' => vec![match rest.first() {
+            Some('d') => "preposition with article",
+            _ => "preposition",
+        }],
+        _ => return None,
+    };
+    Some(description)
+}
+
+fn parse_hebrew(code: &str) -> Option<String> {
+    let aramaic = code.starts_with('A');
+    let parts = code[1..]
+        .split('/')
+        .map(|part| hebrew_part(part, aramaic).map(|d| d.join(", ")))
+        .collect::<Option<Vec<_>>>()?;
+    let description = parts.join(" + ");
+    Some(if aramaic {
+        format!("Aramaic {description}")
+    } else {
+        description
+    })
+}
+
+/// Describes a morphology code, or `None` when the code is not recognized
+pub fn describe_morphology(code: &str) -> Option<String> {
+    let code = code.trim();
+    if code.is_empty() {
+        return None;
+    }
+    // OSHB codes start with the language and never use dashes
+    if (code.starts_with('H') || code.starts_with('A'))
+        && !code.contains('-')
+        && code.len() > 1
+        && code[1..].starts_with(|c: char| "ACDNPRSTV".contains(c))
+        && code != "ADV"
+        && code != "ARAM"
+        && let Some(description) = parse_hebrew(code)
+    {
+        return Some(description);
+    }
+    parse_greek(code)
+}
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::modules::{ModuleFile, normalize_headword};
+
+/// Normalizes a Strong's number: `g0025`, `G25` and `G025` become `G25`
+pub fn normalize_strong(number: &str) -> Option<String> {
+    let number = number.trim();
+    let mut chars = number.chars();
+    let language = chars.next()?.to_ascii_uppercase();
+    if language != 'G' && language != 'H' {
+        return None;
+    }
+    let rest = chars.as_str();
+    let digits = rest.trim_start_matches('0');
+    let (digits, suffix) = digits.split_at(
+        digits
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(digits.len()),
+    );
+    if digits.is_empty() {
+        return None;
+    }
+    Some(format!("{language}{digits}{}", suffix.to_lowercase()))
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct LexiconEntry {
+    pub number: String,
+    pub lemma: String,
+    #[serde(default)]
+    pub transliteration: String,
+    #[serde(default)]
+    pub pronunciation: String,
+    /// Short translation of the lemma
+    #[serde(default)]
+    pub gloss: String,
+    #[serde(default)]
+    pub definition: String,
+}
+
+#[derive(Debug)]
+pub struct Lexicon {
+    pub id: String,
+    pub name: String,
+    entries: HashMap<String, LexiconEntry>,
+}
+
+impl From<ModuleFile<LexiconEntry>> for Lexicon {
+    fn from(file: ModuleFile<LexiconEntry>) -> Self {
+        let entries = file
+            .entries
+            .into_iter()
+            .filter_map(|entry| Some((normalize_strong(&entry.number)?, entry)))
+            .collect();
+        Self {
+            id: file.id,
+            name: file.name,
+            entries,
+        }
+    }
+}
+
+impl Lexicon {
+    pub fn entry(&self, number: &str) -> Option<&LexiconEntry> {
+        self.entries.get(&normalize_strong(number)?)
+    }
+}
+
+/// Word of a translation with the original language word behind it
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct TaggedWord {
+    pub text: String,
+    #[serde(default)]
+    pub strong: Option<String>,
+    #[serde(default)]
+    pub morph: Option<String>,
+    /// Original language word as written in the verse, when the module carries it
+    #[serde(default)]
+    pub original: Option<String>,
+    #[serde(default)]
+    pub transliteration: Option<String>,
+}
+
+impl TaggedWord {
+    fn matches(&self, word: &str) -> bool {
+        self.strong.is_some()
+            && self
+                .text
+                .split_whitespace()
+                .any(|part| normalize_headword(part) == word)
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct TaggedVerse {
+    pub book: String,
+    pub chapter: u32,
+    pub verse: u32,
+    pub words: Vec<TaggedWord>,
+}
+
+impl TaggedVerse {
+    pub fn text(&self) -> String {
+        self.words
+            .iter()
+            .map(|w| w.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Finds the `occurrence`-th tagged word matching `word`
+    pub fn find_word(&self, word: &str, occurrence: usize) -> Option<&TaggedWord> {
+        let word = normalize_headword(word);
+        let mut matches = self.words.iter().filter(|w| w.matches(&word));
+        let first = matches.clone().next();
+        matches.nth(occurrence).or(first)
+    }
+
+    /// Occurrence of the word at `index` as understood by [`TaggedVerse::find_word`]
+    pub fn occurrence_of(&self, index: usize) -> usize {
+        let Some(word) = self.words.get(index) else {
+            return 0;
+        };
+        let Some(part) = word.text.split_whitespace().next() else {
+            return 0;
+        };
+        let part = normalize_headword(part);
+        self.words[..index]
+            .iter()
+            .filter(|w| w.matches(&part))
+            .count()
+    }
+}
+
+pub type VerseKey = (String, u32, u32);
+
+/// Bible text tagged with Strong's numbers and morphology
+#[derive(Debug)]
+pub struct TaggedBible {
+    pub id: String,
+    pub name: String,
+    /// Installed Bible whose text is tagged by this module
+    pub bible: String,
+    verses: Vec<TaggedVerse>,
+    positions: HashMap<VerseKey, usize>,
+    chapters: HashMap<(String, u32), Vec<usize>>,
+    concordance: HashMap<String, Vec<usize>>,
+}
+
+impl From<ModuleFile<TaggedVerse>> for TaggedBible {
+    fn from(file: ModuleFile<TaggedVerse>) -> Self {
+        let mut positions = HashMap::new();
+        let mut chapters = HashMap::<(String, u32), Vec<usize>>::new();
+        let mut concordance = HashMap::<String, Vec<usize>>::new();
+        for (i, verse) in file.entries.iter().enumerate() {
+            positions.insert((verse.book.to_uppercase(), verse.chapter, verse.verse), i);
+            chapters
+                .entry((verse.book.to_uppercase(), verse.chapter))
+                .or_default()
+                .push(i);
+            for number in verse
+                .words
+                .iter()
+                .filter_map(|w| w.strong.as_deref().and_then(normalize_strong))
+            {
+                let verses = concordance.entry(number).or_default();
+                if verses.last() != Some(&i) {
+                    verses.push(i);
+                }
+            }
+        }
+        Self {
+            bible: file.bible.unwrap_or_else(|| file.id.clone()),
+            id: file.id,
+            name: file.name,
+            verses: file.entries,
+            positions,
+            chapters,
+            concordance,
+        }
+    }
+}
+
+impl TaggedBible {
+    pub fn verse(&self, book_id: &str, chapter: u32, verse: u32) -> Option<&TaggedVerse> {
+        self.positions
+            .get(&(book_id.to_uppercase(), chapter, verse))
+            .map(|i| &self.verses[*i])
+    }
+
+    pub fn chapter(&self, book_id: &str, chapter: u32) -> Vec<&TaggedVerse> {
+        self.chapters
+            .get(&(book_id.to_uppercase(), chapter))
+            .map(|verses| verses.iter().map(|i| &self.verses[*i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Every verse that uses the Strong's number, in the order of the module
+    pub fn concordance(&self, number: &str) -> Vec<&TaggedVerse> {
+        normalize_strong(number)
+            .and_then(|number| self.concordance.get(&number))
+            .map(|verses| verses.iter().map(|i| &self.verses[*i]).collect())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::modules::{ModuleFile, normalize_headword};
+use crate::reference::{PassageRef, STANDARD_BIBLE, VerseRef};
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct TopicEntry {
+    pub topic: String,
+    /// Passages citing the topic, as `JHN 3:16`, `John 3:16-18,20`, `Gen 1:1-2:3` or `PSA 23`
+    pub references: Vec<String>,
+    /// Names of related topics
+    #[serde(default)]
+    pub see_also: Vec<String>,
+}
+
+/// Topic of a topical index with its references already parsed
+#[derive(PartialEq, Clone, Debug)]
+pub struct Topic {
+    pub name: String,
+    pub passages: Vec<PassageRef>,
+    pub see_also: Vec<String>,
+}
+
+impl Topic {
+    /// Whether the topic cites the verse, numbered as the KJV
+    pub fn cites(&self, book_id: &str, chapter: u32, verse: u32) -> bool {
+        let verse = VerseRef::new(STANDARD_BIBLE, book_id.to_uppercase(), chapter, verse);
+        self.passages.iter().any(|p| p.contains(&verse))
+    }
+}
+
+/// Topic found in one of the installed topical indexes
+#[derive(PartialEq, Clone, Debug)]
+pub struct TopicHit {
+    pub module: String,
+    pub module_name: String,
+    pub topic: Topic,
+}
+
+#[derive(Debug)]
+pub struct TopicalIndex {
+    pub id: String,
+    pub name: String,
+    topics: Vec<Topic>,
+    names: HashMap<String, usize>,
+    /// Topics citing each chapter, keyed by book id and chapter
+    chapters: HashMap<(String, u32), Vec<usize>>,
+}
+
+impl From<ModuleFile<TopicEntry>> for TopicalIndex {
+    fn from(file: ModuleFile<TopicEntry>) -> Self {
+        let mut topics = file
+            .entries
+            .into_iter()
+            .map(|entry| Topic {
+                passages: entry
+                    .references
+                    .iter()
+                    .filter_map(|r| {
+                        let passages = PassageRef::parse_many(STANDARD_BIBLE, r);
+                        if passages.is_none() {
+                            tracing::warn!("Invalid reference {r} in topic {}", entry.topic);
+                        }
+                        passages
+                    })
+                    .flatten()
+                    .collect(),
+                name: entry.topic,
+                see_also: entry.see_also,
+            })
+            .collect::<Vec<_>>();
+        topics.sort_by_key(|t| normalize_headword(&t.name));
+
+        let mut names = HashMap::new();
+        let mut chapters = HashMap::<(String, u32), Vec<usize>>::new();
+        for (i, topic) in topics.iter().enumerate() {
+            names.insert(normalize_headword(&topic.name), i);
+            for passage in &topic.passages {
+                for chapter in passage.chapters() {
+                    let topics = chapters
+                        .entry((passage.book_id.to_uppercase(), chapter))
+                        .or_default();
+                    if topics.last() != Some(&i) {
+                        topics.push(i);
+                    }
+                }
+            }
+        }
+        Self {
+            id: file.id,
+            name: file.name,
+            topics,
+            names,
+            chapters,
+        }
+    }
+}
+
+impl TopicalIndex {
+    pub fn len(&self) -> usize {
+        self.topics.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.topics.is_empty()
+    }
+
+    /// Every topic in alphabetical order
+    pub fn topics(&self) -> &[Topic] {
+        &self.topics
+    }
+
+    pub fn topic(&self, name: &str) -> Option<&Topic> {
+        self.names
+            .get(&normalize_headword(name))
+            .map(|i| &self.topics[*i])
+    }
+
+    /// Topics whose name contains the query, the ones starting with it first
+    pub fn search(&self, query: &str) -> Vec<&Topic> {
+        let query = normalize_headword(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let (mut prefixed, contained): (Vec<_>, Vec<_>) = self
+            .topics
+            .iter()
+            .filter_map(|topic| {
+                let name = normalize_headword(&topic.name);
+                name.contains(&query)
+                    .then_some((name.starts_with(&query), topic))
+            })
+            .partition(|(prefix, _)| *prefix);
+        prefixed.extend(contained);
+        prefixed.into_iter().map(|(_, topic)| topic).collect()
+    }
+
+    /// Topics with a reference that includes the verse
+    pub fn citing(&self, book_id: &str, chapter: u32, verse: u32) -> Vec<&Topic> {
+        self.chapters
+            .get(&(book_id.to_uppercase(), chapter))
+            .into_iter()
+            .flatten()
+            .map(|i| &self.topics[*i])
+            .filter(|topic| topic.cites(book_id, chapter, verse))
+            .collect()
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::CanonicalBook;
+use crate::modules::ModuleFile;
+
+/// Votes the users of OpenBible.info gave to a cross reference. The verses are written as
+/// `GEN 1:1`, and ranges as `PRO 8:22-30` count as their first verse.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct ReferenceVote {
+    pub from: String,
+    pub to: String,
+    pub votes: i32,
+}
+
+/// Packs a verse in a number, the cross reference datasets have hundreds of thousands of them
+fn verse_key(book_id: &str, chapter: u32, verse: u32) -> Option<u32> {
+    let book = CanonicalBook::by_id(book_id)?.order() as u32;
+    (chapter <= u8::MAX as u32 && verse <= u8::MAX as u32)
+        .then_some((book << 16) | (chapter << 8) | verse)
+}
+
+fn parse_verse(reference: &str) -> Option<u32> {
+    let (book, location) = reference.trim().rsplit_once(' ')?;
+    let (chapter, verses) = location.split_once(':')?;
+    let verse = verses.split('-').next()?;
+    verse_key(book, chapter.parse().ok()?, verse.parse().ok()?)
+}
+
+/// Relevance of the cross references, numbered as the KJV like the ones of the Bibles
+#[derive(Debug)]
+pub struct ReferenceVotes {
+    pub id: String,
+    pub name: String,
+    votes: HashMap<u64, i32>,
+}
+
+impl From<ModuleFile<ReferenceVote>> for ReferenceVotes {
+    fn from(file: ModuleFile<ReferenceVote>) -> Self {
+        let votes = file
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let from = parse_verse(&entry.from)?;
+                let to = parse_verse(&entry.to)?;
+                Some((((from as u64) << 32) | to as u64, entry.votes))
+            })
+            .collect();
+        Self {
+            id: file.id,
+            name: file.name,
+            votes,
+        }
+    }
+}
+
+impl ReferenceVotes {
+    /// Votes of the reference from a verse to another, both as `(book id, chapter, verse)`
+    pub fn votes(&self, from: (&str, u32, u32), to: (&str, u32, u32)) -> Option<i32> {
+        let from = verse_key(from.0, from.1, from.2)?;
+        let to = verse_key(to.0, to.1, to.2)?;
+        self.votes
+            .get(&(((from as u64) << 32) | to as u64))
+            .copied()
+    }
+}
@@ -0,0 +1,519 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::{CANONICAL_BOOKS, CanonicalBook};
+use crate::reference::{PassageRef, STANDARD_BIBLE};
+use crate::utils::{data_dir, load_json, save_json, today};
+
+const PLAN_DAYS: usize = 365;
+
+/// Books in the order their events happened, used by the chronological plan
+const CHRONOLOGICAL_ORDER: &[&str] = &[
+    "GEN", "JOB", "EXO", "LEV", "NUM", "DEU", "JOS", "JDG", "RUT", "1SA", "2SA", "1CH", "PSA",
+    "1KI", "PRO", "ECC", "SNG", "2KI", "2CH", "JON", "AMO", "HOS", "ISA", "MIC", "NAM", "ZEP",
+    "JER", "HAB", "LAM", "OBA", "EZK", "DAN", "JOL", "EZR", "HAG", "ZEC", "EST", "NEH", "MAL",
+    "MAT", "MRK", "LUK", "JHN", "ACT", "JAS", "GAL", "1TH", "2TH", "1CO", "2CO", "ROM", "EPH",
+    "PHP", "COL", "PHM", "1TI", "TIT", "1PE", "HEB", "2TI", "2PE", "JUD", "1JN", "2JN", "3JN",
+    "REV",
+];
+
+/// Ranges of books read in parallel every day by the M'Cheyne plan
+const MCHEYNE_STREAMS: [&[(&str, &str)]; 4] = [
+    &[("GEN", "2CH")],
+    &[("MAT", "REV")],
+    &[("EZR", "MAL")],
+    &[("ACT", "REV"), ("MAT", "JHN")],
+];
+
+/// Plan of daily readings
+#[derive(PartialEq, Clone, Debug)]
+pub struct ReadingPlan {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Passages of every day, the first day at index 0
+    pub days: Vec<Vec<PassageRef>>,
+}
+
+/// Every chapter of the books, in the given order
+fn chapters<'a>(books: impl IntoIterator<Item = &'a CanonicalBook>) -> Vec<PassageRef> {
+    books
+        .into_iter()
+        .flat_map(|book| {
+            (1..=book.chapters as u32)
+                .map(|c| PassageRef::whole_chapters(STANDARD_BIBLE, book.id, c..=c))
+        })
+        .collect()
+}
+
+fn books_between(first: &str, last: &str) -> Vec<&'static CanonicalBook> {
+    let position = |id: &str| CANONICAL_BOOKS.iter().position(|b| b.id == id).unwrap_or(0);
+    CANONICAL_BOOKS[position(first)..=position(last)]
+        .iter()
+        .collect()
+}
+
+/// Spreads the passages evenly over the days of a year
+fn spread(passages: Vec<PassageRef>) -> Vec<Vec<PassageRef>> {
+    let total = passages.len();
+    let mut days = vec![Vec::new(); PLAN_DAYS];
+    for (i, passage) in passages.into_iter().enumerate() {
+        days[i * PLAN_DAYS / total].push(passage);
+    }
+    days
+}
+
+impl ReadingPlan {
+    /// Plans that come with the application
+    pub fn built_in() -> Vec<ReadingPlan> {
+        let mut mcheyne = vec![Vec::new(); PLAN_DAYS];
+        for stream in MCHEYNE_STREAMS {
+            let books = stream
+                .iter()
+                .flat_map(|(first, last)| books_between(first, last));
+            for (day, passages) in spread(chapters(books)).into_iter().enumerate() {
+                mcheyne[day].extend(passages);
+            }
+        }
+        vec![
+            ReadingPlan {
+                id: "canonical".to_string(),
+                name: "Canonical in a year".to_string(),
+                description: "The whole Bible from Genesis to Revelation in 365 days".to_string(),
+                days: spread(chapters(CANONICAL_BOOKS)),
+            },
+            ReadingPlan {
+                id: "chronological".to_string(),
+                name: "Chronological".to_string(),
+                description: "The books in the order their events happened, in 365 days"
+                    .to_string(),
+                days: spread(chapters(
+                    CHRONOLOGICAL_ORDER
+                        .iter()
+                        .filter_map(|id| CanonicalBook::by_id(id)),
+                )),
+            },
+            ReadingPlan {
+                id: "mcheyne".to_string(),
+                name: "M'Cheyne".to_string(),
+                description:
+                    "Four readings a day, the Old Testament once and the New Testament twice"
+                        .to_string(),
+                days: mcheyne,
+            },
+        ]
+    }
+
+    /// Imported plans, stored as JSON in the plans folder of the data dir
+    pub fn imported() -> Vec<ReadingPlan> {
+        let Ok(entries) = std::fs::read_dir(data_dir(&["plans"])) else {
+            return Vec::new();
+        };
+        let mut plans = entries
+            .flatten()
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|e| {
+                Self::load_file(&e.path())
+                    .inspect_err(|err| tracing::error!("Failed to load plan {:?}: {err}", e.path()))
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+        plans.sort_by(|a, b| a.name.cmp(&b.name));
+        plans
+    }
+
+    pub fn all() -> Vec<ReadingPlan> {
+        let mut plans = Self::built_in();
+        plans.extend(Self::imported());
+        plans
+    }
+
+    fn load_file(path: &Path) -> Result<ReadingPlan, PlanError> {
+        let text = std::fs::read_to_string(path)?;
+        let id = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let is_csv = path.extension().is_some_and(|ext| ext == "csv");
+        let name = if is_csv {
+            id.replace(['_', '-'], " ")
+        } else {
+            id.clone()
+        };
+        if is_csv {
+            Self::from_csv(id, name, &text)
+        } else {
+            Self::from_json(id, &text)
+        }
+    }
+
+    /// Parses a plan like `{"name": "Gospels", "days": [["Matt 1", "Matt 2"], ["Matt 3"]]}`,
+    /// where every passage can also hold several passages separated by `;`
+    pub fn from_json(id: impl Into<String>, text: &str) -> Result<ReadingPlan, PlanError> {
+        let file: PlanFile =
+            serde_json::from_str(text).map_err(|e| PlanError::Format(e.to_string()))?;
+        let days = file
+            .days
+            .iter()
+            .map(|day| {
+                day.iter()
+                    .map(|p| {
+                        PassageRef::parse_list(STANDARD_BIBLE, p).map_err(PlanError::InvalidPassage)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|passages| passages.into_iter().flatten().collect())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(id.into(), file.name, file.description, days)
+    }
+
+    /// Parses a plan with a day per line, like `1,Gen 1;Matt 1` or `2,"Gen 2:1,3"`. The day
+    /// number is optional, and a header line is skipped
+    pub fn from_csv(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        text: &str,
+    ) -> Result<ReadingPlan, PlanError> {
+        let mut days = BTreeMap::<usize, Vec<PassageRef>>::new();
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(text.as_bytes());
+        for (record_number, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| PlanError::Format(e.to_string()))?;
+            let mut fields = record.iter().peekable();
+            let (day, numbered) = match fields.peek().map(|f| f.parse::<usize>()) {
+                Some(Ok(day)) => {
+                    fields.next();
+                    (day, true)
+                }
+                _ => (days.keys().next_back().map_or(1, |last| last + 1), false),
+            };
+            let passages = fields
+                .filter(|f| !f.is_empty())
+                .map(|f| PassageRef::parse_list(STANDARD_BIBLE, f))
+                .collect::<Result<Vec<_>, _>>();
+            match passages {
+                Ok(passages) if !passages.is_empty() => days
+                    .entry(day)
+                    .or_default()
+                    .extend(passages.into_iter().flatten()),
+                Ok(_) => {}
+                Err(_) if record_number == 0 && !numbered => {}
+                Err(passage) => return Err(PlanError::InvalidPassage(passage)),
+            }
+        }
+        let last = days.keys().next_back().copied().unwrap_or_default();
+        let days = (1..=last)
+            .map(|day| days.remove(&day).unwrap_or_default())
+            .collect();
+        Self::new(id.into(), name.into(), String::new(), days)
+    }
+
+    fn new(
+        id: String,
+        name: String,
+        description: String,
+        days: Vec<Vec<PassageRef>>,
+    ) -> Result<ReadingPlan, PlanError> {
+        if days.iter().all(Vec::is_empty) {
+            return Err(PlanError::Empty);
+        }
+        Ok(ReadingPlan {
+            name: if name.trim().is_empty() {
+                id.clone()
+            } else {
+                name
+            },
+            id,
+            description,
+            days,
+        })
+    }
+
+    /// Copies a JSON or CSV plan into the plans folder once it is known to be valid
+    pub fn import(path: &Path) -> Result<ReadingPlan, PlanError> {
+        let mut plan = Self::load_file(path)?;
+        let id = plan
+            .id
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect::<String>();
+        plan.id = format!("imported-{id}");
+        let file = PlanFile {
+            name: plan.name.clone(),
+            description: plan.description.clone(),
+            days: plan
+                .days
+                .iter()
+                .map(|day| day.iter().map(PassageRef::label).collect())
+                .collect(),
+        };
+        let target = data_dir(&["plans"]);
+        std::fs::create_dir_all(&target)?;
+        save_json(&target.join(format!("{}.json", plan.id)), &file)?;
+        Ok(plan)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PlanFile {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    description: String,
+    days: Vec<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+pub struct PlanState {
+    /// Day the plan was started, in days since the Unix epoch
+    pub started: u64,
+    /// Days of the plan read, starting at 1
+    #[serde(default)]
+    pub completed: BTreeSet<usize>,
+}
+
+impl PlanState {
+    /// Day of the plan to read today, starting at 1
+    pub fn current_day(&self) -> usize {
+        (today().saturating_sub(self.started) + 1) as usize
+    }
+}
+
+/// Reading plans followed by the user, stored in the data dir
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+pub struct PlanProgress {
+    /// Plan whose reading is shown on startup
+    #[serde(default)]
+    pub active: Option<String>,
+    #[serde(default)]
+    pub plans: BTreeMap<String, PlanState>,
+}
+
+impl PlanProgress {
+    fn path() -> PathBuf {
+        data_dir(&["reading_plans.json"])
+    }
+
+    pub fn load() -> Self {
+        load_json(&Self::path())
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = save_json(&Self::path(), self) {
+            tracing::error!("Failed to save reading plans: {e}");
+        }
+    }
+
+    /// Starts following the plan from today, keeping the progress of a plan already started
+    pub fn start(&mut self, plan_id: &str) {
+        self.plans
+            .entry(plan_id.to_string())
+            .or_insert_with(|| PlanState {
+                started: today(),
+                completed: BTreeSet::new(),
+            });
+        self.active = Some(plan_id.to_string());
+    }
+
+    pub fn restart(&mut self, plan_id: &str) {
+        self.plans.remove(plan_id);
+        self.start(plan_id);
+    }
+
+    pub fn toggle_day(&mut self, plan_id: &str, day: usize) {
+        let Some(state) = self.plans.get_mut(plan_id) else {
+            return;
+        };
+        if !state.completed.remove(&day) {
+            state.completed.insert(day);
+        }
+    }
+
+    /// Active plan and its day to read today, when it is not read yet
+    pub fn pending_today<'a>(&self, plans: &'a [ReadingPlan]) -> Option<(&'a ReadingPlan, usize)> {
+        let plan_id = self.active.as_ref()?;
+        let plan = plans.iter().find(|p| p.id == *plan_id)?;
+        let state = self.plans.get(plan_id)?;
+        let day = state.current_day();
+        (day <= plan.days.len() && !state.completed.contains(&day)).then_some((plan, day))
+    }
+}
+
+#[derive(Debug)]
+pub enum PlanError {
+    Io(std::io::Error),
+    Format(String),
+    InvalidPassage(String),
+    Empty,
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanError::Io(e) => write!(f, "io error: {e}"),
+            PlanError::Format(e) => write!(f, "invalid plan: {e}"),
+            PlanError::InvalidPassage(p) => write!(f, "invalid passage: {p}"),
+            PlanError::Empty => write!(f, "the plan has no readings"),
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+impl From<std::io::Error> for PlanError {
+    fn from(e: std::io::Error) -> Self {
+        PlanError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(day: &[PassageRef]) -> Vec<String> {
+        day.iter().map(PassageRef::label).collect()
+    }
+
+    /// Every chapter read by the plan, with the times it is read
+    fn chapter_counts(plan: &ReadingPlan) -> BTreeMap<(String, u32), usize> {
+        let mut counts = BTreeMap::new();
+        for passage in plan.days.iter().flatten() {
+            *counts
+                .entry((passage.book_id.clone(), passage.chapter))
+                .or_default() += 1;
+        }
+        counts
+    }
+
+    fn built_in(id: &str) -> ReadingPlan {
+        ReadingPlan::built_in()
+            .into_iter()
+            .find(|p| p.id == id)
+            .unwrap()
+    }
+
+    #[test]
+    fn csv_days_with_quoted_fields() {
+        let plan = ReadingPlan::from_csv(
+            "gospels",
+            "Gospels",
+            "day,reading\n1,Matt 1;Matt 2\n2,\"John 3:16,18\",Rom 8\n",
+        )
+        .unwrap();
+        assert_eq!(plan.days.len(), 2);
+        assert_eq!(labels(&plan.days[0]), ["Matthew 1", "Matthew 2"]);
+        assert_eq!(
+            labels(&plan.days[1]),
+            ["John 3:16", "John 3:18", "Romans 8"]
+        );
+    }
+
+    #[test]
+    fn csv_days_without_numbers_follow_each_other() {
+        let plan = ReadingPlan::from_csv("p", "", "Gen 1\n\n3,Gen 2\nGen 3").unwrap();
+        assert_eq!(plan.name, "p");
+        let days = plan.days.iter().map(|d| labels(d)).collect::<Vec<_>>();
+        assert_eq!(
+            days,
+            [
+                vec!["Genesis 1".to_string()],
+                vec![],
+                vec!["Genesis 2".to_string()],
+                vec!["Genesis 3".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_invalid_passages_are_reported() {
+        let error = ReadingPlan::from_csv("p", "P", "1,Gen 1\n2,Nowhere 1").unwrap_err();
+        assert!(matches!(error, PlanError::InvalidPassage(p) if p == "Nowhere 1"));
+        let error = ReadingPlan::from_csv("p", "P", "day,reading\n").unwrap_err();
+        assert!(matches!(error, PlanError::Empty));
+    }
+
+    #[test]
+    fn json_days() {
+        let plan = ReadingPlan::from_json(
+            "gospels",
+            r#"{"name": "Gospels", "days": [["Matt 1", "Matt 2; Mark 1"], ["Gen 1:1-2:3"]]}"#,
+        )
+        .unwrap();
+        assert_eq!(plan.name, "Gospels");
+        assert_eq!(labels(&plan.days[0]), ["Matthew 1", "Matthew 2", "Mark 1"]);
+        assert_eq!(labels(&plan.days[1]), ["Genesis 1:1-2:3"]);
+    }
+
+    #[test]
+    fn json_errors() {
+        assert!(matches!(
+            ReadingPlan::from_json("p", "[]"),
+            Err(PlanError::Format(_))
+        ));
+        assert!(matches!(
+            ReadingPlan::from_json("p", r#"{"days": [["Nowhere 1"]]}"#),
+            Err(PlanError::InvalidPassage(_))
+        ));
+        assert!(matches!(
+            ReadingPlan::from_json("p", r#"{"days": [[]]}"#),
+            Err(PlanError::Empty)
+        ));
+    }
+
+    #[test]
+    fn canonical_and_chronological_read_every_chapter_once() {
+        for id in ["canonical", "chronological"] {
+            let plan = built_in(id);
+            assert_eq!(plan.days.len(), PLAN_DAYS, "{id}");
+            assert!(plan.days.iter().all(|day| !day.is_empty()), "{id}");
+            let counts = chapter_counts(&plan);
+            assert_eq!(counts.len(), 1189, "{id}");
+            assert!(counts.values().all(|count| *count == 1), "{id}");
+        }
+    }
+
+    #[test]
+    fn canonical_plan_follows_the_books() {
+        let plan = built_in("canonical");
+        assert_eq!(labels(&plan.days[0])[0], "Genesis 1");
+        assert_eq!(
+            plan.days[PLAN_DAYS - 1]
+                .last()
+                .map(PassageRef::label)
+                .unwrap(),
+            "Revelation 22"
+        );
+    }
+
+    #[test]
+    fn mcheyne_reads_the_new_testament_twice() {
+        let plan = built_in("mcheyne");
+        assert_eq!(plan.days.len(), PLAN_DAYS);
+        let counts = chapter_counts(&plan);
+        assert_eq!(counts.len(), 1189);
+        let new_testament = books_between("MAT", "REV")
+            .iter()
+            .map(|b| b.id)
+            .collect::<Vec<_>>();
+        for ((book_id, chapter), count) in counts {
+            let expected = if new_testament.contains(&book_id.as_str()) {
+                2
+            } else {
+                1
+            };
+            assert_eq!(count, expected, "{book_id} {chapter}");
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Full screen presentation of verses on a second screen
+//!
+//! Every window has its own radio station, so the projector window and the reader share the
+//! presentation through a global, and the projector is woken through a channel when it changes.
+
+use std::rc::Rc;
+use std::sync::Mutex;
+
+use freya::prelude::*;
+use kanal::{Sender, unbounded};
+use serde::{Deserialize, Serialize};
+use winit::window::Fullscreen;
+
+pub const BACKGROUNDS: [&str; 5] = ["#000000", "#1B2A49", "#2E1A47", "#0F3D2E", "#FFFFFF"];
+pub const TEXT_COLORS: [&str; 4] = ["#FFFFFF", "#F5E6A8", "#BFE3FF", "#000000"];
+pub const FONT_FAMILIES: [&str; 3] = ["sans-serif", "serif", "monospace"];
+
+/// Look of the projected verses, stored in the settings
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[serde(default)]
+pub struct PresentationStyle {
+    pub background: String,
+    pub color: String,
+    pub font_family: String,
+    /// Size of the verse text in pixels
+    pub font_size: f32,
+}
+
+impl Default for PresentationStyle {
+    fn default() -> Self {
+        Self {
+            background: BACKGROUNDS[0].to_string(),
+            color: TEXT_COLORS[0].to_string(),
+            font_family: FONT_FAMILIES[0].to_string(),
+            font_size: 56.,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Slide {
+    pub reference: String,
+    pub verse: u32,
+    pub text: String,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Presentation {
+    /// Chapter the slides were built from, as `bible/book/chapter`
+    pub chapter_key: String,
+    pub slides: Vec<Slide>,
+    pub current: usize,
+    /// Hides the text without ending the presentation
+    pub blank: bool,
+    pub active: bool,
+    pub window_open: bool,
+    pub style: Option<PresentationStyle>,
+    /// Bumped on every change so the windows know when to render again
+    pub revision: u64,
+}
+
+static PRESENTATION: Mutex<Presentation> = Mutex::new(Presentation {
+    chapter_key: String::new(),
+    slides: Vec::new(),
+    current: 0,
+    blank: false,
+    active: false,
+    window_open: false,
+    style: None,
+    revision: 0,
+});
+
+/// Wakes the projector window on every change, set while the window is open
+static NOTIFIER: Mutex<Option<Sender<()>>> = Mutex::new(None);
+
+impl Presentation {
+    pub fn snapshot() -> Self {
+        PRESENTATION
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub fn update(update: impl FnOnce(&mut Presentation)) {
+        {
+            let mut presentation = PRESENTATION.lock().unwrap_or_else(|e| e.into_inner());
+            update(&mut presentation);
+            presentation.revision += 1;
+        }
+        if let Some(notifier) = &*NOTIFIER.lock().unwrap_or_else(|e| e.into_inner()) {
+            let _ = notifier.send(());
+        }
+    }
+
+    /// Presents the verses of a chapter starting from `verse`, opening the projector window if
+    /// it is not open yet
+    pub fn start(chapter_key: String, slides: Vec<Slide>, verse: u32, style: PresentationStyle) {
+        let mut launch = false;
+        Self::update(|presentation| {
+            presentation.current = slides.iter().position(|s| s.verse == verse).unwrap_or(0);
+            presentation.chapter_key = chapter_key;
+            presentation.slides = slides;
+            presentation.blank = false;
+            presentation.active = true;
+            presentation.style = Some(style);
+            launch = !presentation.window_open;
+            presentation.window_open = true;
+        });
+        if launch {
+            Platform::get().launch_window(
+                WindowConfig::new(window)
+                    .with_title("Biblion Presentation")
+                    .with_size(960., 540.),
+            );
+        }
+    }
+
+    pub fn stop() {
+        Self::update(|presentation| presentation.active = false);
+    }
+
+    pub fn current_slide(&self) -> Option<&Slide> {
+        self.slides.get(self.current)
+    }
+
+    pub fn next(&mut self) {
+        if self.current + 1 < self.slides.len() {
+            self.current += 1;
+        }
+        self.blank = false;
+    }
+
+    pub fn previous(&mut self) {
+        self.current = self.current.saturating_sub(1);
+        self.blank = false;
+    }
+
+    /// Handles the keys of the keyboard and of presentation clickers, returns whether the key
+    /// was used
+    pub fn handle_key(key: &Key) -> bool {
+        match key {
+            Key::Named(
+                NamedKey::ArrowRight | NamedKey::ArrowDown | NamedKey::PageDown | NamedKey::Enter,
+            ) => {
+                Self::update(Presentation::next);
+                true
+            }
+            Key::Character(c) if c.as_str() == " " => {
+                Self::update(Presentation::next);
+                true
+            }
+            Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowUp | NamedKey::PageUp) => {
+                Self::update(Presentation::previous);
+                true
+            }
+            // Clickers usually send `b` or `.` to blank the screen
+            Key::Character(c) if c.eq_ignore_ascii_case("b") || c.as_str() == "." => {
+                Self::update(|p| p.blank = !p.blank);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Marks the projector window as closed when it goes away, and drops the notifier so the loop
+/// waiting for changes ends
+struct WindowGuard;
+
+impl Drop for WindowGuard {
+    fn drop(&mut self) {
+        NOTIFIER.lock().unwrap_or_else(|e| e.into_inner()).take();
+        Presentation::update(|presentation| presentation.window_open = false);
+    }
+}
+
+/// Root of the projector window
+pub fn window() -> impl IntoElement {
+    let mut presentation = use_state(Presentation::snapshot);
+    use_hook(|| Rc::new(WindowGuard));
+
+    // Projectors are usually the second screen, so the window goes full screen on a monitor
+    // other than the primary one when there is one
+    use_hook(|| {
+        Platform::get().with_window(None, |window| {
+            let primary = window.primary_monitor();
+            let monitor = window
+                .available_monitors()
+                .find(|monitor| Some(monitor) != primary.as_ref())
+                .or(primary);
+            window.set_fullscreen(Some(Fullscreen::Borderless(monitor)));
+        });
+    });
+
+    use_hook(|| {
+        let (tx, rx) = unbounded();
+        *NOTIFIER.lock().unwrap_or_else(|e| e.into_inner()) = Some(tx);
+        let rx = rx.to_async();
+        spawn(async move {
+            while rx.recv().await.is_ok() {
+                // Changes made in a row are shown at once
+                while let Ok(Some(())) = rx.try_recv() {}
+                let latest = Presentation::snapshot();
+                if latest.revision != presentation.peek().revision {
+                    presentation.set(latest);
+                }
+            }
+        });
+    });
+
+    let presentation = presentation.read().clone();
+    let style = presentation.style.clone().unwrap_or_default();
+    let background = Color::from_hex(&style.background).unwrap_or(Color::BLACK);
+    let color = Color::from_hex(&style.color).unwrap_or(Color::WHITE);
+    let slide = presentation
+        .current_slide()
+        .filter(|_| presentation.active && !presentation.blank)
+        .cloned();
+
+    rect()
+        .expanded()
+        .center()
+        .padding(48.)
+        .background(background)
+        .on_global_key_down(move |e: Event<KeyboardEventData>| {
+            if Presentation::handle_key(&e.key) {
+                return;
+            }
+            // F toggles full screen on the monitor the window is on, Escape leaves it
+            let fullscreen = match &e.key {
+                Key::Character(c) if c.eq_ignore_ascii_case("f") => None,
+                Key::Named(NamedKey::Escape) => Some(false),
+                _ => return,
+            };
+            Platform::get().with_window(None, move |window| {
+                let enable = fullscreen.unwrap_or(window.fullscreen().is_none());
+                window.set_fullscreen(
+                    enable.then(|| Fullscreen::Borderless(window.current_monitor())),
+                );
+            });
+        })
+        .maybe_child(slide.map(|slide| {
+            rect()
+                .width(Size::Fill)
+                .vertical()
+                .spacing(style.font_size * 0.6)
+                .cross_align(Alignment::Center)
+                .child(
+                    rect()
+                        .width(Size::Fill)
+                        .text_align(TextAlign::Center)
+                        .child(
+                            label()
+                                .width(Size::Fill)
+                                .font_family(style.font_family.clone())
+                                .font_size(style.font_size)
+                                .color(color)
+                                .text(slide.text),
+                        ),
+                )
+                .child(
+                    label()
+                        .font_family(style.font_family.clone())
+                        .font_size(style.font_size * 0.5)
+                        .color(color.with_a(180))
+                        .text(slide.reference),
+                )
+                .into_element()
+        }))
+}
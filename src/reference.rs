@@ -0,0 +1,802 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::{CanonicalBook, Catalog};
+
+/// Scheme of the links that open the app at a verse, e.g. `biblion://read/kjv/JHN/3/16`
+pub const URI_SCHEME: &str = "biblion";
+
+/// Bible id of passages that are in no installed Bible, like the ones of reading plans, topics
+/// and exports, which are numbered as the KJV
+pub const STANDARD_BIBLE: &str = "";
+
+/// Last verse of passages that go on to the end of their last chapter, whichever verse it is
+pub const END_OF_CHAPTER: u32 = u32::MAX;
+
+fn book_order(book_id: &str) -> usize {
+    CanonicalBook::by_id(book_id).map_or(usize::MAX, CanonicalBook::order)
+}
+
+/// Name of the book in the Bible, its English name when the Bible does not have it
+fn book_name(catalog: &Catalog, bible_id: &str, book_id: &str) -> String {
+    catalog
+        .book(bible_id, book_id)
+        .map(|b| b.name.clone())
+        .or_else(|| CanonicalBook::by_id(book_id).map(|b| b.names[0].to_string()))
+        .unwrap_or_else(|| book_id.to_string())
+}
+
+/// A verse of an installed Bible, numbered as that Bible
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Hash)]
+pub struct VerseRef {
+    pub bible_id: String,
+    pub book_id: String,
+    pub chapter: u32,
+    pub verse: u32,
+}
+
+impl VerseRef {
+    pub fn new(
+        bible_id: impl Into<String>,
+        book_id: impl Into<String>,
+        chapter: u32,
+        verse: u32,
+    ) -> Self {
+        Self {
+            bible_id: bible_id.into(),
+            book_id: book_id.into(),
+            chapter,
+            verse,
+        }
+    }
+
+    /// Parses a reference like `John 3:16` or `Jn 3` in a Bible, a chapter meaning its first
+    /// verse
+    pub fn parse(bible_id: &str, input: &str) -> Option<Self> {
+        PassageRef::parse(bible_id, input).map(|passage| passage.first())
+    }
+
+    /// Reads a `biblion://read/<bible>/<BOOK>/<chapter>/<verse>` link, the verse being optional
+    pub fn from_uri(uri: &str) -> Option<Self> {
+        let path = uri
+            .strip_prefix(URI_SCHEME)?
+            .strip_prefix("://read/")?
+            .trim_end_matches('/');
+        let mut parts = path.split('/');
+        let bible_id = parts.next().filter(|id| !id.is_empty())?;
+        let book_id = parts.next().filter(|id| !id.is_empty())?;
+        let chapter = parts.next()?.parse().ok().filter(|c| *c > 0)?;
+        let verse = match parts.next() {
+            Some(verse) => verse.parse().ok().filter(|v| *v > 0)?,
+            None => 1,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self::new(bible_id, book_id.to_uppercase(), chapter, verse))
+    }
+
+    pub fn to_uri(&self) -> String {
+        format!(
+            "{URI_SCHEME}://read/{}/{}/{}/{}",
+            self.bible_id, self.book_id, self.chapter, self.verse
+        )
+    }
+
+    /// Verse numbered as the KJV, mapped to the versification of the Bible
+    pub fn from_standard(
+        catalog: &Catalog,
+        bible_id: impl Into<String>,
+        book_id: impl Into<String>,
+        chapter: u32,
+        verse: u32,
+    ) -> Self {
+        let bible_id = bible_id.into();
+        let book_id = book_id.into();
+        let (chapter, verse) = catalog
+            .versification(&bible_id)
+            .from_standard(&book_id, chapter, verse);
+        Self::new(bible_id, book_id, chapter, verse)
+    }
+
+    /// Chapter and verse numbered as the KJV
+    pub fn to_standard(&self, catalog: &Catalog) -> (u32, u32) {
+        catalog
+            .versification(&self.bible_id)
+            .to_standard(&self.book_id, self.chapter, self.verse)
+    }
+
+    /// The same verse in another Bible, following its versification
+    pub fn in_bible(&self, catalog: &Catalog, bible_id: &str) -> Self {
+        let (chapter, verse) = catalog.map_verse(
+            &self.bible_id,
+            bible_id,
+            &self.book_id,
+            self.chapter,
+            self.verse,
+        );
+        Self::new(bible_id, self.book_id.clone(), chapter, verse)
+    }
+
+    pub fn with_verse(&self, verse: u32) -> Self {
+        Self {
+            verse,
+            ..self.clone()
+        }
+    }
+
+    /// Identifies the chapter of the verse, as `bible/BOOK/chapter`
+    pub fn chapter_key(&self) -> String {
+        format!("{}/{}/{}", self.bible_id, self.book_id, self.chapter)
+    }
+
+    /// Name the Bible gives to the book of the verse, e.g. `Juan`
+    pub fn book_name(&self, catalog: &Catalog) -> String {
+        book_name(catalog, &self.bible_id, &self.book_id)
+    }
+
+    /// Reference with the name the Bible gives to the book, e.g. `Juan 3:16`
+    pub fn localized(&self, catalog: &Catalog) -> String {
+        format!(
+            "{} {}:{}",
+            self.book_name(catalog),
+            self.chapter,
+            self.verse
+        )
+    }
+}
+
+/// Written with the book id, e.g. `JHN 3:16`
+impl fmt::Display for VerseRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}:{}", self.book_id, self.chapter, self.verse)
+    }
+}
+
+/// Verses are ordered by Bible, then in canonical order
+impl Ord for VerseRef {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bible_id
+            .cmp(&other.bible_id)
+            .then(book_order(&self.book_id).cmp(&book_order(&other.book_id)))
+            .then(self.book_id.cmp(&other.book_id))
+            .then((self.chapter, self.verse).cmp(&(other.chapter, other.verse)))
+    }
+}
+
+impl PartialOrd for VerseRef {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Consecutive verses of a book of an installed Bible, from a verse of `chapter` to a verse of
+/// `end_chapter`. The verses are private so a passage can never end before it starts, stored
+/// passages are checked when read.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Hash)]
+#[serde(try_from = "StoredPassage")]
+pub struct PassageRef {
+    pub bible_id: String,
+    pub book_id: String,
+    pub chapter: u32,
+    start: u32,
+    end_chapter: u32,
+    end: u32,
+}
+
+/// A passage as written on disk, before its verses are checked
+#[derive(Deserialize)]
+struct StoredPassage {
+    bible_id: String,
+    book_id: String,
+    chapter: u32,
+    start: u32,
+    /// Missing in passages saved before they could span chapters
+    #[serde(default)]
+    end_chapter: Option<u32>,
+    end: u32,
+}
+
+impl TryFrom<StoredPassage> for PassageRef {
+    type Error = String;
+
+    fn try_from(stored: StoredPassage) -> Result<Self, Self::Error> {
+        let start = (stored.chapter, stored.start);
+        let end = (stored.end_chapter.unwrap_or(stored.chapter), stored.end);
+        Self::span(stored.bible_id, stored.book_id, start, end).ok_or_else(|| {
+            format!(
+                "passage starts at {}:{} after its last verse {}:{}",
+                start.0, start.1, end.0, end.1
+            )
+        })
+    }
+}
+
+/// Splits a reference into the book and the location after it, without spaces
+fn split_reference(input: &str) -> Option<(&str, String)> {
+    let input = input.trim();
+    let (book_end, last) = input.char_indices().rfind(|(_, c)| c.is_alphabetic())?;
+    let (book, location) = input.split_at(book_end + last.len_utf8());
+    let location = location
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+    (!location.is_empty()).then_some((book.trim(), location))
+}
+
+/// Parses a location like `3`, `1-3`, `3:16`, `3:16-18` or `1:1-2:3` in the book
+fn parse_location(bible_id: &str, book: &str, location: &str) -> Option<PassageRef> {
+    let book = CanonicalBook::by_name(book)?;
+    let number = |n: &str| n.parse::<u32>().ok().filter(|n| *n > 0);
+    let (from, to) = location.split_once('-').unwrap_or((location, location));
+    match (from.split_once(':'), to.split_once(':')) {
+        // A verse or verses across chapters, which must come in order
+        (Some((chapter, start)), Some((end_chapter, end))) => PassageRef::span(
+            bible_id,
+            book.id,
+            (number(chapter)?, number(start)?),
+            (number(end_chapter)?, number(end)?),
+        ),
+        (Some((chapter, start)), None) => Some(PassageRef::new(
+            bible_id,
+            book.id,
+            number(chapter)?,
+            number(start)?..=number(to)?,
+        )),
+        (None, None) => Some(PassageRef::whole_chapters(
+            bible_id,
+            book.id,
+            number(from)?..=number(to)?,
+        )),
+        (None, Some(_)) => None,
+    }
+}
+
+impl PassageRef {
+    /// Passage of the verses in the range, which may be given in either order
+    pub fn new(
+        bible_id: impl Into<String>,
+        book_id: impl Into<String>,
+        chapter: u32,
+        verses: RangeInclusive<u32>,
+    ) -> Self {
+        Self {
+            bible_id: bible_id.into(),
+            book_id: book_id.into(),
+            chapter,
+            start: *verses.start().min(verses.end()),
+            end_chapter: chapter,
+            end: *verses.start().max(verses.end()),
+        }
+    }
+
+    /// Passage from a `(chapter, verse)` to one of the same or a later chapter, `None` when it
+    /// would end before it starts
+    pub fn span(
+        bible_id: impl Into<String>,
+        book_id: impl Into<String>,
+        start: (u32, u32),
+        end: (u32, u32),
+    ) -> Option<Self> {
+        (start <= end).then(|| Self {
+            bible_id: bible_id.into(),
+            book_id: book_id.into(),
+            chapter: start.0,
+            start: start.1,
+            end_chapter: end.0,
+            end: end.1,
+        })
+    }
+
+    /// Passage of whole chapters, which may be given in either order
+    pub fn whole_chapters(
+        bible_id: impl Into<String>,
+        book_id: impl Into<String>,
+        chapters: RangeInclusive<u32>,
+    ) -> Self {
+        Self {
+            bible_id: bible_id.into(),
+            book_id: book_id.into(),
+            chapter: *chapters.start().min(chapters.end()),
+            start: 1,
+            end_chapter: *chapters.start().max(chapters.end()),
+            end: END_OF_CHAPTER,
+        }
+    }
+
+    /// Passage of a single verse
+    pub fn verse(verse: &VerseRef) -> Self {
+        Self::new(
+            verse.bible_id.clone(),
+            verse.book_id.clone(),
+            verse.chapter,
+            verse.verse..=verse.verse,
+        )
+    }
+
+    /// Parses a passage of a Bible like `John 3`, `Jn 3:16`, `Juan 3:16-18`, `Gen 1:1-2:3` or
+    /// `Ps 1-3`
+    pub fn parse(bible_id: &str, input: &str) -> Option<Self> {
+        let (book, location) = split_reference(input)?;
+        parse_location(bible_id, book, &location)
+    }
+
+    /// Parses passages of a book separated by `,`, like `Gen 1:1,3,5-7` or `Ps 1,3`. Items
+    /// without a chapter are verses of the chapter before when it had verses, chapters
+    /// otherwise.
+    pub fn parse_many(bible_id: &str, input: &str) -> Option<Vec<Self>> {
+        let (book, location) = split_reference(input)?;
+        let mut passages = Vec::<Self>::new();
+        for item in location.split(',') {
+            let passage = match passages.last() {
+                Some(last) if last.end != END_OF_CHAPTER && !item.contains(':') => {
+                    parse_location(bible_id, book, &format!("{}:{item}", last.end_chapter))?
+                }
+                _ => parse_location(bible_id, book, item)?,
+            };
+            passages.push(passage);
+        }
+        Some(passages)
+    }
+
+    /// Parses passages separated by `;`, returning the first one that is not valid on error
+    pub fn parse_list(bible_id: &str, input: &str) -> Result<Vec<Self>, String> {
+        input
+            .split(';')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| Self::parse_many(bible_id, p).ok_or_else(|| p.to_string()))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|passages| passages.into_iter().flatten().collect())
+    }
+
+    pub fn first(&self) -> VerseRef {
+        VerseRef::new(
+            self.bible_id.clone(),
+            self.book_id.clone(),
+            self.chapter,
+            self.start,
+        )
+    }
+
+    /// Last verse, numbered `END_OF_CHAPTER` when the passage goes on to the end of its last
+    /// chapter
+    pub fn last(&self) -> VerseRef {
+        VerseRef::new(
+            self.bible_id.clone(),
+            self.book_id.clone(),
+            self.end_chapter,
+            self.end,
+        )
+    }
+
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    pub fn end_chapter(&self) -> u32 {
+        self.end_chapter
+    }
+
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// Chapters the passage covers, at least in part
+    pub fn chapters(&self) -> RangeInclusive<u32> {
+        self.chapter..=self.end_chapter
+    }
+
+    /// Verses of the passage in one of its chapters. Every chapter but the last goes on to
+    /// `END_OF_CHAPTER`.
+    pub fn verses_in(&self, chapter: u32) -> Option<RangeInclusive<u32>> {
+        if !self.chapters().contains(&chapter) {
+            return None;
+        }
+        let start = if chapter == self.chapter {
+            self.start
+        } else {
+            1
+        };
+        let end = if chapter == self.end_chapter {
+            self.end
+        } else {
+            END_OF_CHAPTER
+        };
+        Some(start..=end)
+    }
+
+    fn start_position(&self) -> (u32, u32) {
+        (self.chapter, self.start)
+    }
+
+    fn end_position(&self) -> (u32, u32) {
+        (self.end_chapter, self.end)
+    }
+
+    fn same_book(&self, other: &Self) -> bool {
+        self.bible_id == other.bible_id && self.book_id == other.book_id
+    }
+
+    pub fn contains(&self, verse: &VerseRef) -> bool {
+        self.bible_id == verse.bible_id
+            && self.book_id == verse.book_id
+            && (self.start_position()..=self.end_position()).contains(&(verse.chapter, verse.verse))
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.same_book(other)
+            && self.start_position() <= other.end_position()
+            && other.start_position() <= self.end_position()
+    }
+
+    /// Verses shared by both passages
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let start = self.start_position().max(other.start_position());
+        let end = self.end_position().min(other.end_position());
+        self.overlaps(other).then(|| Self {
+            chapter: start.0,
+            start: start.1,
+            end_chapter: end.0,
+            end: end.1,
+            ..self.clone()
+        })
+    }
+
+    /// Passage covering both, when they overlap or follow each other
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        // Position of the verse after the last one of a passage
+        let after = |(chapter, verse): (u32, u32)| match verse {
+            END_OF_CHAPTER => (chapter + 1, 1),
+            verse => (chapter, verse + 1),
+        };
+        let start = self.start_position().min(other.start_position());
+        let end = self.end_position().max(other.end_position());
+        (self.same_book(other)
+            && self.start_position() <= after(other.end_position())
+            && other.start_position() <= after(self.end_position()))
+        .then(|| Self {
+            chapter: start.0,
+            start: start.1,
+            end_chapter: end.0,
+            end: end.1,
+            ..self.clone()
+        })
+    }
+
+    /// The same verses numbered as in another Bible. Both ends are mapped through the
+    /// versifications, so a passage may start and end in different chapters of the other
+    /// Bible. `None` when that Bible places its last verse before the first one.
+    pub fn in_bible(&self, catalog: &Catalog, bible_id: &str) -> Option<Self> {
+        let first = self.first().in_bible(catalog, bible_id);
+        let end = match self.end {
+            END_OF_CHAPTER => {
+                let (chapter, verse) = catalog.map_chapter_end(
+                    &self.bible_id,
+                    bible_id,
+                    &self.book_id,
+                    self.end_chapter,
+                );
+                (chapter, verse.unwrap_or(END_OF_CHAPTER))
+            }
+            _ => {
+                let last = self.last().in_bible(catalog, bible_id);
+                (last.chapter, last.verse)
+            }
+        };
+        Self::span(
+            bible_id,
+            self.book_id.clone(),
+            (first.chapter, first.verse),
+            end,
+        )
+    }
+
+    /// Chapters and verses of the passage, e.g. `3`, `3:16`, `3:16-18`, `1:1-2:3` or `1-3`.
+    /// Passages that go on to the end of a chapter but do not start it end with `ff`, e.g.
+    /// `9:22ff` or `114:10-115ff`.
+    pub fn location(&self) -> String {
+        let (chapter, start) = self.start_position();
+        let (end_chapter, end) = self.end_position();
+        match (chapter == end_chapter, start, end) {
+            (true, 1, END_OF_CHAPTER) => chapter.to_string(),
+            (true, _, END_OF_CHAPTER) => format!("{chapter}:{start}ff"),
+            (true, _, _) if start == end => format!("{chapter}:{start}"),
+            (true, _, _) => format!("{chapter}:{start}-{end}"),
+            (false, 1, END_OF_CHAPTER) => format!("{chapter}-{end_chapter}"),
+            (false, _, END_OF_CHAPTER) => format!("{chapter}:{start}-{end_chapter}ff"),
+            (false, _, _) => format!("{chapter}:{start}-{end_chapter}:{end}"),
+        }
+    }
+
+    /// Reference with the English name of the book, e.g. `John 3:16-18`
+    pub fn label(&self) -> String {
+        let book = CanonicalBook::by_id(&self.book_id)
+            .and_then(|b| b.names.first().copied())
+            .unwrap_or(&self.book_id);
+        format!("{book} {}", self.location())
+    }
+
+    /// Reference with the name the Bible gives to the book, e.g. `Juan 3:16-18`
+    pub fn localized(&self, catalog: &Catalog) -> String {
+        let book = book_name(catalog, &self.bible_id, &self.book_id);
+        format!("{book} {}", self.location())
+    }
+}
+
+/// Written with the book id, e.g. `JHN 3:16-18`
+impl fmt::Display for PassageRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.book_id, self.location())
+    }
+}
+
+impl Ord for PassageRef {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.first()
+            .cmp(&other.first())
+            .then(self.end_position().cmp(&other.end_position()))
+    }
+}
+
+impl PartialOrd for PassageRef {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn john_3(verses: RangeInclusive<u32>) -> PassageRef {
+        PassageRef::new("kjv", "JHN", 3, verses)
+    }
+
+    #[test]
+    fn parses_verses_and_chapters() {
+        assert_eq!(
+            VerseRef::parse("kjv", "John 3:16"),
+            Some(VerseRef::new("kjv", "JHN", 3, 16))
+        );
+        assert_eq!(
+            VerseRef::parse("kjv", "Jn 3"),
+            Some(VerseRef::new("kjv", "JHN", 3, 1))
+        );
+        assert_eq!(VerseRef::parse("kjv", "Nowhere 3:16"), None);
+        assert_eq!(VerseRef::parse("kjv", "John three"), None);
+    }
+
+    #[test]
+    fn uri_round_trips() {
+        let verse = VerseRef::new("kjv", "JHN", 3, 16);
+        assert_eq!(verse.to_uri(), "biblion://read/kjv/JHN/3/16");
+        assert_eq!(VerseRef::from_uri(&verse.to_uri()), Some(verse));
+    }
+
+    #[test]
+    fn uri_verse_is_optional() {
+        assert_eq!(
+            VerseRef::from_uri("biblion://read/kjv/jhn/3/"),
+            Some(VerseRef::new("kjv", "JHN", 3, 1))
+        );
+    }
+
+    #[test]
+    fn invalid_uris_are_rejected() {
+        for uri in [
+            "https://read/kjv/JHN/3/16",
+            "biblion://open/kjv/JHN/3/16",
+            "biblion://read/kjv/JHN",
+            "biblion://read/kjv/JHN/0/16",
+            "biblion://read/kjv/JHN/3/0",
+            "biblion://read/kjv/JHN/3/x",
+            "biblion://read/kjv/JHN/3/16/1",
+            "biblion://read//JHN/3/16",
+        ] {
+            assert_eq!(VerseRef::from_uri(uri), None, "{uri}");
+        }
+    }
+
+    #[test]
+    fn new_orders_the_verses() {
+        let passage = john_3(RangeInclusive::new(18, 16));
+        assert_eq!((passage.start(), passage.end()), (16, 18));
+    }
+
+    #[test]
+    fn overlapping_passages() {
+        assert!(john_3(16..=18).overlaps(&john_3(18..=20)));
+        assert!(!john_3(16..=18).overlaps(&john_3(19..=20)));
+        assert!(!john_3(16..=18).overlaps(&PassageRef::new("kjv", "JHN", 4, 16..=18)));
+        assert!(!john_3(16..=18).overlaps(&PassageRef::new("web", "JHN", 3, 16..=18)));
+    }
+
+    #[test]
+    fn intersection_keeps_the_shared_verses() {
+        assert_eq!(
+            john_3(16..=18).intersection(&john_3(17..=20)),
+            Some(john_3(17..=18))
+        );
+        assert_eq!(john_3(16..=18).intersection(&john_3(19..=20)), None);
+    }
+
+    #[test]
+    fn union_joins_overlapping_and_following_passages() {
+        assert_eq!(
+            john_3(16..=18).union(&john_3(17..=20)),
+            Some(john_3(16..=20))
+        );
+        assert_eq!(
+            john_3(19..=20).union(&john_3(16..=18)),
+            Some(john_3(16..=20))
+        );
+        assert_eq!(john_3(16..=17).union(&john_3(19..=20)), None);
+    }
+
+    #[test]
+    fn parses_chapters_and_verses() {
+        assert_eq!(
+            PassageRef::parse(STANDARD_BIBLE, "John 3"),
+            Some(PassageRef::whole_chapters(STANDARD_BIBLE, "JHN", 3..=3))
+        );
+        assert_eq!(PassageRef::parse("kjv", "Jn 3:16"), Some(john_3(16..=16)));
+        assert_eq!(
+            PassageRef::parse("kjv", "Juan 3:18-16"),
+            Some(john_3(16..=18))
+        );
+        assert_eq!(
+            PassageRef::parse("kjv", "1 John 4:7 - 8"),
+            Some(PassageRef::new("kjv", "1JN", 4, 7..=8))
+        );
+    }
+
+    #[test]
+    fn parses_ranges_across_chapters() {
+        assert_eq!(
+            PassageRef::parse(STANDARD_BIBLE, "Gen 1:1-2:3"),
+            PassageRef::span(STANDARD_BIBLE, "GEN", (1, 1), (2, 3))
+        );
+        assert_eq!(
+            PassageRef::parse(STANDARD_BIBLE, "Ps 1-3"),
+            Some(PassageRef::whole_chapters(STANDARD_BIBLE, "PSA", 1..=3))
+        );
+        assert_eq!(PassageRef::parse(STANDARD_BIBLE, "Gen 2:3-1:1"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_passages() {
+        for input in [
+            "John",
+            "Nowhere 3",
+            "John 0",
+            "John 3:",
+            "John 3-4:2",
+            "John x:1",
+        ] {
+            assert_eq!(PassageRef::parse(STANDARD_BIBLE, input), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn parses_lists_of_verses_and_chapters() {
+        let genesis_1 = |verses| PassageRef::new(STANDARD_BIBLE, "GEN", 1, verses);
+        assert_eq!(
+            PassageRef::parse_many(STANDARD_BIBLE, "Gen 1:1,3,5-7"),
+            Some(vec![genesis_1(1..=1), genesis_1(3..=3), genesis_1(5..=7)])
+        );
+        assert_eq!(
+            PassageRef::parse_many(STANDARD_BIBLE, "Ps 1, 3"),
+            Some(vec![
+                PassageRef::whole_chapters(STANDARD_BIBLE, "PSA", 1..=1),
+                PassageRef::whole_chapters(STANDARD_BIBLE, "PSA", 3..=3),
+            ])
+        );
+        assert_eq!(
+            PassageRef::parse_many(STANDARD_BIBLE, "Gen 1:31,2:1-3"),
+            Some(vec![
+                genesis_1(31..=31),
+                PassageRef::new(STANDARD_BIBLE, "GEN", 2, 1..=3),
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_passages_separated_by_semicolons() {
+        assert_eq!(
+            PassageRef::parse_list("kjv", "John 3:16,18; Rom 8"),
+            Ok(vec![
+                john_3(16..=16),
+                john_3(18..=18),
+                PassageRef::whole_chapters("kjv", "ROM", 8..=8),
+            ])
+        );
+        assert_eq!(
+            PassageRef::parse_list("kjv", "John 3; Nowhere 1"),
+            Err("Nowhere 1".to_string())
+        );
+    }
+
+    #[test]
+    fn verses_of_passages_across_chapters() {
+        let passage = PassageRef::parse("kjv", "Gen 1:30-3:2").unwrap();
+        assert_eq!(passage.verses_in(1), Some(30..=END_OF_CHAPTER));
+        assert_eq!(passage.verses_in(2), Some(1..=END_OF_CHAPTER));
+        assert_eq!(passage.verses_in(3), Some(1..=2));
+        assert_eq!(passage.verses_in(4), None);
+        let genesis = |chapter, verse| VerseRef::new("kjv", "GEN", chapter, verse);
+        assert!(passage.contains(&genesis(1, 31)));
+        assert!(!passage.contains(&genesis(1, 29)));
+        assert!(passage.contains(&genesis(2, 25)));
+        assert!(!passage.contains(&genesis(3, 3)));
+        assert!(!passage.contains(&genesis(4, 1)));
+        assert!(passage.overlaps(&PassageRef::whole_chapters("kjv", "GEN", 2..=2)));
+        assert_eq!(
+            passage.union(&PassageRef::new("kjv", "GEN", 3, 3..=5)),
+            PassageRef::span("kjv", "GEN", (1, 30), (3, 5))
+        );
+    }
+
+    #[test]
+    fn labels_round_trip() {
+        for input in [
+            "John 3",
+            "John 3:16",
+            "John 3:16-18",
+            "Genesis 1:1-2:3",
+            "Psalms 1-3",
+        ] {
+            let passage = PassageRef::parse(STANDARD_BIBLE, input).unwrap();
+            assert_eq!(passage.label(), input);
+            assert_eq!(
+                PassageRef::parse(STANDARD_BIBLE, &passage.label()),
+                Some(passage)
+            );
+        }
+    }
+
+    #[test]
+    fn both_ends_are_mapped_to_other_bibles() {
+        let catalog = Catalog::from_installed(
+            [("lat_vulgate".to_string(), vec!["Psalmi".to_string()])],
+            &BTreeMap::new(),
+        );
+        let passage = PassageRef::new("kjv", "PSA", 116, 9..=10);
+        assert_eq!(
+            passage.in_bible(&catalog, "lat_vulgate"),
+            PassageRef::span("lat_vulgate", "PSA", (114, 9), (115, 1))
+        );
+        // Psalm 116 of the KJV is split into Psalms 114 and 115 of the Vulgate
+        let psalm = PassageRef::whole_chapters(STANDARD_BIBLE, "PSA", 116..=116);
+        let mapped = psalm.in_bible(&catalog, "lat_vulgate").unwrap();
+        assert_eq!(mapped.location(), "114-115");
+        let psalm_10 = PassageRef::whole_chapters(STANDARD_BIBLE, "PSA", 10..=10);
+        assert_eq!(
+            psalm_10
+                .in_bible(&catalog, "lat_vulgate")
+                .map(|p| p.location()),
+            Some("9:22ff".to_string())
+        );
+        assert_eq!(
+            mapped.in_bible(&catalog, STANDARD_BIBLE),
+            Some(psalm.clone())
+        );
+    }
+
+    #[test]
+    fn stored_passages_are_checked() {
+        let passage = john_3(16..=18);
+        let json = serde_json::to_string(&passage).unwrap();
+        assert_eq!(serde_json::from_str::<PassageRef>(&json).unwrap(), passage);
+        let reversed = r#"{"bible_id":"kjv","book_id":"JHN","chapter":3,"start":18,"end":16}"#;
+        assert!(serde_json::from_str::<PassageRef>(reversed).is_err());
+        let across =
+            r#"{"bible_id":"kjv","book_id":"JHN","chapter":3,"start":18,"end_chapter":2,"end":16}"#;
+        assert!(serde_json::from_str::<PassageRef>(across).is_err());
+    }
+}
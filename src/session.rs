@@ -0,0 +1,151 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::Catalog;
+use crate::history::NavigationHistory;
+use crate::reference::VerseRef;
+use crate::utils::{data_dir, load_json, save_json};
+
+/// Passage open in a tab, with the search and the scroll it was left at
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+#[serde(default)]
+pub struct Tab {
+    pub verse: Option<VerseRef>,
+    pub search: String,
+    /// Vertical offset the chapter text was left at, scrolled to the verse when missing
+    pub scroll: Option<i32>,
+    /// Verses visited in the tab, to go back to them after the app is restarted
+    pub history: NavigationHistory<VerseRef>,
+}
+
+impl Tab {
+    pub fn at(verse: VerseRef) -> Self {
+        Self {
+            verse: Some(verse),
+            ..Default::default()
+        }
+    }
+
+    pub fn title(&self, catalog: &Catalog) -> String {
+        match &self.verse {
+            Some(verse) => verse.localized(catalog),
+            None if !self.search.trim().is_empty() => self.search.trim().to_string(),
+            None => "New tab".to_string(),
+        }
+    }
+}
+
+/// How long the session kept by `Session::save_later` waits for further changes before it is
+/// written
+const SAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// Last state of the session that is not written yet, with the time it is due to be written,
+/// see `Session::save_later`
+static PENDING: Mutex<Option<(Session, Instant)>> = Mutex::new(None);
+
+/// Tabs open when the app was closed, stored in the data dir
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[serde(default)]
+pub struct Session {
+    pub tabs: Vec<Tab>,
+    pub active: usize,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            tabs: vec![Tab::default()],
+            active: 0,
+        }
+    }
+}
+
+impl Session {
+    fn path() -> PathBuf {
+        data_dir(&["session.json"])
+    }
+
+    /// Loads the stored session, always with at least a tab
+    pub fn load() -> Self {
+        let mut session: Self = load_json(&Self::path());
+        if session.tabs.is_empty() {
+            session.tabs.push(Tab::default());
+        }
+        session.active = session.active.min(session.tabs.len() - 1);
+        session
+    }
+
+    pub fn save(&self) {
+        // A pending state is older than this one and must not replace it later
+        PENDING.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Err(e) = save_json(&Self::path(), self) {
+            tracing::error!("Failed to save the session: {e}");
+        }
+    }
+
+    /// Keeps the session to be written once it stops changing for `SAVE_DELAY`, for changes
+    /// too frequent to write every time, like the scroll of the reader. Every call pushes the
+    /// write back.
+    pub fn save_later(&self) {
+        *PENDING.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((self.clone(), Instant::now() + SAVE_DELAY));
+    }
+
+    /// Writes the session kept by `save_later` if it has not changed for `SAVE_DELAY`
+    pub fn flush_settled() {
+        let pending = {
+            let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+            let settled = pending
+                .as_ref()
+                .is_some_and(|(_, due)| *due <= Instant::now());
+            if settled { pending.take() } else { None }
+        };
+        if let Some((session, _)) = pending {
+            session.save();
+        }
+    }
+
+    /// Writes the session kept by `save_later` right away, for when Android pauses the app,
+    /// which may be killed afterwards
+    pub fn flush() {
+        let pending = PENDING.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some((session, _)) = pending {
+            session.save();
+        }
+    }
+
+    pub fn active_tab(&self) -> &Tab {
+        &self.tabs[self.active]
+    }
+
+    pub fn active_tab_mut(&mut self) -> &mut Tab {
+        &mut self.tabs[self.active]
+    }
+
+    /// Adds a tab after the active one and makes it active
+    pub fn open(&mut self, tab: Tab) {
+        self.active += 1;
+        self.tabs.insert(self.active, tab);
+    }
+
+    /// Closes the tab, the last one being replaced by an empty tab. Returns whether the active
+    /// tab changed
+    pub fn close(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() {
+            return false;
+        }
+        self.tabs.remove(index);
+        if self.tabs.is_empty() {
+            self.tabs.push(Tab::default());
+            return true;
+        }
+        let was_active = index == self.active;
+        if index < self.active || self.active == self.tabs.len() {
+            self.active -= 1;
+        }
+        was_active
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::Canon;
+use crate::citation::CitationFormat;
+use crate::presentation::PresentationStyle;
+use crate::storage::StorageLocation;
+use crate::utils::{data_dir, load_json, save_json};
+use crate::versification::Versification;
+
+/// Preferences of the user, stored in the data dir
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+#[serde(default)]
+pub struct Settings {
+    /// Format used when copying verses
+    pub citation: CitationFormat,
+    /// Look of the projector window
+    pub presentation: PresentationStyle,
+    /// Canon the search results are limited to, every book when missing
+    pub search_canon: Option<Canon>,
+    /// How the search results and the reader share the window
+    pub panes: PaneLayout,
+    /// Where the search indexes are kept
+    pub index_storage: StorageLocation,
+    /// Verse numbering chosen for some Bibles, the others use the one guessed from their id
+    pub versifications: BTreeMap<String, Versification>,
+}
+
+/// Placement of the reader next to the search results
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Copy, Debug)]
+pub enum SplitDirection {
+    /// Search results on the left, reader on the right
+    #[default]
+    Horizontal,
+    /// Search results on top, reader below
+    Vertical,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct PaneLayout {
+    pub direction: SplitDirection,
+    /// Share of the split given to the reader, in percent
+    pub reader_size: f32,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            direction: SplitDirection::default(),
+            reader_size: 60.,
+        }
+    }
+}
+
+impl PaneLayout {
+    /// Neither pane can be made smaller than this share of the split
+    pub const MIN_SIZE: f32 = 20.;
+
+    pub fn set_reader_size(&mut self, size: f32) {
+        self.reader_size = size.clamp(Self::MIN_SIZE, 100. - Self::MIN_SIZE);
+    }
+}
+
+impl Settings {
+    fn path() -> PathBuf {
+        data_dir(&["settings.json"])
+    }
+
+    pub fn load() -> Self {
+        let mut settings: Self = load_json(&Self::path());
+        let reader_size = settings.panes.reader_size;
+        settings.panes.set_reader_size(reader_size);
+        settings
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = save_json(&Self::path(), self) {
+            tracing::error!("Failed to save settings: {e}");
+        }
+    }
+}
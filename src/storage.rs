@@ -0,0 +1,87 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::data_dir;
+
+/// Where the search indexes of the Bibles and commentaries are kept, the biggest files of the
+/// app
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum StorageLocation {
+    /// Private storage of the app, next to the rest of its data
+    #[default]
+    Internal,
+    /// Folder of the app in the shared storage of the device, usually bigger
+    External,
+}
+
+impl StorageLocation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StorageLocation::Internal => "Internal storage",
+            StorageLocation::External => "External storage",
+        }
+    }
+
+    /// Folder of the indexes in this location, missing when the device has no external storage
+    pub fn index_root(&self) -> Option<PathBuf> {
+        match self {
+            StorageLocation::Internal => Some(data_dir(&["index"])),
+            StorageLocation::External => external_data_dir().map(|dir| dir.join("index")),
+        }
+    }
+}
+
+/// Folder of the app in the shared storage, which only Android provides. It can be missing
+/// when the storage is not mounted.
+pub fn external_data_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "android")]
+    return crate::android::app().and_then(|app| app.external_data_path());
+    #[cfg(not(target_os = "android"))]
+    None
+}
+
+/// Folder of the search indexes in `location`, the internal storage while the external one is
+/// not available. It is resolved when the app starts and after the indexes are moved, and kept
+/// in the `AppState`.
+pub fn index_dir(location: StorageLocation) -> PathBuf {
+    location.index_root().unwrap_or_else(|| {
+        tracing::warn!(
+            "{} is not available, using the internal one",
+            location.label()
+        );
+        data_dir(&["index"])
+    })
+}
+
+/// Moves the indexes to another location. Both are usually different filesystems, so the files
+/// are copied and then removed from the old location. Nothing may keep the indexes open while
+/// they are moved, and they have to be opened again from the new location afterwards.
+pub fn move_indexes(from: StorageLocation, to: StorageLocation) -> io::Result<()> {
+    let (Some(source), Some(target)) = (from.index_root(), to.index_root()) else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "External storage is not available",
+        ));
+    };
+    if source == target || !source.exists() {
+        return Ok(());
+    }
+    copy_dir(&source, &target)?;
+    std::fs::remove_dir_all(&source)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use crate::APP_NAME;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::env;
 use std::path::{Path, PathBuf};
 
@@ -16,7 +18,40 @@ pub fn data_dir(sub_dir: &[impl AsRef<Path>]) -> PathBuf {
         .fold(data_path.clone(), |path, b| path.join(b))
 }
 
+/// Reads a JSON file of user data, falling back to the default value when it does not exist
+/// or cannot be parsed
+pub fn load_json<T: DeserializeOwned + Default>(path: &Path) -> T {
+    let Ok(bytes) = std::fs::read(path) else {
+        return T::default();
+    };
+    serde_json::from_slice(&bytes)
+        .inspect_err(|e| tracing::error!("Failed to parse {}: {e}", path.display()))
+        .unwrap_or_default()
+}
+
+/// Writes a JSON file of user data, replacing it atomically
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> std::io::Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    std::fs::rename(tmp, path)
+}
+
+/// Days since the Unix epoch in local time, used to schedule things by day so the day changes
+/// at the user's midnight
+pub fn today() -> u64 {
+    let now = chrono::Local::now().naive_local();
+    u64::try_from(now.and_utc().timestamp() / 86_400).unwrap_or_default()
+}
+
 fn app_data_dir() -> PathBuf {
+    // Private folder of the app given by the activity, removed along with the app
+    #[cfg(target_os = "android")]
+    {
+        return crate::android::app()
+            .and_then(|app| app.internal_data_path())
+            .unwrap_or_else(|| env::temp_dir().join(APP_NAME));
+    }
+
     #[cfg(target_os = "windows")]
     {
         if let Some(appdata) = env::var_os("APPDATA") {
@@ -0,0 +1,518 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+
+/// Verse numbering followed by a Bible. References are converted between schemes through the
+/// KJV numbering, which is also the one used by the cross reference data and the modules.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Versification {
+    #[default]
+    Kjv,
+    Vulgate,
+    Lxx,
+    Hebrew,
+    Synodal,
+}
+
+/// Last verse of open ended spans, larger than any chapter
+const LAST: u32 = 999;
+
+/// Verses `start..=end` of a chapter
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+struct Span {
+    chapter: u32,
+    start: u32,
+    end: u32,
+}
+
+impl Span {
+    const fn new(chapter: u32, start: u32, end: u32) -> Self {
+        Self {
+            chapter,
+            start,
+            end,
+        }
+    }
+
+    fn contains(&self, chapter: u32, verse: u32) -> bool {
+        self.chapter == chapter && (self.start..=self.end).contains(&verse)
+    }
+
+    /// Verse at the same position in `other`, the last one of `other` when it is shorter
+    fn translate(&self, verse: u32, other: &Span) -> (u32, u32) {
+        (
+            other.chapter,
+            (other.start + (verse - self.start)).min(other.end),
+        )
+    }
+}
+
+/// Verses as `(chapter, start, end)`, the way the tables below are written
+type Verses = (u32, u32, u32);
+
+/// Verses numbered differently in a scheme than in the KJV
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+struct Mapping {
+    book: &'static str,
+    standard: Span,
+    scheme: Span,
+}
+
+impl Mapping {
+    const fn new(book: &'static str, standard: Verses, scheme: Verses) -> Self {
+        Self {
+            book,
+            standard: Span::new(standard.0, standard.1, standard.2),
+            scheme: Span::new(scheme.0, scheme.1, scheme.2),
+        }
+    }
+}
+
+/// Chapter divisions of the Hebrew text that differ from the KJV, as
+/// `(book, (chapter, start, end) in the KJV, (chapter, start, end) in the Hebrew text)`
+#[rustfmt::skip]
+const HEBREW_CHAPTERS: &[(&str, Verses, Verses)] = &[
+    ("GEN", (31, 55, 55), (32, 1, 1)),
+    ("GEN", (32, 1, 32), (32, 2, 33)),
+    ("EXO", (8, 1, 4), (7, 26, 29)),
+    ("EXO", (8, 5, 32), (8, 1, 28)),
+    ("EXO", (22, 1, 1), (21, 37, 37)),
+    ("EXO", (22, 2, 31), (22, 1, 30)),
+    ("LEV", (6, 1, 7), (5, 20, 26)),
+    ("LEV", (6, 8, 30), (6, 1, 23)),
+    ("NUM", (16, 36, 50), (17, 1, 15)),
+    ("NUM", (17, 1, 13), (17, 16, 28)),
+    ("NUM", (29, 40, 40), (30, 1, 1)),
+    ("NUM", (30, 1, 16), (30, 2, 17)),
+    ("DEU", (12, 32, 32), (13, 1, 1)),
+    ("DEU", (13, 1, 18), (13, 2, 19)),
+    ("DEU", (22, 30, 30), (23, 1, 1)),
+    ("DEU", (23, 1, 25), (23, 2, 26)),
+    ("DEU", (29, 1, 1), (28, 69, 69)),
+    ("DEU", (29, 2, 29), (29, 1, 28)),
+    ("1SA", (23, 29, 29), (24, 1, 1)),
+    ("1SA", (24, 1, 22), (24, 2, 23)),
+    ("2SA", (18, 33, 33), (19, 1, 1)),
+    ("2SA", (19, 1, 43), (19, 2, 44)),
+    ("1KI", (4, 21, 34), (5, 1, 14)),
+    ("1KI", (5, 1, 18), (5, 15, 32)),
+    ("2KI", (11, 21, 21), (12, 1, 1)),
+    ("2KI", (12, 1, 21), (12, 2, 22)),
+    ("1CH", (6, 1, 15), (5, 27, 41)),
+    ("1CH", (6, 16, 81), (6, 1, 66)),
+    ("2CH", (2, 1, 1), (1, 18, 18)),
+    ("2CH", (2, 2, 18), (2, 1, 17)),
+    ("2CH", (14, 1, 1), (13, 23, 23)),
+    ("2CH", (14, 2, 15), (14, 1, 14)),
+    ("NEH", (4, 1, 6), (3, 33, 38)),
+    ("NEH", (4, 7, 23), (4, 1, 17)),
+    ("NEH", (9, 38, 38), (10, 1, 1)),
+    ("NEH", (10, 1, 39), (10, 2, 40)),
+    ("JOB", (41, 1, 8), (40, 25, 32)),
+    ("JOB", (41, 9, 34), (41, 1, 26)),
+    ("ECC", (5, 1, 1), (4, 17, 17)),
+    ("ECC", (5, 2, 20), (5, 1, 19)),
+    ("SNG", (6, 13, 13), (7, 1, 1)),
+    ("SNG", (7, 1, 13), (7, 2, 14)),
+    ("ISA", (9, 1, 1), (8, 23, 23)),
+    ("ISA", (9, 2, 21), (9, 1, 20)),
+    ("ISA", (64, 1, 1), (63, 19, 19)),
+    ("ISA", (64, 2, 12), (64, 1, 11)),
+    ("JER", (9, 1, 1), (8, 23, 23)),
+    ("JER", (9, 2, 26), (9, 1, 25)),
+    ("EZK", (20, 45, 49), (21, 1, 5)),
+    ("EZK", (21, 1, 32), (21, 6, 37)),
+    ("DAN", (4, 1, 3), (3, 31, 33)),
+    ("DAN", (4, 4, 37), (4, 1, 34)),
+    ("DAN", (5, 31, 31), (6, 1, 1)),
+    ("DAN", (6, 1, 28), (6, 2, 29)),
+    ("HOS", (1, 10, 11), (2, 1, 2)),
+    ("HOS", (2, 1, 23), (2, 3, 25)),
+    ("HOS", (11, 12, 12), (12, 1, 1)),
+    ("HOS", (12, 1, 14), (12, 2, 15)),
+    ("HOS", (13, 16, 16), (14, 1, 1)),
+    ("HOS", (14, 1, 9), (14, 2, 10)),
+    ("JOL", (2, 28, 32), (3, 1, 5)),
+    ("JOL", (3, 1, 21), (4, 1, 21)),
+    ("JON", (1, 17, 17), (2, 1, 1)),
+    ("JON", (2, 1, 10), (2, 2, 11)),
+    ("MIC", (5, 1, 1), (4, 14, 14)),
+    ("MIC", (5, 2, 15), (5, 1, 14)),
+    ("NAM", (1, 15, 15), (2, 1, 1)),
+    ("NAM", (2, 1, 13), (2, 2, 14)),
+    ("ZEC", (1, 18, 21), (2, 1, 4)),
+    ("ZEC", (2, 1, 13), (2, 5, 17)),
+    ("MAL", (4, 1, 6), (3, 19, 24)),
+];
+
+/// Chapters the Septuagint divides like the Hebrew text
+const LXX_CHAPTERS: &[&str] = &["JOL", "MAL"];
+
+/// Psalms whose title is numbered as one verse outside the KJV
+const ONE_VERSE_TITLES: &[u32] = &[
+    3, 4, 5, 6, 7, 8, 9, 12, 13, 18, 19, 20, 21, 22, 30, 31, 34, 36, 38, 39, 40, 41, 42, 44, 45,
+    46, 47, 48, 49, 53, 55, 56, 57, 58, 59, 61, 62, 63, 64, 65, 67, 68, 69, 70, 75, 76, 77, 80, 81,
+    83, 84, 85, 88, 89, 92, 102, 108, 140, 142,
+];
+
+/// Psalms whose title is numbered as two verses outside the KJV
+const TWO_VERSE_TITLES: &[u32] = &[51, 52, 54, 60];
+
+fn title_verses(psalm: u32) -> u32 {
+    if TWO_VERSE_TITLES.contains(&psalm) {
+        2
+    } else if ONE_VERSE_TITLES.contains(&psalm) {
+        1
+    } else {
+        0
+    }
+}
+
+/// Psalms numbered with their titles, and with the Greek numbering when `greek` is set, which
+/// joins 9-10 and 114-115 and splits 116 and 147
+fn psalms(greek: bool) -> Vec<Mapping> {
+    let mut mappings = Vec::new();
+    let mut titles = Vec::new();
+    for psalm in 1..=150 {
+        let title = title_verses(psalm);
+        let chapter = match psalm {
+            _ if !greek => psalm,
+            11..=113 | 117..=146 => psalm - 1,
+            _ => psalm,
+        };
+        match (greek, psalm) {
+            (true, 9) => {
+                mappings.push(Mapping::new("PSA", (9, 1, 20), (9, 1 + title, 20 + title)));
+            }
+            (true, 10) => mappings.push(Mapping::new("PSA", (10, 1, LAST), (9, 22, LAST))),
+            (true, 114) => mappings.push(Mapping::new("PSA", (114, 1, 8), (113, 1, 8))),
+            (true, 115) => mappings.push(Mapping::new("PSA", (115, 1, LAST), (113, 9, LAST))),
+            (true, 116) => {
+                mappings.push(Mapping::new("PSA", (116, 1, 9), (114, 1, 9)));
+                mappings.push(Mapping::new("PSA", (116, 10, LAST), (115, 1, LAST)));
+            }
+            (true, 147) => {
+                mappings.push(Mapping::new("PSA", (147, 1, 11), (146, 1, 11)));
+                mappings.push(Mapping::new("PSA", (147, 12, LAST), (147, 1, LAST)));
+            }
+            _ if title == 0 && chapter == psalm => {}
+            _ => mappings.push(Mapping::new(
+                "PSA",
+                (psalm, 1, LAST),
+                (chapter, 1 + title, LAST + title),
+            )),
+        }
+        // Titles only map to the first verse of the KJV, after the verses so the KJV verse
+        // maps to the text and not to the title
+        if title > 0 {
+            titles.push(Mapping::new("PSA", (psalm, 1, 1), (chapter, 1, title)));
+        }
+    }
+    mappings.extend(titles);
+    mappings
+}
+
+fn chapters(books: Option<&[&str]>) -> impl Iterator<Item = Mapping> {
+    HEBREW_CHAPTERS
+        .iter()
+        .filter(move |(book, ..)| books.is_none_or(|books| books.contains(book)))
+        .map(|(book, standard, scheme)| Mapping::new(book, *standard, *scheme))
+}
+
+/// Doxology of Romans placed at the end of chapter 14
+const ROMANS_DOXOLOGY: Mapping = Mapping::new("ROM", (16, 25, 27), (14, 24, 26));
+
+/// Last verse of 3 John split in two
+const THIRD_JOHN_ENDING: Mapping = Mapping::new("3JN", (1, 14, 14), (1, 14, 15));
+
+static HEBREW: LazyLock<Vec<Mapping>> = LazyLock::new(|| {
+    let mut mappings = chapters(None).collect::<Vec<_>>();
+    mappings.extend(psalms(false));
+    mappings
+});
+
+static LXX: LazyLock<Vec<Mapping>> = LazyLock::new(|| {
+    let mut mappings = chapters(Some(LXX_CHAPTERS)).collect::<Vec<_>>();
+    mappings.extend(psalms(true));
+    mappings
+});
+
+static VULGATE: LazyLock<Vec<Mapping>> = LazyLock::new(|| {
+    let mut mappings = psalms(true);
+    mappings.push(THIRD_JOHN_ENDING);
+    mappings
+});
+
+static SYNODAL: LazyLock<Vec<Mapping>> = LazyLock::new(|| {
+    let mut mappings = psalms(true);
+    mappings.extend([ROMANS_DOXOLOGY, THIRD_JOHN_ENDING]);
+    mappings
+});
+
+/// Where `chapter` ends once its verses are moved by the `(from, to)` spans, as the chapter
+/// and its verse, the verse being `None` when that chapter ends there too
+fn chapter_end(spans: &[(Span, Span)], chapter: u32) -> (u32, Option<u32>) {
+    if let Some((from, to)) = spans
+        .iter()
+        .filter(|(from, _)| from.chapter == chapter)
+        .max_by_key(|(from, _)| from.end)
+    {
+        return (to.chapter, (from.end < LAST).then_some(to.end));
+    }
+    // Verses of later chapters moved to the end of this one
+    let moved = spans
+        .iter()
+        .filter(|(from, to)| to.chapter == chapter && from.chapter > chapter)
+        .map(|(_, to)| to.start)
+        .min();
+    (chapter, moved.map(|start| start - 1))
+}
+
+impl Versification {
+    pub const ALL: [Versification; 5] = [
+        Versification::Kjv,
+        Versification::Vulgate,
+        Versification::Lxx,
+        Versification::Hebrew,
+        Versification::Synodal,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Versification::Kjv => "KJV",
+            Versification::Vulgate => "Vulgate",
+            Versification::Lxx => "Septuagint",
+            Versification::Hebrew => "Hebrew",
+            Versification::Synodal => "Synodal",
+        }
+    }
+
+    /// Guesses the scheme of an installed Bible from its id, e.g. `rus_synodal` or `lat_vulgate`.
+    /// Bibles that are not recognized are assumed to follow the KJV.
+    pub fn detect(bible_id: &str) -> Self {
+        let id = bible_id.to_lowercase();
+        let has = |patterns: &[&str]| patterns.iter().any(|p| id.contains(p));
+        if has(&["synod"]) || id.starts_with("rus") || id.starts_with("ukr") {
+            Versification::Synodal
+        } else if has(&["vulg"]) || id.starts_with("lat") {
+            Versification::Vulgate
+        } else if has(&["lxx", "sept"]) {
+            Versification::Lxx
+        } else if has(&["wlc", "bhs", "leningrad", "masoretic"])
+            || id.starts_with("heb")
+            || id.starts_with("hbo")
+        {
+            Versification::Hebrew
+        } else {
+            Versification::Kjv
+        }
+    }
+
+    fn mappings(&self) -> &'static [Mapping] {
+        match self {
+            Versification::Kjv => &[],
+            Versification::Vulgate => &VULGATE,
+            Versification::Lxx => &LXX,
+            Versification::Hebrew => &HEBREW,
+            Versification::Synodal => &SYNODAL,
+        }
+    }
+
+    /// Chapter and verse in the KJV numbering of a verse numbered in this scheme
+    pub fn to_standard(&self, book_id: &str, chapter: u32, verse: u32) -> (u32, u32) {
+        self.mappings()
+            .iter()
+            .find(|m| m.book.eq_ignore_ascii_case(book_id) && m.scheme.contains(chapter, verse))
+            .map_or((chapter, verse), |m| m.scheme.translate(verse, &m.standard))
+    }
+
+    /// Chapter and verse in this scheme of a verse numbered as in the KJV
+    pub fn from_standard(&self, book_id: &str, chapter: u32, verse: u32) -> (u32, u32) {
+        self.mappings()
+            .iter()
+            .find(|m| m.book.eq_ignore_ascii_case(book_id) && m.standard.contains(chapter, verse))
+            .map_or((chapter, verse), |m| m.standard.translate(verse, &m.scheme))
+    }
+
+    /// Chapters of a book in this scheme, from its `standard` chapters in the KJV. Chapters
+    /// keep their number unless their first verse is moved, and moved verses may start others.
+    pub fn chapters(&self, book_id: &str, standard: u32) -> u32 {
+        let moved = self
+            .mappings()
+            .iter()
+            .filter(|m| m.book.eq_ignore_ascii_case(book_id) && m.standard.chapter <= standard)
+            .map(|m| m.scheme.chapter);
+        (1..=standard)
+            .map(|chapter| self.from_standard(book_id, chapter, 1).0)
+            .chain(moved)
+            .max()
+            .unwrap_or(standard)
+    }
+
+    /// Spans of the book moved by this scheme, as `(standard, scheme)` or the other way around
+    fn spans(&self, book_id: &str, from_standard: bool) -> Vec<(Span, Span)> {
+        self.mappings()
+            .iter()
+            .filter(|m| m.book.eq_ignore_ascii_case(book_id))
+            .map(|m| {
+                if from_standard {
+                    (m.standard, m.scheme)
+                } else {
+                    (m.scheme, m.standard)
+                }
+            })
+            .collect()
+    }
+
+    /// Where a chapter of this scheme ends in the `to` scheme, as the chapter and its verse,
+    /// the verse being `None` when that chapter ends there too
+    pub fn map_chapter_end(
+        &self,
+        to: Versification,
+        book_id: &str,
+        chapter: u32,
+    ) -> (u32, Option<u32>) {
+        if *self == to {
+            return (chapter, None);
+        }
+        match chapter_end(&self.spans(book_id, false), chapter) {
+            (chapter, None) => chapter_end(&to.spans(book_id, true), chapter),
+            (chapter, Some(verse)) => {
+                let (chapter, verse) = to.from_standard(book_id, chapter, verse);
+                (chapter, Some(verse))
+            }
+        }
+    }
+
+    /// Chapter and verse in the `to` scheme of a verse numbered in this one
+    pub fn map(&self, to: Versification, book_id: &str, chapter: u32, verse: u32) -> (u32, u32) {
+        if *self == to {
+            return (chapter, verse);
+        }
+        let (chapter, verse) = self.to_standard(book_id, chapter, verse);
+        to.from_standard(book_id, chapter, verse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verses of the KJV in the chapters a scheme numbers differently
+    fn assert_round_trips(scheme: Versification, book_id: &str, chapters: &[(u32, u32)]) {
+        for (chapter, verses) in chapters {
+            for verse in 1..=*verses {
+                let (c, v) = scheme.from_standard(book_id, *chapter, verse);
+                assert_eq!(
+                    scheme.to_standard(book_id, c, v),
+                    (*chapter, verse),
+                    "{scheme:?} {book_id} {chapter}:{verse} numbered {c}:{v}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn greek_psalms_round_trip() {
+        let psalms = [(9, 20), (10, 18), (114, 8), (115, 18), (116, 19), (147, 20)];
+        for scheme in [
+            Versification::Vulgate,
+            Versification::Lxx,
+            Versification::Synodal,
+        ] {
+            assert_round_trips(scheme, "PSA", &psalms);
+        }
+    }
+
+    #[test]
+    fn greek_psalms_join_and_split() {
+        let vulgate = Versification::Vulgate;
+        // Psalm 9 gains its title as the first verse
+        assert_eq!(vulgate.from_standard("PSA", 9, 1), (9, 2));
+        assert_eq!(vulgate.from_standard("PSA", 10, 1), (9, 22));
+        assert_eq!(vulgate.from_standard("PSA", 115, 1), (113, 9));
+        assert_eq!(vulgate.from_standard("PSA", 116, 10), (115, 1));
+        assert_eq!(vulgate.from_standard("PSA", 147, 11), (146, 11));
+        assert_eq!(vulgate.from_standard("PSA", 147, 12), (147, 1));
+    }
+
+    #[test]
+    fn hebrew_chapters_round_trip() {
+        for scheme in [Versification::Hebrew, Versification::Lxx] {
+            assert_round_trips(scheme, "MAL", &[(3, 18), (4, 6)]);
+            assert_round_trips(scheme, "JOL", &[(2, 32), (3, 21)]);
+        }
+        assert_round_trips(Versification::Hebrew, "PSA", &[(9, 20), (10, 18), (51, 19)]);
+    }
+
+    #[test]
+    fn hebrew_chapters_are_divided_differently() {
+        for scheme in [Versification::Hebrew, Versification::Lxx] {
+            assert_eq!(scheme.from_standard("MAL", 4, 1), (3, 19));
+            assert_eq!(scheme.from_standard("MAL", 4, 6), (3, 24));
+            assert_eq!(scheme.from_standard("JOL", 2, 28), (3, 1));
+            assert_eq!(scheme.from_standard("JOL", 3, 1), (4, 1));
+            assert_eq!(scheme.chapters("MAL", 4), 3);
+            assert_eq!(scheme.chapters("JOL", 3), 4);
+        }
+        // The Vulgate keeps the divisions of the KJV
+        assert_eq!(Versification::Vulgate.from_standard("MAL", 4, 1), (4, 1));
+    }
+
+    #[test]
+    fn chapter_ends_follow_moved_verses() {
+        let kjv = Versification::Kjv;
+        // Psalm 10 ends the Greek Psalm 9, Psalm 9 ends before it
+        assert_eq!(
+            kjv.map_chapter_end(Versification::Vulgate, "PSA", 10),
+            (9, None)
+        );
+        assert_eq!(
+            kjv.map_chapter_end(Versification::Vulgate, "PSA", 9),
+            (9, Some(21))
+        );
+        assert_eq!(
+            kjv.map_chapter_end(Versification::Vulgate, "PSA", 116),
+            (115, None)
+        );
+        assert_eq!(
+            Versification::Vulgate.map_chapter_end(kjv, "PSA", 113),
+            (115, None)
+        );
+        // Hebrew chapters that give their first verses to the one before, or take the last
+        // verse of the one before
+        assert_eq!(
+            kjv.map_chapter_end(Versification::Hebrew, "MAL", 4),
+            (3, Some(24))
+        );
+        assert_eq!(
+            kjv.map_chapter_end(Versification::Hebrew, "EXO", 7),
+            (7, Some(25))
+        );
+        assert_eq!(
+            kjv.map_chapter_end(Versification::Hebrew, "GEN", 31),
+            (32, Some(1))
+        );
+        assert_eq!(
+            Versification::Hebrew.map_chapter_end(kjv, "GEN", 31),
+            (31, Some(54))
+        );
+        assert_eq!(kjv.map_chapter_end(kjv, "PSA", 10), (10, None));
+    }
+
+    #[test]
+    fn kjv_keeps_the_numbers() {
+        assert_eq!(Versification::Kjv.from_standard("PSA", 10, 1), (10, 1));
+        assert_eq!(Versification::Kjv.to_standard("MAL", 4, 6), (4, 6));
+    }
+
+    #[test]
+    fn detects_schemes_from_ids() {
+        assert_eq!(Versification::detect("rus_synodal"), Versification::Synodal);
+        assert_eq!(Versification::detect("lat_vulgate"), Versification::Vulgate);
+        assert_eq!(Versification::detect("grc_lxx"), Versification::Lxx);
+        assert_eq!(Versification::detect("hbo_wlc"), Versification::Hebrew);
+        assert_eq!(Versification::detect("spa_rv1960"), Versification::Kjv);
+    }
+}